@@ -1,9 +1,16 @@
 mod setup;
 
+use ff::Field;
 use gungraun::{library_benchmark, library_benchmark_group, main};
 use pasta_curves::{EpAffine, Fp, Fq};
-use ragu_arithmetic::{Domain, dot, eval, factor, geosum, mul, poly_with_roots};
-use setup::{f, setup_domain_ell, setup_domain_fft, setup_rng, setup_with_rng, vec_affine, vec_f};
+use ragu_arithmetic::{
+    Domain, FixedBaseTable, dot, eval, factor, fold_vectors, geosum, mul, poly_with_roots,
+    scale_by_powers,
+};
+use setup::{
+    f, setup_domain_ell, setup_domain_fft, setup_fixed_base, setup_rng, setup_with_rng, vec_affine,
+    vec_f, vecs_f,
+};
 use std::hint::black_box;
 
 #[library_benchmark(setup = setup_rng)]
@@ -12,14 +19,21 @@ use std::hint::black_box;
     ((vec_f::< 256, Fq>, vec_affine::< 256>)),
     ((vec_f::< 1024, Fq>, vec_affine::< 1024>)),
     ((vec_f::< 4096, Fq>, vec_affine::< 4096>)),
+    ((vec_f::< 8192, Fq>, vec_affine::< 8192>)),
 )]
 fn msm_mul((coeffs, bases): (Vec<Fq>, Vec<EpAffine>)) {
     black_box(mul(coeffs.iter(), bases.iter()));
 }
 
+#[library_benchmark(setup = setup_fixed_base)]
+#[benches::multiple(64, 256, 1024, 4096)]
+fn msm_fixed_base((coeffs, table): (Vec<Fq>, FixedBaseTable<EpAffine>)) {
+    black_box(table.mul(coeffs.iter().enumerate()));
+}
+
 library_benchmark_group!(
     name = msm_ops;
-    benchmarks = msm_mul
+    benchmarks = msm_mul, msm_fixed_base
 );
 
 #[library_benchmark(setup = setup_domain_fft)]
@@ -75,16 +89,44 @@ library_benchmark_group!(
     benchmarks = with_roots, poly_eval, poly_factor
 );
 
+// The 2048 and 8192 element cases match the coefficient vectors of structured
+// polynomials and the coefficients of unstructured polynomials for `R<13>`.
+// Compare runs with and without the `batched-kernels` feature to measure the
+// batched field kernels.
+
 #[library_benchmark(setup = setup_rng)]
 #[benches::with_setup(
     ((vec_f::< 256, Fp>, vec_f::< 256, Fp>)),
+    ((vec_f::< 2048, Fp>, vec_f::< 2048, Fp>)),
     ((vec_f::< 4096, Fp>, vec_f::< 4096, Fp>)),
+    ((vec_f::< 8192, Fp>, vec_f::< 8192, Fp>)),
     ((vec_f::< 65536, Fp>, vec_f::< 65536, Fp>)),
 )]
 fn field_dot((a, b): (Vec<Fp>, Vec<Fp>)) {
     black_box(dot(&a, &b));
 }
 
+#[library_benchmark(setup = setup_rng)]
+#[benches::with_setup(
+    ((vecs_f::<2, 2048, Fp>, f::<Fp>)),
+    ((vecs_f::<8, 2048, Fp>, f::<Fp>)),
+    ((vecs_f::<8, 8192, Fp>, f::<Fp>)),
+)]
+fn field_fold_vectors((vectors, scale): (Vec<Vec<Fp>>, Fp)) {
+    let vectors: Vec<&[Fp]> = vectors.iter().map(|v| &v[..]).collect();
+    black_box(fold_vectors(&vectors, scale));
+}
+
+#[library_benchmark(setup = setup_rng)]
+#[benches::with_setup(
+    ((vec_f::< 2048, Fp>, f::<Fp>)),
+    ((vec_f::< 8192, Fp>, f::<Fp>)),
+)]
+fn field_scale_by_powers((mut coeffs, z): (Vec<Fp>, Fp)) {
+    black_box(scale_by_powers(coeffs.iter_mut(), Fp::ONE, z));
+    black_box(coeffs);
+}
+
 #[library_benchmark(setup = setup_with_rng)]
 #[benches::with_setup((256, (f,)), (4096, (f,)))]
 fn field_geosum((n, (r,)): (usize, (Fp,))) {
@@ -93,7 +135,7 @@ fn field_geosum((n, (r,)): (usize, (Fp,))) {
 
 library_benchmark_group!(
     name = field_ops;
-    benchmarks = field_dot, field_geosum
+    benchmarks = field_dot, field_fold_vectors, field_scale_by_powers, field_geosum
 );
 
 main!(
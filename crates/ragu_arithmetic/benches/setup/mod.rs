@@ -1,7 +1,7 @@
 use ff::Field;
 use pasta_curves::group::prime::PrimeCurveAffine;
 use pasta_curves::{EpAffine, Fp, Fq};
-use ragu_arithmetic::Domain;
+use ragu_arithmetic::{Domain, FixedBaseTable};
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 
@@ -55,6 +55,10 @@ pub fn vec_f<const N: usize, F: Field>(rng: &mut StdRng) -> Vec<F> {
     (0..N).map(|_| F::random(&mut *rng)).collect()
 }
 
+pub fn vecs_f<const K: usize, const N: usize, F: Field>(rng: &mut StdRng) -> Vec<Vec<F>> {
+    (0..K).map(|_| vec_f::<N, F>(rng)).collect()
+}
+
 pub fn vec_affine<const N: usize>(rng: &mut StdRng) -> Vec<EpAffine> {
     let g = EpAffine::generator();
     (0..N).map(|_| (g * Fq::random(&mut *rng)).into()).collect()
@@ -73,3 +77,11 @@ pub fn setup_domain_ell(k: u32) -> (Domain<Fp>, Fp, usize) {
     let n = domain.n();
     (domain, Fp::random(&mut rng), n)
 }
+
+pub fn setup_fixed_base(n: usize) -> (Vec<Fq>, FixedBaseTable<EpAffine>) {
+    let mut rng = StdRng::seed_from_u64(1234);
+    let coeffs = (0..n).map(|_| Fq::random(&mut rng)).collect();
+    let g = EpAffine::generator();
+    let bases: Vec<EpAffine> = (0..n).map(|_| (g * Fq::random(&mut rng)).into()).collect();
+    (coeffs, FixedBaseTable::new(&bases))
+}
@@ -0,0 +1,372 @@
+use ff::PrimeField;
+
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    domain::Domain,
+    fft::{FFTField, Ring, bitreverse},
+};
+
+/// Evaluation domain formed by the union of $m$ cosets $h_c H$ of a radix-2
+/// [`Domain`] $H$ of size $2^k$, where $h_c = g^c$ for $0 \leq c < m$ and $g$
+/// is [`PrimeField::MULTIPLICATIVE_GENERATOR`].
+///
+/// A field with 2-adicity $S$ only has radix-2 domains of up to $2^S$
+/// elements, which is a small number for fields like the secp256k1 base and
+/// scalar fields. Larger domains are built from cosets of the largest radix-2
+/// domain instead. Because $g$ generates the multiplicative group, the shifts
+/// $h_c$ fall in distinct cosets for every $m \leq (p - 1) / 2^S$. A domain
+/// that fits within $2^S$ elements is a single radix-2 domain ($m = 1$).
+///
+/// # Element placement
+///
+/// The $i$th [element](Self::element) is $h_c \Omega^{f(i')}$, where
+/// $i = c 2^S + i'$, $f(i')$ is the $S$-bit reversal of $i'$ and $\Omega$ is
+/// [`PrimeField::ROOT_OF_UNITY`]. The first $2^j$ elements therefore form the
+/// radix-2 domain of size $2^j$ for every $j \leq S$, and the position of an
+/// element does not depend on the size of the domain that contains it.
+///
+/// Evaluations and Lagrange coefficients are ordered by
+/// [slot](Self::slot), in which coset $c$ occupies slots $c 2^k$ through
+/// $(c + 1) 2^k - 1$ as $h_c \omega^0, h_c \omega^1, \ldots$ for the
+/// generator $\omega$ of $H$.
+pub struct CosetDomain<F: PrimeField> {
+    /// The radix-2 domain $H$ of size $2^k$.
+    subgroup: Domain<F>,
+    /// The shifts $h_c$.
+    shifts: Vec<F>,
+    /// $t_c = h_c^{2^k}$, the value of $X^{2^k}$ over the coset $h_c H$.
+    powers: Vec<F>,
+    /// $(2^k t_c \prod_{c' \neq c} (t_c - t_{c'}))^{-1}$.
+    weights: Vec<F>,
+}
+
+impl<F: PrimeField> CosetDomain<F> {
+    /// Initializes the smallest domain that contains the first `n`
+    /// [elements](Self::element).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds the number of elements that can be assigned
+    /// distinct cosets in the field.
+    pub fn new(n: usize) -> Self {
+        Self::with_max_log2(n, F::S)
+    }
+
+    /// Initializes the smallest domain that contains the first `n` elements,
+    /// using cosets of radix-2 domains of at most $2^{\text{max\_log2}}$
+    /// elements.
+    fn with_max_log2(n: usize, max_log2: u32) -> Self {
+        let log2_n = n.max(1).next_power_of_two().trailing_zeros();
+        let (k, m) = if log2_n <= max_log2 {
+            (log2_n, 1)
+        } else {
+            (max_log2, n.div_ceil(1 << max_log2))
+        };
+
+        let subgroup = Domain::new(k);
+        let shifts: Vec<F> =
+            core::iter::successors(Some(F::ONE), |h| Some(*h * F::MULTIPLICATIVE_GENERATOR))
+                .take(m)
+                .collect();
+        let powers: Vec<F> = shifts.iter().map(|h| h.pow([1 << k])).collect();
+
+        let mut weights: Vec<F> = powers
+            .iter()
+            .enumerate()
+            .map(|(c, t_c)| {
+                let product = powers
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != c)
+                    .fold(F::ONE, |acc, (_, t)| acc * (*t_c - t));
+                F::from(1 << k) * t_c * product
+            })
+            .collect();
+        {
+            let mut scratch = weights.clone();
+            ff::BatchInverter::invert_with_external_scratch(&mut weights, &mut scratch);
+        }
+        assert!(
+            weights.iter().all(|w| !bool::from(w.is_zero())),
+            "too many cosets for the field"
+        );
+
+        CosetDomain {
+            subgroup,
+            shifts,
+            powers,
+            weights,
+        }
+    }
+
+    /// Returns the $i$th element of any domain that contains at least $i + 1$
+    /// elements, as described in the [type-level
+    /// documentation](CosetDomain#element-placement).
+    pub fn element(i: usize) -> F {
+        Self::element_with_max_log2(i, F::S)
+    }
+
+    fn element_with_max_log2(i: usize, max_log2: u32) -> F {
+        let coset = (i as u64) >> max_log2;
+        let index = (i as u64) & ((1 << max_log2) - 1);
+        let rotation = bitreverse(index as u32, F::S);
+        F::MULTIPLICATIVE_GENERATOR.pow([coset]) * F::ROOT_OF_UNITY.pow([rotation.into()])
+    }
+
+    /// The number of elements $n = m 2^k$ in the domain.
+    pub fn n(&self) -> usize {
+        self.shifts.len() * self.subgroup.n()
+    }
+
+    /// The number $m$ of cosets of $H$ in the domain.
+    pub fn num_cosets(&self) -> usize {
+        self.shifts.len()
+    }
+
+    /// Returns the radix-2 domain $H$ of which this domain is a union of
+    /// cosets.
+    pub fn subgroup(&self) -> &Domain<F> {
+        &self.subgroup
+    }
+
+    /// Returns the values $t_c = h_c^{2^k}$ that $X^{2^k}$ takes over each
+    /// coset. The vanishing polynomial of the domain is
+    /// $Z(X) = \prod_c (X^{2^k} - t_c)$.
+    pub fn coset_powers(&self) -> &[F] {
+        &self.powers
+    }
+
+    /// Returns the slot of the $i$th [element](Self::element).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain has fewer than $i + 1$ elements.
+    pub fn slot(&self, i: usize) -> usize {
+        assert!(i < self.n());
+        let k = self.subgroup.log2_n();
+        let offset = i & ((1 << k) - 1);
+        (i - offset) | bitreverse(offset as u32, k) as usize
+    }
+
+    /// Returns true if `x` is an element of this domain.
+    pub fn contains(&self, x: F) -> bool {
+        let x_k = x.pow([self.subgroup.n() as u64]);
+        self.powers.contains(&x_k)
+    }
+
+    /// Produces the evaluations of the Lagrange basis polynomials of this
+    /// domain at `x`, ordered by slot. Returns `None` if `x` is an element
+    /// of the domain.
+    ///
+    /// # Implementation
+    ///
+    /// The vanishing polynomial of the domain is
+    /// $Z(X) = \prod_c (X^{2^k} - t_c)$ where $t_c = h_c^{2^k}$. Its
+    /// derivative at an element $x_i$ of the coset $h_c H$ is
+    /// $2^k t_c x_i^{-1} \prod_{c' \neq c} (t_c - t_{c'})$, because
+    /// $x_i^{2^k} = t_c$, and so
+    ///
+    /// $$ \ell_i(x) = \frac{Z(x)}{Z'(x_i) (x - x_i)} = \frac{Z(x) \, x_i}{2^k
+    /// t_c \prod_{c' \neq c} (t_c - t_{c'}) (x - x_i)}. $$
+    ///
+    /// With a single coset this is the usual $\ell_i(x) = \frac{(x^n - 1)
+    /// \omega^i}{n (x - \omega^i)}$ of [`Domain::ell`].
+    pub fn ell(&self, x: F) -> Option<Vec<F>> {
+        let x_k = x.pow([self.subgroup.n() as u64]);
+        let vanishing = self.powers.iter().fold(F::ONE, |acc, t| acc * (x_k - t));
+        if vanishing.is_zero_vartime() {
+            return None;
+        }
+
+        let mut denominators: Vec<F> = self.elements().map(|x_i| x - x_i).collect();
+        {
+            let mut scratch = denominators.clone();
+            ff::BatchInverter::invert_with_external_scratch(&mut denominators, &mut scratch);
+        }
+
+        let coset_size = self.subgroup.n();
+        Some(
+            denominators
+                .into_iter()
+                .zip(self.elements())
+                .enumerate()
+                .map(|(slot, (denominator, x_i))| {
+                    vanishing * self.weights[slot / coset_size] * x_i * denominator
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the elements of the domain, ordered by slot.
+    fn elements(&self) -> impl Iterator<Item = F> + '_ {
+        let omega = self.subgroup.omega();
+        self.shifts.iter().flat_map(move |h| {
+            core::iter::successors(Some(*h), move |x| Some(*x * omega)).take(self.subgroup.n())
+        })
+    }
+
+    /// Replaces evaluations over this domain of a polynomial with coefficients
+    /// in a ring, ordered by slot, with its $n$ coefficients (in ascending
+    /// order of degree).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `evals` does not have exactly $n$ elements.
+    ///
+    /// # Implementation
+    ///
+    /// With a single coset this is an inverse FFT over $H$. Otherwise, let
+    /// $A_c(X)$ be the polynomial of degree less than $2^k$ that takes the
+    /// values over $h_c H$ divided by $\prod_{c' \neq c} (t_c - t_{c'})$,
+    /// which is found with an inverse FFT over the coset. Then
+    ///
+    /// $$ P(X) = \sum_c A_c(X) \prod_{c' \neq c} (X^{2^k} - t_{c'}) $$
+    ///
+    /// has degree less than $n$ and takes the provided values, since every
+    /// term but the $c$th vanishes over $h_c H$. This needs $O(m^2 2^k)$ ring
+    /// operations in addition to the $m$ inverse FFTs, and a second buffer
+    /// of $n$ ring elements.
+    pub fn ring_interpolate<R: Ring<F = F>>(&self, evals: &mut [R::R]) {
+        assert_eq!(evals.len(), self.n());
+        if self.num_cosets() == 1 {
+            self.subgroup.ring_ifft::<R>(evals);
+            return;
+        }
+
+        let coset_size = self.subgroup.n();
+        let mut coeffs = vec![R::R::default(); evals.len()];
+        for (c, chunk) in evals.chunks_exact_mut(coset_size).enumerate() {
+            self.subgroup.ring_coset_ifft::<R>(chunk, self.shifts[c]);
+
+            // \prod_{c' \neq c} (Y - t_{c'}) in ascending order of degree,
+            // divided by \prod_{c' \neq c} (t_c - t_{c'}).
+            let mut factor = vec![self.weights[c] * F::from(coset_size as u64) * self.powers[c]];
+            for (_, t) in self
+                .powers
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != c)
+            {
+                factor.push(F::ZERO);
+                for l in (0..factor.len()).rev() {
+                    let lower = if l > 0 { factor[l - 1] } else { F::ZERO };
+                    factor[l] = lower - factor[l] * t;
+                }
+            }
+
+            for (l, scale) in factor.iter().enumerate() {
+                for (j, coeff) in chunk.iter().enumerate() {
+                    let mut term = coeff.clone();
+                    R::scale_assign(&mut term, *scale);
+                    R::add_assign(&mut coeffs[l * coset_size + j], &term);
+                }
+            }
+        }
+        evals.clone_from_slice(&coeffs);
+    }
+
+    /// Replaces evaluations over this domain, ordered by slot, with the
+    /// coefficients of the polynomial that takes them. See
+    /// [`Self::ring_interpolate`].
+    pub fn interpolate(&self, evals: &mut [F]) {
+        self.ring_interpolate::<FFTField<F>>(evals);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval;
+    use alloc::format;
+    use ff::Field;
+    use pasta_curves::Fp as F;
+    use proptest::prelude::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Cosets of radix-2 domains of at most $2^2$ elements, as if the field
+    /// had 2-adicity 2.
+    const MAX_LOG2: u32 = 2;
+
+    fn elements(domain: &CosetDomain<F>) -> Vec<F> {
+        (0..domain.n())
+            .map(|i| CosetDomain::<F>::element_with_max_log2(i, MAX_LOG2))
+            .collect()
+    }
+
+    #[test]
+    fn test_sizes() {
+        for (n, expected_n, expected_cosets) in [
+            (0, 1, 1),
+            (1, 1, 1),
+            (3, 4, 1),
+            (4, 4, 1),
+            (5, 8, 2),
+            (13, 16, 4),
+        ] {
+            let domain = CosetDomain::<F>::with_max_log2(n, MAX_LOG2);
+            assert_eq!(domain.n(), expected_n);
+            assert_eq!(domain.num_cosets(), expected_cosets);
+        }
+
+        // Without a bound, every size fits a radix-2 domain of Pasta fields.
+        let domain = CosetDomain::<F>::new(13);
+        assert_eq!(domain.n(), 16);
+        assert_eq!(domain.num_cosets(), 1);
+    }
+
+    #[test]
+    fn test_element_placement() {
+        // Elements do not depend on the size of the domain, and in a radix-2
+        // domain they are its bit-reversed powers of \omega.
+        for log2_n in 0..6 {
+            let domain = Domain::<F>::new(log2_n);
+            for i in 0..domain.n() {
+                let j = bitreverse(i as u32, log2_n);
+                assert_eq!(CosetDomain::<F>::element(i), domain.omega().pow([j.into()]));
+            }
+        }
+
+        // Elements are distinct and occupy their slot.
+        let domain = CosetDomain::<F>::with_max_log2(11, MAX_LOG2);
+        let elements = elements(&domain);
+        let by_slot: Vec<F> = domain.elements().collect();
+        for (i, x) in elements.iter().enumerate() {
+            assert!(domain.contains(*x));
+            assert_eq!(by_slot[domain.slot(i)], *x);
+            assert!(!elements[..i].contains(x));
+        }
+        assert!(!domain.contains(F::ZERO));
+        assert!(!domain.contains(F::from(0x1234)));
+    }
+
+    proptest! {
+        #[test]
+        fn test_interpolate(seed in any::<u64>(), n in 1usize..40) {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let domain = CosetDomain::<F>::with_max_log2(n, MAX_LOG2);
+            let elements = elements(&domain);
+            let values: Vec<F> = (0..domain.n()).map(|_| F::random(&mut rng)).collect();
+
+            let mut coeffs = vec![F::ZERO; domain.n()];
+            for (i, value) in values.iter().enumerate() {
+                coeffs[domain.slot(i)] = *value;
+            }
+            domain.interpolate(&mut coeffs);
+            for (x, value) in elements.iter().zip(values.iter()) {
+                prop_assert_eq!(eval(&coeffs, *x), *value);
+            }
+
+            // The Lagrange basis evaluates the same polynomial elsewhere.
+            let x = F::random(&mut rng);
+            let ell = domain.ell(x).unwrap();
+            let expected: F = values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| ell[domain.slot(i)] * value)
+                .sum();
+            prop_assert_eq!(eval(&coeffs, x), expected);
+            prop_assert!(domain.ell(elements[n - 1]).is_none());
+        }
+    }
+}
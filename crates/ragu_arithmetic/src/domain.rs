@@ -118,6 +118,72 @@ impl<F: PrimeField> Domain<F> {
         self.ring_ifft::<crate::fft::FFTField<F>>(input);
     }
 
+    /// Returns the domain of size $2^k n$ that contains this domain of size
+    /// $n$, such as for evaluating products of polynomials over this domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the extended domain is larger than supported by the field.
+    pub fn extend(&self, k: u32) -> Self {
+        Self::new(self.log2_n + k)
+    }
+
+    /// Computes the evaluations of a polynomial over the coset
+    /// $\{ g \omega^i \}$ of this domain given its coefficients in a slice
+    /// of generic ring elements, where $g$ is the provided `shift`.
+    ///
+    /// Using [`PrimeField::MULTIPLICATIVE_GENERATOR`] as the shift produces a
+    /// coset that is disjoint from every domain supported by the field.
+    pub fn ring_coset_fft<R: crate::fft::Ring<F = F>>(&self, input: &mut [R::R], shift: F) {
+        crate::fft::distribute_powers::<R>(input, shift);
+        self.ring_fft::<R>(input);
+    }
+
+    /// Performs the inverse operation of [`Self::ring_coset_fft`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shift` is zero.
+    pub fn ring_coset_ifft<R: crate::fft::Ring<F = F>>(&self, input: &mut [R::R], shift: F) {
+        self.ring_ifft::<R>(input);
+        let shift_inv = shift.invert().expect("coset shift must be nonzero");
+        crate::fft::distribute_powers::<R>(input, shift_inv);
+    }
+
+    /// Computes the evaluations of a polynomial over the coset
+    /// $\{ g \omega^i \}$ of this domain given its coefficients, where $g$
+    /// is the provided `shift`. See [`Self::ring_coset_fft`].
+    pub fn coset_fft(&self, input: &mut [F], shift: F) {
+        self.ring_coset_fft::<crate::fft::FFTField<F>>(input, shift);
+    }
+
+    /// Performs the inverse operation of [`Self::coset_fft`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shift` is zero.
+    pub fn coset_ifft(&self, input: &mut [F], shift: F) {
+        self.ring_coset_ifft::<crate::fft::FFTField<F>>(input, shift);
+    }
+
+    /// Computes the evaluations of a polynomial of degree less than $n$ over
+    /// the coset $\{ g \omega'^i \}$ of the [extended](Self::extend) domain
+    /// of size $2^k n$ generated by $\omega'$, given its coefficients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than $n$ coefficients are provided, or if the extended
+    /// domain is larger than supported by the field.
+    pub fn extended_coset_fft(&self, coeffs: &[F], k: u32, shift: F) -> Vec<F> {
+        assert!(coeffs.len() <= self.n);
+
+        let extended = self.extend(k);
+        let mut evals = coeffs.to_vec();
+        evals.resize(extended.n, F::ZERO);
+        extended.coset_fft(&mut evals, shift);
+        evals
+    }
+
     /// This function produces the evaluations of Lagrange basis polynomials
     /// $\ell_i(x)$ for the domain generated by powers of $\omega$ where
     /// $\omega$ is an `n`-th primitive root of unity. Returns `None` if `x` is
@@ -286,3 +352,142 @@ fn test_domain_exceeds_max_boundary_panics() {
     let over_max = F::S + 1;
     let _domain = Domain::<F>::new(over_max);
 }
+
+#[cfg(test)]
+mod coset_tests {
+    use super::*;
+    use crate::{eval, fft::Ring};
+    use alloc::{format, vec};
+    use ff::Field;
+    use pasta_curves::Fp as F;
+    use proptest::prelude::*;
+
+    /// Pairs of field elements, to exercise the generic ring FFTs.
+    struct Pair;
+
+    impl Ring for Pair {
+        type R = (F, F);
+        type F = F;
+
+        fn scale_assign(r: &mut Self::R, by: Self::F) {
+            r.0 *= by;
+            r.1 *= by;
+        }
+
+        fn add_assign(r: &mut Self::R, other: &Self::R) {
+            r.0 += other.0;
+            r.1 += other.1;
+        }
+
+        fn sub_assign(r: &mut Self::R, other: &Self::R) {
+            r.0 -= other.0;
+            r.1 -= other.1;
+        }
+    }
+
+    fn arb_fe() -> impl Strategy<Value = F> {
+        any::<u64>().prop_map(|i| F::from(i) * F::MULTIPLICATIVE_GENERATOR + F::DELTA)
+    }
+
+    fn arb_poly() -> impl Strategy<Value = (u32, Vec<F>)> {
+        (0u32..7).prop_flat_map(|log2_n| {
+            (
+                Just(log2_n),
+                proptest::collection::vec(arb_fe(), 0..=(1usize << log2_n)),
+            )
+        })
+    }
+
+    fn coset(domain: &Domain<F>, shift: F) -> impl Iterator<Item = F> {
+        let omega = domain.omega();
+        (0..domain.n()).scan(shift, move |cur, _| {
+            let x = *cur;
+            *cur *= omega;
+            Some(x)
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_coset_fft((log2_n, coeffs) in arb_poly(), shift in arb_fe()) {
+            let domain = Domain::<F>::new(log2_n);
+            let mut padded = coeffs.clone();
+            padded.resize(domain.n(), F::ZERO);
+
+            let mut evals = padded.clone();
+            domain.coset_fft(&mut evals, shift);
+            for (x, e) in coset(&domain, shift).zip(evals.iter()) {
+                prop_assert_eq!(*e, eval(&coeffs, x));
+            }
+
+            domain.coset_ifft(&mut evals, shift);
+            prop_assert_eq!(evals, padded);
+        }
+
+        #[test]
+        fn test_extended_coset_fft(
+            (log2_n, coeffs) in arb_poly(),
+            k in 0u32..=2,
+            shift in arb_fe(),
+        ) {
+            let domain = Domain::<F>::new(log2_n);
+            let extended = domain.extend(k);
+            prop_assert_eq!(extended.n(), domain.n() << k);
+            prop_assert!(extended.contains(domain.omega()));
+
+            let mut evals = domain.extended_coset_fft(&coeffs, k, shift);
+            prop_assert_eq!(evals.len(), extended.n());
+            for (x, e) in coset(&extended, shift).zip(evals.iter()) {
+                prop_assert_eq!(*e, eval(&coeffs, x));
+            }
+
+            extended.coset_ifft(&mut evals, shift);
+            let mut padded = coeffs.clone();
+            padded.resize(extended.n(), F::ZERO);
+            prop_assert_eq!(evals, padded);
+        }
+
+        #[test]
+        fn test_ring_coset_fft(
+            (log2_n, a) in arb_poly(),
+            b in proptest::collection::vec(arb_fe(), 0..=64),
+            shift in arb_fe(),
+        ) {
+            let domain = Domain::<F>::new(log2_n);
+            let mut a = a;
+            let mut b = b;
+            a.resize(domain.n(), F::ZERO);
+            b.resize(domain.n(), F::ZERO);
+
+            let mut pairs: Vec<_> = a.iter().copied().zip(b.iter().copied()).collect();
+            domain.ring_coset_fft::<Pair>(&mut pairs, shift);
+            for (x, (ea, eb)) in coset(&domain, shift).zip(pairs.iter()) {
+                prop_assert_eq!(*ea, eval(&a, x));
+                prop_assert_eq!(*eb, eval(&b, x));
+            }
+
+            domain.ring_coset_ifft::<Pair>(&mut pairs, shift);
+            prop_assert_eq!(pairs, a.into_iter().zip(b).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_coset_is_disjoint() {
+        let extended = Domain::<F>::new(4).extend(2);
+        let shift = F::MULTIPLICATIVE_GENERATOR;
+        for x in coset(&extended, shift) {
+            assert!(!extended.contains(x));
+        }
+
+        // The vanishing polynomial of the original domain is nonzero on the
+        // extended coset, so it can be divided out of evaluations there.
+        let mut evals = vec![F::ZERO; 17];
+        evals[0] = -F::ONE;
+        evals[16] = F::ONE;
+        evals.resize(extended.n(), F::ZERO);
+        extended.coset_fft(&mut evals, shift);
+        assert!(evals.iter().all(|e| !bool::from(e.is_zero())));
+    }
+}
@@ -37,6 +37,15 @@ impl<F: PrimeField> Ring for FFTField<F> {
     }
 }
 
+/// Scales the $i$th element of `input` by $g^i$.
+pub(crate) fn distribute_powers<R: Ring>(input: &mut [R::R], g: R::F) {
+    let mut cur = R::F::ONE;
+    for elem in input.iter_mut() {
+        R::scale_assign(elem, cur);
+        cur *= g;
+    }
+}
+
 /// Reverses the bits of `n` using `l` bits.
 pub fn bitreverse(mut n: u32, l: u32) -> u32 {
     let mut r = 0;
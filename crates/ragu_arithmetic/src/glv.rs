@@ -0,0 +1,398 @@
+//! Scalar decomposition for the
+//! [GLV method](https://www.iacr.org/archive/crypto2001/21390189.pdf).
+//!
+//! Curves with the endomorphism $\phi(x, y) = (\zeta_b x, y)$, where
+//! $\zeta_b$ is [`ZETA`](WithSmallOrderMulGroup::ZETA) in the base field,
+//! satisfy $\phi(P) = [\zeta] P$ for $\zeta$ the corresponding constant in the
+//! scalar field. Any scalar $k$ can be written as $k_1 + k_2 \zeta$ where
+//! $k_1, k_2$ have roughly half the bit length of the scalar field, which
+//! halves the number of windows a multiscalar multiplication must process.
+
+use alloc::boxed::Box;
+use core::{any::Any, cmp::Ordering};
+
+use ff::{Field, PrimeField, WithSmallOrderMulGroup};
+use once_cell::race::OnceBox;
+use pasta_curves::{
+    arithmetic::{Coordinates, CurveAffine},
+    group::Curve,
+};
+
+/// Number of limbs in a [`Wide`] integer, enough for the product of a scalar
+/// with a half-width scalar.
+const LIMBS: usize = 8;
+
+/// Unsigned integer used to derive and apply the decomposition.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Wide([u64; LIMBS]);
+
+impl Wide {
+    const ZERO: Self = Wide([0; LIMBS]);
+
+    fn one() -> Self {
+        let mut v = Self::ZERO;
+        v.0[0] = 1;
+        v
+    }
+
+    /// Interprets little-endian bytes, such as the representation of a field
+    /// element.
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let mut v = Self::ZERO;
+        for (i, byte) in bytes.iter().enumerate() {
+            v.0[i / 8] |= u64::from(*byte) << ((i % 8) * 8);
+        }
+        v
+    }
+
+    fn from_field<F: PrimeField>(value: &F) -> Self {
+        Self::from_bytes(value.to_repr().as_ref())
+    }
+
+    /// Returns this integer as a field element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this integer is not smaller than the field modulus.
+    fn to_field<F: PrimeField>(self) -> F {
+        let mut repr = F::Repr::default();
+        for (i, byte) in repr.as_mut().iter_mut().enumerate() {
+            *byte = (self.0[i / 8] >> ((i % 8) * 8)) as u8;
+        }
+        F::from_repr(repr).expect("value must be smaller than the modulus")
+    }
+
+    /// Returns the field modulus $r$ of `F`.
+    fn modulus<F: PrimeField>() -> Self {
+        // r = (r - 1) + 1
+        Self::from_field(&-F::ONE).add(&Self::one())
+    }
+
+    fn bits(&self) -> usize {
+        for i in (0..LIMBS).rev() {
+            if self.0[i] != 0 {
+                return i * 64 + 64 - self.0[i].leading_zeros() as usize;
+            }
+        }
+        0
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut result = Self::ZERO;
+        let mut carry = 0;
+        for i in 0..LIMBS {
+            let sum = u128::from(self.0[i]) + u128::from(other.0[i]) + carry;
+            result.0[i] = sum as u64;
+            carry = sum >> 64;
+        }
+        result
+    }
+
+    /// Computes `self - other`, assuming `self >= other`.
+    fn sub(&self, other: &Self) -> Self {
+        let mut result = Self::ZERO;
+        let mut borrow = false;
+        for i in 0..LIMBS {
+            let (diff, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (diff, b2) = diff.overflowing_sub(u64::from(borrow));
+            result.0[i] = diff;
+            borrow = b1 || b2;
+        }
+        result
+    }
+
+    /// Computes the product modulo $2^{64 \cdot 8}$.
+    fn mul(&self, other: &Self) -> Self {
+        let mut result = Self::ZERO;
+        let other_limbs = other.bits().div_ceil(64);
+        for i in 0..self.bits().div_ceil(64) {
+            let mut carry = 0;
+            for j in i..LIMBS {
+                let prod = u128::from(self.0[i])
+                    * u128::from(other.0.get(j - i).copied().unwrap_or(0))
+                    + u128::from(result.0[j])
+                    + carry;
+                result.0[j] = prod as u64;
+                carry = prod >> 64;
+                if j - i >= other_limbs && carry == 0 {
+                    break;
+                }
+            }
+        }
+        result
+    }
+
+    fn shl(&self, n: usize) -> Self {
+        let mut result = Self::ZERO;
+        let (limbs, bits) = (n / 64, n % 64);
+        for i in (limbs..LIMBS).rev() {
+            result.0[i] = self.0[i - limbs] << bits;
+            if bits > 0 && i > limbs {
+                result.0[i] |= self.0[i - limbs - 1] >> (64 - bits);
+            }
+        }
+        result
+    }
+
+    fn shr(&self, n: usize) -> Self {
+        let mut result = Self::ZERO;
+        let (limbs, bits) = (n / 64, n % 64);
+        for i in 0..(LIMBS - limbs) {
+            result.0[i] = self.0[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < LIMBS {
+                result.0[i] |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+        result
+    }
+
+    /// Returns the quotient and remainder of `self / divisor`.
+    fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let mut quotient = Self::ZERO;
+        let mut remainder = *self;
+        if remainder < *divisor {
+            return (quotient, remainder);
+        }
+
+        let shift = remainder.bits() - divisor.bits();
+        let mut divisor = divisor.shl(shift);
+        for i in (0..=shift).rev() {
+            if remainder >= divisor {
+                remainder = remainder.sub(&divisor);
+                quotient.0[i / 64] |= 1 << (i % 64);
+            }
+            divisor = divisor.shr(1);
+        }
+        (quotient, remainder)
+    }
+
+    /// Returns `round(self / divisor)`.
+    fn div_round(&self, divisor: &Self) -> Self {
+        self.add(&divisor.shr(1)).div_rem(divisor).0
+    }
+}
+
+impl PartialOrd for Wide {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Wide {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+/// A signed integer, as its sign (`true` if negative) and magnitude.
+type Signed = (bool, Wide);
+
+fn signed_to_field<F: PrimeField>((neg, mag): Signed) -> F {
+    let value = mag.to_field::<F>();
+    if neg { -value } else { value }
+}
+
+/// Precomputed parameters for decomposing scalars in `F`.
+pub(crate) struct Glv<F> {
+    /// A short vector $(a, b)$ with $a + b \zeta \equiv 0$.
+    a: F,
+    b: F,
+    /// $\lfloor 2^{256} (a - b) / d \rceil$ for the lattice determinant $d$.
+    g1: Signed,
+    /// $\lfloor 2^{256} b / d \rceil$ for the lattice determinant $d$.
+    g2: Signed,
+    /// $(r - 1) / 2$, the largest positive value of a signed scalar.
+    half: Wide,
+}
+
+impl<F: WithSmallOrderMulGroup<3>> Glv<F> {
+    /// Derives a short basis for the lattice of vectors $(x, y)$ with
+    /// $x + y \zeta \equiv 0$ using the extended Euclidean algorithm.
+    pub(crate) fn new() -> Self {
+        let modulus = Wide::modulus::<F>();
+
+        // Invariant: r_i \equiv t_i \zeta, where the signs of t_i alternate.
+        let (mut r0, mut r1) = (modulus, Wide::from_field(&F::ZETA));
+        let (mut t0, mut t1) = (Wide::ZERO, Wide::one());
+        let mut t1_neg = false;
+        while r1.mul(&r1) >= modulus {
+            let (q, r2) = r0.div_rem(&r1);
+            let t2 = t0.add(&q.mul(&t1));
+            (r0, r1) = (r1, r2);
+            (t0, t1) = (t1, t2);
+            t1_neg = !t1_neg;
+        }
+
+        // (r_i, -t_i) is a short vector of the lattice, and multiplying by
+        // \zeta (using \zeta^2 = -1 - \zeta) produces the linearly independent
+        // vector (t_i, r_i + t_i) of the same length.
+        let a: Signed = (false, r1);
+        let b: Signed = (!t1_neg, t1);
+
+        // The determinant a^2 - ab + b^2 and a - b.
+        let (a_sq, b_sq, ab) = (a.1.mul(&a.1), b.1.mul(&b.1), a.1.mul(&b.1));
+        let det = if b.0 {
+            a_sq.add(&b_sq).add(&ab)
+        } else {
+            a_sq.add(&b_sq).sub(&ab)
+        };
+        let a_minus_b: Signed = if b.0 {
+            (false, a.1.add(&b.1))
+        } else if a.1 >= b.1 {
+            (false, a.1.sub(&b.1))
+        } else {
+            (true, b.1.sub(&a.1))
+        };
+
+        let glv = Self {
+            a: signed_to_field(a),
+            b: signed_to_field(b),
+            g1: (a_minus_b.0, a_minus_b.1.shl(256).div_round(&det)),
+            g2: (b.0, b.1.shl(256).div_round(&det)),
+            half: modulus.shr(1),
+        };
+        debug_assert!(bool::from((glv.a + glv.b * F::ZETA).is_zero()));
+        glv
+    }
+
+    /// Decomposes `k` into $k_1 + k_2 \zeta$, returning the sign (`true` if
+    /// negative) and the representation of the magnitude of $k_1$ and $k_2$.
+    pub(crate) fn decompose(&self, k: &F) -> [(bool, F::Repr); 2] {
+        // Babai rounding: (c1, c2) = round((k, 0) * B^{-1}) for the basis
+        // B = [(a, b), (-b, a - b)].
+        let k_wide = Wide::from_field(k);
+        let round = |(neg, g): Signed, negate: bool| {
+            let c = k_wide.mul(&g).add(&Wide::one().shl(255)).shr(256);
+            signed_to_field::<F>((neg ^ negate, c))
+        };
+        let c1 = round(self.g1, false);
+        let c2 = round(self.g2, true);
+
+        let k1 = *k - c1 * self.a + c2 * self.b;
+        let k2 = -(c1 * self.b) - c2 * (self.a - self.b);
+        [self.signed_magnitude(k1), self.signed_magnitude(k2)]
+    }
+
+    /// Returns the sign and magnitude of `value` interpreted as an integer in
+    /// $(-r/2, r/2)$.
+    fn signed_magnitude(&self, value: F) -> (bool, F::Repr) {
+        let repr = value.to_repr();
+        if Wide::from_bytes(repr.as_ref()) > self.half {
+            (true, (-value).to_repr())
+        } else {
+            (false, repr)
+        }
+    }
+}
+
+/// The endomorphism of a curve `C`, with the parameters for decomposing
+/// scalars against it.
+pub(crate) struct Endomorphism<C: CurveAffine> {
+    /// The cube root of unity $\zeta_b$ in the base field for which
+    /// $(\zeta_b x, y) = [\zeta] (x, y)$.
+    pub(crate) zeta: C::Base,
+    pub(crate) glv: Glv<C::Scalar>,
+}
+
+impl<C: CurveAffine> Endomorphism<C> {
+    /// Pairs $\zeta$ in the scalar field with the cube root of unity in the
+    /// base field that realizes it on the generator of `C`, or returns `None`
+    /// if neither does.
+    fn new() -> Option<Self> {
+        let g = C::generator();
+        let coords = Option::<Coordinates<C>>::from(g.coordinates())?;
+        let expected = (g * C::Scalar::ZETA).to_affine();
+        [C::Base::ZETA, C::Base::ZETA.square()]
+            .into_iter()
+            .find(|zeta| {
+                Option::from(C::from_xy(*coords.x() * zeta, *coords.y())) == Some(expected)
+            })
+            .map(|zeta| Endomorphism {
+                zeta,
+                glv: Glv::new(),
+            })
+    }
+
+    /// Calls `f` with the endomorphism of `C`, which is derived and checked
+    /// once per curve, or with `None` if the curve has no usable endomorphism.
+    pub(crate) fn with<R>(f: impl FnOnce(Option<&Self>) -> R) -> R {
+        /// Number of curves whose parameters are retained.
+        const SLOTS: usize = 8;
+        type Slot = Box<dyn Any + Send + Sync>;
+        static CACHE: [OnceBox<Slot>; SLOTS] = [const { OnceBox::new() }; SLOTS];
+
+        for slot in &CACHE {
+            let cached = slot.get_or_init(|| Box::new(Box::new(Self::new())));
+            if let Some(endo) = cached.downcast_ref::<Option<Self>>() {
+                return f(endo.as_ref());
+            }
+        }
+
+        // Every slot is occupied by another curve.
+        f(Self::new().as_ref())
+    }
+}
+
+/// Returns the bit length of the integer with little-endian representation
+/// `bytes`.
+pub(crate) fn bits(bytes: &[u8]) -> usize {
+    Wide::from_bytes(bytes).bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pasta_curves::{EpAffine, EqAffine, Fp, Fq};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn check<F: WithSmallOrderMulGroup<3>>() {
+        let glv = Glv::<F>::new();
+        let mut rng = StdRng::seed_from_u64(1234);
+        let scalars = (0..1000).map(|_| F::random(&mut rng)).chain([
+            F::ZERO,
+            F::ONE,
+            -F::ONE,
+            F::ZETA,
+            -F::ZETA,
+        ]);
+        for k in scalars {
+            let [(neg1, k1), (neg2, k2)] = glv.decompose(&k);
+            assert!(bits(k1.as_ref()) <= F::NUM_BITS as usize / 2 + 2);
+            assert!(bits(k2.as_ref()) <= F::NUM_BITS as usize / 2 + 2);
+
+            let k1 = F::from_repr(k1).unwrap();
+            let k2 = F::from_repr(k2).unwrap();
+            let k1 = if neg1 { -k1 } else { k1 };
+            let k2 = if neg2 { -k2 } else { k2 };
+            assert_eq!(k1 + k2 * F::ZETA, k);
+        }
+    }
+
+    #[test]
+    fn test_decompose() {
+        check::<Fp>();
+        check::<Fq>();
+    }
+
+    fn check_endomorphism<C: CurveAffine>() {
+        let first = Endomorphism::<C>::with(|endo| endo.map(|endo| endo as *const _));
+        let second = Endomorphism::<C>::with(|endo| endo.map(|endo| endo as *const _));
+        assert!(first.is_some());
+        assert_eq!(first, second);
+
+        let mut rng = StdRng::seed_from_u64(1234);
+        let p = (C::generator() * C::Scalar::random(&mut rng)).to_affine();
+        let coords = p.coordinates().unwrap();
+        Endomorphism::<C>::with(|endo| {
+            let zeta = endo.unwrap().zeta;
+            let image = C::from_xy(*coords.x() * zeta, *coords.y()).unwrap();
+            assert_eq!(image, (p * C::Scalar::ZETA).to_affine());
+        });
+    }
+
+    #[test]
+    fn test_endomorphism() {
+        check_endomorphism::<EpAffine>();
+        check_endomorphism::<EqAffine>();
+    }
+}
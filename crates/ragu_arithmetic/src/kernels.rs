@@ -0,0 +1,387 @@
+//! Batched field arithmetic kernels, enabled by the `batched-kernels` feature.
+//!
+//! The hot loops of the prover (inner products, folding and dilating
+//! polynomials) are written generically over [`Field`], so every product is
+//! fully reduced and every sum is a modular addition. For the Pasta fields the
+//! kernels in this module instead operate directly on the four 64-bit limbs of
+//! the Montgomery representation: products are accumulated into a wide
+//! (unreduced) integer and only a single Montgomery reduction is performed per
+//! output, roughly halving the cost of each term.
+//!
+//! Dispatch happens at runtime by comparing [`TypeId`]s, so that callers can
+//! remain generic. Fields other than [`Fp`] and [`Fq`] fall back to the scalar
+//! implementations.
+//!
+//! Reading the limbs relies on the in-memory layout of `Fp` and `Fq`, which
+//! `pasta_curves` does not expose, so the workspace pins `pasta_curves` to an
+//! exact version and `test_representation` checks the layout against the
+//! public encoding.
+
+use ff::{Field, PrimeField};
+use pasta_curves::{Fp, Fq};
+
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+
+/// Field types whose elements are represented as four little-endian 64-bit
+/// limbs in Montgomery form, with the Montgomery radix $R = 2^{256}$.
+trait Montgomery: PrimeField {
+    /// The modulus $p$ as little-endian limbs.
+    const MODULUS_LIMBS: [u64; 4];
+
+    /// $-p^{-1} \bmod 2^{64}$.
+    const INV: u64;
+}
+
+impl Montgomery for Fp {
+    const MODULUS_LIMBS: [u64; 4] = [
+        0x992d30ed00000001,
+        0x224698fc094cf91b,
+        0x0000000000000000,
+        0x4000000000000000,
+    ];
+    const INV: u64 = 0x992d30ecffffffff;
+}
+
+impl Montgomery for Fq {
+    const MODULUS_LIMBS: [u64; 4] = [
+        0x8c46eb2100000001,
+        0x224698fc0994a8dd,
+        0x0000000000000000,
+        0x4000000000000000,
+    ];
+    const INV: u64 = 0x8c46eb20ffffffff;
+}
+
+// `Fp` and `Fq` are `#[repr(transparent)]` wrappers around `[u64; 4]` in
+// `pasta_curves` 0.5. That is not a documented guarantee, so the layout that
+// the limb accessors rely on is checked here at compile time, and the
+// Montgomery form of the limbs by `test_representation`.
+const _: () = assert!(size_of::<Fp>() == size_of::<[u64; 4]>());
+const _: () = assert!(size_of::<Fq>() == size_of::<[u64; 4]>());
+const _: () = assert!(align_of::<Fp>() == align_of::<[u64; 4]>());
+const _: () = assert!(align_of::<Fq>() == align_of::<[u64; 4]>());
+
+/// Returns the Montgomery limbs of a field element.
+#[allow(unsafe_code)]
+fn limbs<M: Montgomery>(x: &M) -> &[u64; 4] {
+    // SAFETY: `Montgomery` is only implemented for `Fp` and `Fq`, which have
+    // the size and alignment of `[u64; 4]` (asserted above) and always hold
+    // their value in Montgomery form.
+    unsafe { &*(x as *const M as *const [u64; 4]) }
+}
+
+/// Returns the field element with the given (fully reduced) Montgomery limbs.
+#[allow(unsafe_code)]
+fn from_limbs<M: Montgomery>(limbs: [u64; 4]) -> M {
+    // SAFETY: See `limbs`; `transmute_copy` reads exactly `size_of::<M>()`
+    // bytes, and the caller ensures that `limbs` is less than the modulus.
+    unsafe { core::mem::transmute_copy(&limbs) }
+}
+
+fn is<F: 'static, M: 'static>() -> bool {
+    TypeId::of::<F>() == TypeId::of::<M>()
+}
+
+/// Reinterprets `&F` as `&M`, which must be the same type.
+fn cast_ref<F: 'static, M: 'static>(x: &F) -> &M {
+    (x as &dyn Any)
+        .downcast_ref()
+        .expect("dispatched on the same type")
+}
+
+/// Reinterprets `F` as `M`, which must be the same type.
+fn cast<F: Copy + 'static, M: Copy + 'static>(x: F) -> M {
+    *cast_ref(&x)
+}
+
+/// Evaluates `$body` with `$M` bound to the Pasta field that `$F` is, or
+/// `$fallback` if it is neither.
+macro_rules! dispatch {
+    ($F:ty, |$M:ident| $body:expr, $fallback:expr) => {
+        if is::<$F, Fp>() {
+            type $M = Fp;
+            $body
+        } else if is::<$F, Fq>() {
+            type $M = Fq;
+            $body
+        } else {
+            $fallback
+        }
+    };
+}
+
+/// Computes `a + (b * c) + carry`, returning the result and the new carry.
+#[inline(always)]
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + ((b as u128) * (c as u128)) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// Computes `a + b + carry`, returning the result and the new carry.
+#[inline(always)]
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + (b as u128) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// Computes `a - (b + borrow)`, returning the result and the new borrow.
+#[inline(always)]
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let ret = (a as u128).wrapping_sub((b as u128) + ((borrow >> 63) as u128));
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// Adds the 512-bit product `a * b` to the wide accumulator `acc`.
+#[inline(always)]
+fn accumulate(acc: &mut [u64; 9], a: &[u64; 4], b: &[u64; 4]) {
+    let (r0, carry) = mac(0, a[0], b[0], 0);
+    let (r1, carry) = mac(0, a[0], b[1], carry);
+    let (r2, carry) = mac(0, a[0], b[2], carry);
+    let (r3, r4) = mac(0, a[0], b[3], carry);
+
+    let (r1, carry) = mac(r1, a[1], b[0], 0);
+    let (r2, carry) = mac(r2, a[1], b[1], carry);
+    let (r3, carry) = mac(r3, a[1], b[2], carry);
+    let (r4, r5) = mac(r4, a[1], b[3], carry);
+
+    let (r2, carry) = mac(r2, a[2], b[0], 0);
+    let (r3, carry) = mac(r3, a[2], b[1], carry);
+    let (r4, carry) = mac(r4, a[2], b[2], carry);
+    let (r5, r6) = mac(r5, a[2], b[3], carry);
+
+    let (r3, carry) = mac(r3, a[3], b[0], 0);
+    let (r4, carry) = mac(r4, a[3], b[1], carry);
+    let (r5, carry) = mac(r5, a[3], b[2], carry);
+    let (r6, r7) = mac(r6, a[3], b[3], carry);
+
+    let (t0, carry) = adc(acc[0], r0, 0);
+    let (t1, carry) = adc(acc[1], r1, carry);
+    let (t2, carry) = adc(acc[2], r2, carry);
+    let (t3, carry) = adc(acc[3], r3, carry);
+    let (t4, carry) = adc(acc[4], r4, carry);
+    let (t5, carry) = adc(acc[5], r5, carry);
+    let (t6, carry) = adc(acc[6], r6, carry);
+    let (t7, carry) = adc(acc[7], r7, carry);
+    let t8 = acc[8].wrapping_add(carry);
+
+    *acc = [t0, t1, t2, t3, t4, t5, t6, t7, t8];
+}
+
+/// Reduces a wide accumulator of products of Montgomery-form elements to the
+/// field element it represents.
+///
+/// Each product $(aR)(bR)$ in the accumulator represents $ab R^2$, so the
+/// accumulated integer $T$ represents $T R^{-2}$.
+#[inline(always)]
+fn reduce<M: Montgomery>(acc: &[u64; 9]) -> M {
+    let p = M::MODULUS_LIMBS;
+    let [t0, t1, t2, t3, t4, t5, t6, t7, t8] = *acc;
+
+    // Montgomery reduction of the low 512 bits (Algorithm 14.32 in the
+    // Handbook of Applied Cryptography), which yields an integer congruent to
+    // their value times R^{-1} that is less than R + p.
+    let k = t0.wrapping_mul(M::INV);
+    let (_, carry) = mac(t0, k, p[0], 0);
+    let (t1, carry) = mac(t1, k, p[1], carry);
+    let (t2, carry) = mac(t2, k, p[2], carry);
+    let (t3, carry) = mac(t3, k, p[3], carry);
+    let (t4, carry2) = adc(t4, 0, carry);
+
+    let k = t1.wrapping_mul(M::INV);
+    let (_, carry) = mac(t1, k, p[0], 0);
+    let (t2, carry) = mac(t2, k, p[1], carry);
+    let (t3, carry) = mac(t3, k, p[2], carry);
+    let (t4, carry) = mac(t4, k, p[3], carry);
+    let (t5, carry2) = adc(t5, carry2, carry);
+
+    let k = t2.wrapping_mul(M::INV);
+    let (_, carry) = mac(t2, k, p[0], 0);
+    let (t3, carry) = mac(t3, k, p[1], carry);
+    let (t4, carry) = mac(t4, k, p[2], carry);
+    let (t5, carry) = mac(t5, k, p[3], carry);
+    let (t6, carry2) = adc(t6, carry2, carry);
+
+    let k = t3.wrapping_mul(M::INV);
+    let (_, carry) = mac(t3, k, p[0], 0);
+    let (t4, carry) = mac(t4, k, p[1], carry);
+    let (t5, carry) = mac(t5, k, p[2], carry);
+    let (t6, carry) = mac(t6, k, p[3], carry);
+    let (t7, carry2) = adc(t7, carry2, carry);
+
+    // The Pasta moduli exceed R/4, so at most four subtractions of p are
+    // needed to fully reduce the result.
+    let mut u = [t4, t5, t6, t7, carry2];
+    for _ in 0..4 {
+        let (d0, borrow) = sbb(u[0], p[0], 0);
+        let (d1, borrow) = sbb(u[1], p[1], borrow);
+        let (d2, borrow) = sbb(u[2], p[2], borrow);
+        let (d3, borrow) = sbb(u[3], p[3], borrow);
+        let (d4, borrow) = sbb(u[4], 0, borrow);
+
+        // `borrow` is all ones if u < p, in which case u is kept.
+        for (u, d) in u.iter_mut().zip([d0, d1, d2, d3, d4]) {
+            *u = (*u & borrow) | (d & !borrow);
+        }
+    }
+
+    let mut result = from_limbs::<M>([u[0], u[1], u[2], u[3]]);
+
+    // The top limb represents t8 * 2^512 * R^{-2} = t8. It is only nonzero
+    // once more than 16 products have been accumulated.
+    if t8 != 0 {
+        result += M::from(t8);
+    }
+
+    result
+}
+
+/// Computes the inner product of Montgomery limb pairs with a single
+/// reduction.
+#[inline(always)]
+fn dot_montgomery<'a, M: Montgomery>(
+    pairs: impl Iterator<Item = (&'a [u64; 4], &'a [u64; 4])>,
+) -> M {
+    let mut acc = [0u64; 9];
+    for (a, b) in pairs {
+        accumulate(&mut acc, a, b);
+    }
+    reduce(&acc)
+}
+
+/// Computes $\langle \mathbf{a}, \mathbf{b} \rangle$ if `F` is a Pasta field,
+/// or returns the iterators otherwise.
+pub(crate) fn dot<'a, F: Field, A, B>(a: A, b: B) -> Result<F, (A, B)>
+where
+    A: Iterator<Item = &'a F>,
+    B: Iterator<Item = &'a F>,
+{
+    dispatch!(
+        F,
+        |M| Ok(cast::<M, F>(dot_montgomery::<M>(
+            a.map(|a| limbs::<M>(cast_ref(a)))
+                .zip(b.map(|b| limbs::<M>(cast_ref(b))))
+        ))),
+        Err((a, b))
+    )
+}
+
+/// Computes $\sum_j s^{k - 1 - j} \mathbf{v}_j$ over the $k$ `vectors` if `F`
+/// is a Pasta field, reducing each output coefficient only once.
+pub(crate) fn fold<F: Field>(vectors: &[&[F]], scale: F, len: usize) -> Option<Vec<F>> {
+    dispatch!(
+        F,
+        |M| {
+            let scale = cast::<F, M>(scale);
+
+            // The vectors are traversed in order, accumulating into a wide
+            // accumulator per output coefficient.
+            let mut accs = alloc::vec![[0u64; 9]; len];
+            let mut power = M::ONE;
+            for v in vectors.iter().rev() {
+                let power_limbs = limbs(&power);
+                for (acc, coeff) in accs.iter_mut().zip(v.iter()) {
+                    accumulate(acc, limbs::<M>(cast_ref(coeff)), power_limbs);
+                }
+                power *= scale;
+            }
+
+            Some(
+                accs.iter()
+                    .map(|acc| cast::<M, F>(reduce::<M>(acc)))
+                    .collect(),
+            )
+        },
+        None
+    )
+}
+
+/// Multiplies the coefficients by successive powers of `z` starting at `cur`,
+/// returning the next power.
+///
+/// Four interleaved chains of powers (each advancing by $z^4$) are used, so
+/// consecutive multiplications do not depend on each other.
+pub(crate) fn scale_by_powers<'a, F: Field>(
+    coeffs: impl IntoIterator<Item = &'a mut F>,
+    cur: F,
+    z: F,
+) -> F {
+    let z2 = z.square();
+    let z4 = z2.square();
+    let mut lanes = [cur, cur * z, cur * z2, cur * z2 * z];
+
+    let mut lane = 0;
+    for coeff in coeffs {
+        *coeff *= lanes[lane];
+        lanes[lane] *= z4;
+        lane = (lane + 1) & 3;
+    }
+
+    lanes[lane]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+    use proptest::prelude::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn to_limbs<M: Montgomery>(repr: M::Repr) -> [u64; 4] {
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(repr.as_ref().chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+        }
+        limbs
+    }
+
+    fn check_representation<M: Montgomery>() {
+        // p - 1 is the largest element.
+        let mut p = to_limbs::<M>((-M::ONE).to_repr());
+        p[0] += 1;
+        assert_eq!(M::MODULUS_LIMBS, p);
+        assert_eq!(M::MODULUS_LIMBS[0].wrapping_mul(M::INV), u64::MAX);
+
+        // One is represented by R = 2^256 mod p.
+        let r = M::from_u128(1 << 64).pow([4]);
+        assert_eq!(*limbs(&M::ONE), to_limbs::<M>(r.to_repr()));
+
+        let x = M::random(StdRng::seed_from_u64(0));
+        assert_eq!(from_limbs::<M>(*limbs(&x)), x);
+    }
+
+    #[test]
+    fn test_representation() {
+        check_representation::<Fp>();
+        check_representation::<Fq>();
+    }
+
+    fn scalar_dot<F: Field>(a: &[F], b: &[F]) -> F {
+        a.iter().zip(b).fold(F::ZERO, |acc, (a, b)| acc + *a * *b)
+    }
+
+    proptest! {
+        #[test]
+        fn test_dot(seed in any::<u64>(), len in 0usize..100) {
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            let a: Vec<Fp> = (0..len).map(|_| Fp::random(&mut rng)).collect();
+            let b: Vec<Fp> = (0..len).map(|_| Fp::random(&mut rng)).collect();
+            prop_assert_eq!(dot(a.iter(), b.iter()).ok(), Some(scalar_dot(&a, &b)));
+
+            let a: Vec<Fq> = (0..len).map(|_| Fq::random(&mut rng)).collect();
+            let b: Vec<Fq> = (0..len).map(|_| Fq::random(&mut rng)).collect();
+            prop_assert_eq!(dot(a.iter(), b.iter()).ok(), Some(scalar_dot(&a, &b)));
+        }
+    }
+
+    #[test]
+    fn test_dot_extremes() {
+        // The largest elements maximize the accumulator and the intermediate
+        // result of the reduction.
+        for len in [0, 1, 15, 16, 17, 1000] {
+            let a = vec![-Fp::ONE; len];
+            assert_eq!(dot(a.iter(), a.iter()).ok(), Some(scalar_dot(&a, &a)));
+        }
+    }
+}
@@ -33,13 +33,15 @@
 //! convenient in some cases and necessary for some applications and SNARK
 //! protocols.
 //!
-//! We currently require curves to have the high 2-adicity property needed to
-//! support these evaluation domains, essentially limiting users to the Pasta
-//! curves. However, this is not an inherent requirement of the cryptography and
-//! future versions of Ragu may support cycles such as the one formed by
-//! [sec**p**256k1](https://en.bitcoin.it/wiki/Secp256k1)/sec**q**256k1, which
-//! do not have this high 2-adicity property but are used in many
-//! cryptocurrencies.
+//! High 2-adicity is not an inherent requirement of the cryptography, though,
+//! and the cycle formed by
+//! [sec**p**256k1](https://en.bitcoin.it/wiki/Secp256k1)/sec**q**256k1 (used
+//! in many cryptocurrencies, and implemented by the `ragu_secp` crate) has
+//! 2-adicity of only $1$ and $6$. Circuit registries therefore place their
+//! circuits on a [`CosetDomain`], a union of cosets of the largest radix-2
+//! subgroup, which is an ordinary radix-2 [`Domain`] whenever the field allows
+//! it. Polynomial arithmetic that relies on larger radix-2 FFTs is still
+//! limited to fields with enough 2-adicity.
 //!
 //! ## Endomorphisms
 //!
@@ -71,8 +73,13 @@
 extern crate alloc;
 
 mod coeff;
+mod cosets;
 mod domain;
 mod fft;
+mod glv;
+#[cfg(feature = "batched-kernels")]
+mod kernels;
+mod msm;
 mod uendo;
 mod util;
 
@@ -93,10 +100,14 @@ pub trait PrimeFieldExt: PrimeField {
 impl<F: PrimeField> PrimeFieldExt for F {}
 
 pub use coeff::Coeff;
+pub use cosets::CosetDomain;
 pub use domain::Domain;
 pub use fft::{Ring, bitreverse};
+pub use msm::FixedBaseTable;
 pub use pasta_curves::arithmetic::{Coordinates, CurveAffine, CurveExt};
-pub use util::{dot, eval, factor, factor_iter, geosum, mul, poly_with_roots};
+pub use util::{
+    dot, eval, factor, factor_iter, fold_vectors, geosum, mul, poly_with_roots, scale_by_powers,
+};
 
 /// Converts a 256-bit integer literal into the little endian `[u64; 4]`
 /// representation that e.g. [`Fp::from_raw`](pasta_curves::Fp::from_raw) or
@@ -104,9 +115,15 @@ pub use util::{dot, eval, factor, factor_iter, geosum, mul, poly_with_roots};
 /// slightly more readable, but is not intended for use in other contexts.
 pub use ragu_macros::repr256;
 
-// TODO(ebfull): Use this if we need to increase the bit size of endoscalars.
-// pub use uendo::Uendo;
+// Endoscalars are 128 bits wide by default. The `wide-endoscalars` feature
+// switches to a wider integer type for a larger security margin, at the cost
+// of more constraints per endoscaling. The width is global: enabling the
+// feature anywhere in a build changes the circuits, and so the registry
+// digests and proofs, of every crate in that build.
+#[cfg(not(feature = "wide-endoscalars"))]
 pub use u128 as Uendo;
+#[cfg(feature = "wide-endoscalars")]
+pub use uendo::Uendo;
 
 /// Represents a "cycle" of elliptic curves where the scalar field of one curve
 /// is the base field of the other, and vice-versa.
@@ -182,6 +199,37 @@ pub trait FixedGenerators<C: CurveAffine>: Send + Sync + 'static {
     /// Generator used as a blinding factor or randomization.
     fn h(&self) -> &C;
 
+    /// Returns a precomputed table for the generators [`g`](Self::g) followed
+    /// by [`h`](Self::h), if one is available.
+    ///
+    /// Tables are optional because they require substantially more memory
+    /// than the generators themselves.
+    fn table(&self) -> Option<&FixedBaseTable<C>> {
+        None
+    }
+
+    /// Compute a commitment $\sum_k a_k \cdot G_{i_k} + r \cdot H$ to the
+    /// provided pairs of indices $i_k$ into [`g`](Self::g) and scalars $a_k$,
+    /// with the blinding factor $r$. The precomputed [`table`](Self::table) is
+    /// used if available.
+    fn commit<'a>(
+        &self,
+        terms: impl IntoIterator<Item = (usize, &'a C::ScalarExt)>,
+        blind: &'a C::ScalarExt,
+    ) -> C::CurveExt {
+        let h = self.g().len();
+        let terms = terms.into_iter().chain(Some((h, blind)));
+        match self.table() {
+            Some(table) => table.mul(terms),
+            None => {
+                let base = |i| if i == h { self.h() } else { &self.g()[i] };
+                let (coeffs, bases): (alloc::vec::Vec<_>, alloc::vec::Vec<_>) =
+                    terms.map(|(i, coeff)| (coeff, base(i))).unzip();
+                mul(coeffs, bases)
+            }
+        }
+    }
+
     /// Compute a commitment to a single value.
     fn short_commit(&self, value: C::ScalarExt, blind: C::ScalarExt) -> C {
         // TODO(ebfull): This returns a C, but the most efficient method would
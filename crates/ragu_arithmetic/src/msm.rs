@@ -0,0 +1,170 @@
+use ff::PrimeField;
+use pasta_curves::{
+    arithmetic::CurveAffine,
+    group::{Curve, Group},
+};
+
+use alloc::{vec, vec::Vec};
+
+use crate::util::{Bucket, bucket_lookup, get_at, mul, sum_buckets};
+
+/// A precomputed table for multiscalar multiplications against a fixed
+/// sequence of bases $\mathbf{G} \in \mathbb{G}^n$.
+///
+/// For a window size of $c$ bits, the table stores $2^{cj} \cdot G_i$ for every
+/// base $G_i$ and every window $j$ of the scalar. The windows of every scalar
+/// can then be placed into a single set of $2^c - 1$ buckets, so that the
+/// doublings and the per-window bucket summation performed by [`mul`] are
+/// avoided, and a larger window can be used. This costs memory for
+/// $n \cdot \lceil \ell / c \rceil$ affine points, where $\ell$ is the bit
+/// length of the scalar field.
+pub struct FixedBaseTable<C: CurveAffine> {
+    window: usize,
+    windows: usize,
+    /// `table[i * windows + j]` is $2^{cj} \cdot G_i$.
+    table: Vec<C>,
+}
+
+impl<C: CurveAffine> FixedBaseTable<C> {
+    /// Precomputes a table for the provided bases, choosing the window size
+    /// that minimizes the cost of a multiscalar multiplication over all of
+    /// them.
+    pub fn new<'a>(bases: impl IntoIterator<Item = &'a C>) -> Self {
+        let bases: Vec<C> = bases.into_iter().copied().collect();
+        let bits = C::Scalar::NUM_BITS as usize;
+        let cost = |c: usize| bases.len() * bits.div_ceil(c) + (1 << (c + 1));
+        let window = (1..=16).min_by_key(|&c| cost(c)).unwrap();
+
+        Self::with_window(&bases, window)
+    }
+
+    /// Precomputes a table for the provided bases using a window size of
+    /// `window` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is zero or larger than 32.
+    pub fn with_window(bases: &[C], window: usize) -> Self {
+        assert!(window > 0 && window <= 32);
+        let windows = (C::Scalar::NUM_BITS as usize).div_ceil(window);
+
+        let mut projective = Vec::with_capacity(bases.len() * windows);
+        for base in bases {
+            let mut cur = base.to_curve();
+            for _ in 0..windows {
+                projective.push(cur);
+                for _ in 0..window {
+                    cur = cur.double();
+                }
+            }
+        }
+        let mut table = vec![C::identity(); projective.len()];
+        C::Curve::batch_normalize(&projective, &mut table);
+
+        FixedBaseTable {
+            window,
+            windows,
+            table,
+        }
+    }
+
+    /// Returns the number of bases in this table.
+    pub fn len(&self) -> usize {
+        self.table.len() / self.windows
+    }
+
+    /// Returns `true` if this table contains no bases.
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Returns the base at index `i`.
+    pub fn base(&self, i: usize) -> &C {
+        &self.table[i * self.windows]
+    }
+
+    /// Computes $\sum_k a_k \cdot G_{i_k}$ for the provided pairs of base
+    /// indices $i_k$ and scalars $a_k$.
+    ///
+    /// Multiplications with few scalars relative to the window size fall back
+    /// to [`mul`] over the (unshifted) bases in the table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    pub fn mul<'a>(&self, terms: impl IntoIterator<Item = (usize, &'a C::Scalar)>) -> C::Curve {
+        let terms: Vec<_> = terms.into_iter().collect();
+
+        let fallback_window = bucket_lookup(terms.len());
+        let fallback_cost = (terms.len() + (1 << (fallback_window + 1)))
+            * (C::Scalar::NUM_BITS as usize).div_ceil(fallback_window);
+        if fallback_cost < terms.len() * self.windows + (1 << (self.window + 1)) {
+            return mul(
+                terms.iter().map(|(_, coeff)| *coeff),
+                terms.iter().map(|&(i, _)| self.base(i)),
+            );
+        }
+
+        let mut buckets = vec![Bucket::None; (1 << self.window) - 1];
+        for (i, coeff) in terms {
+            let bases = &self.table[i * self.windows..][..self.windows];
+            let coeff = coeff.to_repr();
+            for (j, base) in bases.iter().enumerate() {
+                let digit = get_at::<C::Scalar>(j, self.window, &coeff);
+                if digit != 0 {
+                    buckets[digit - 1].add_assign(base);
+                }
+            }
+        }
+
+        sum_buckets(buckets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ff::Field;
+    use pasta_curves::{EpAffine, Fq, group::prime::PrimeCurveAffine};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn test_fixed_base_mul() {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let bases: Vec<EpAffine> = (0..300)
+            .map(|_| (EpAffine::generator() * Fq::random(&mut rng)).into())
+            .collect();
+        let coeffs: Vec<Fq> = (0..300).map(|_| Fq::random(&mut rng)).collect();
+
+        for table in [
+            FixedBaseTable::new(&bases),
+            FixedBaseTable::with_window(&bases, 3),
+            FixedBaseTable::with_window(&bases, 11),
+        ] {
+            assert_eq!(table.len(), bases.len());
+
+            // Full multiplication.
+            let expected = crate::mul(coeffs.iter(), bases.iter());
+            assert_eq!(table.mul(coeffs.iter().enumerate()), expected);
+
+            // Sparse multiplication over a subset of the bases, which may use
+            // the fallback.
+            for n in [0, 1, 5, 40] {
+                let terms = (0..n).map(|k| ((k * 7) % bases.len(), &coeffs[k]));
+                let expected = crate::mul(
+                    coeffs[..n].iter(),
+                    (0..n).map(|k| &bases[(k * 7) % bases.len()]),
+                );
+                assert_eq!(table.mul(terms), expected);
+            }
+        }
+
+        // Extreme scalars.
+        let table = FixedBaseTable::new(&bases[..2]);
+        let coeffs = [-Fq::ONE, Fq::ZERO];
+        assert_eq!(
+            table.mul(coeffs.iter().enumerate()),
+            crate::mul(coeffs.iter(), bases[..2].iter())
+        );
+    }
+}
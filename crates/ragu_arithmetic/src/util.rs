@@ -1,9 +1,16 @@
 use ff::{Field, PrimeField};
-use pasta_curves::{arithmetic::CurveAffine, group::Group};
+use pasta_curves::{
+    arithmetic::{Coordinates, CurveAffine},
+    group::Group,
+};
 
 use alloc::{boxed::Box, vec, vec::Vec};
+use core::cmp::Ordering;
 
-use crate::domain::Domain;
+use crate::{
+    domain::Domain,
+    glv::{self, Endomorphism},
+};
 
 /// Evaluates a polynomial $p \in \mathbb{F}\[X]$ at a point $x \in \mathbb{F}$,
 /// where $p$ is defined by `coeffs` in ascending order of degree.
@@ -36,12 +43,81 @@ where
     let a = a.into_iter();
     let b = b.into_iter();
     assert_eq!(a.len(), b.len());
-    a.into_iter()
-        .zip(b)
+
+    #[cfg(feature = "batched-kernels")]
+    let (a, b) = match crate::kernels::dot(a, b) {
+        Ok(result) => return result,
+        Err(unsupported) => unsupported,
+    };
+
+    dot_scalar(a, b)
+}
+
+/// The scalar implementation of [`dot`].
+fn dot_scalar<'a, F: Field>(a: impl Iterator<Item = &'a F>, b: impl Iterator<Item = &'a F>) -> F {
+    a.zip(b)
         .map(|(a, b)| *a * *b)
         .fold(F::ZERO, |acc, x| acc + x)
 }
 
+/// Computes the weighted sum $\sum_{j=0}^{k-1} s^{k - 1 - j} \mathbf{v}_j$ of
+/// the $k$ provided vectors by the powers of `scale_factor` $s$, as Horner's
+/// method would. Missing trailing coefficients of shorter vectors are treated
+/// as zero, so the result is as long as the longest vector.
+pub fn fold_vectors<F: Field>(vectors: &[&[F]], scale_factor: F) -> Vec<F> {
+    #[cfg(feature = "batched-kernels")]
+    {
+        let len = vectors.iter().map(|v| v.len()).max().unwrap_or(0);
+        if let Some(result) = crate::kernels::fold(vectors, scale_factor, len) {
+            return result;
+        }
+    }
+
+    fold_vectors_scalar(vectors, scale_factor)
+}
+
+/// The scalar implementation of [`fold_vectors`].
+fn fold_vectors_scalar<F: Field>(vectors: &[&[F]], scale_factor: F) -> Vec<F> {
+    let len = vectors.iter().map(|v| v.len()).max().unwrap_or(0);
+    let mut acc = Vec::with_capacity(len);
+    for v in vectors {
+        acc.iter_mut().for_each(|c| *c *= scale_factor);
+        if acc.len() < v.len() {
+            acc.resize(v.len(), F::ZERO);
+        }
+        acc.iter_mut().zip(v.iter()).for_each(|(c, v)| *c += *v);
+    }
+    acc
+}
+
+/// Multiplies the provided coefficients by successive powers $c, cz, cz^2,
+/// \ldots$ of `z` starting at `cur` $= c$, returning the next power.
+pub fn scale_by_powers<'a, F: Field>(
+    coeffs: impl IntoIterator<Item = &'a mut F>,
+    cur: F,
+    z: F,
+) -> F {
+    #[cfg(feature = "batched-kernels")]
+    return crate::kernels::scale_by_powers(coeffs, cur, z);
+
+    #[cfg(not(feature = "batched-kernels"))]
+    scale_by_powers_scalar(coeffs, cur, z)
+}
+
+/// The scalar implementation of [`scale_by_powers`].
+#[cfg(any(test, not(feature = "batched-kernels")))]
+fn scale_by_powers_scalar<'a, F: Field>(
+    coeffs: impl IntoIterator<Item = &'a mut F>,
+    mut cur: F,
+    z: F,
+) -> F {
+    for coeff in coeffs {
+        *coeff *= cur;
+        cur *= z;
+    }
+    cur
+}
+
 fn factor_iter_inner<F: Field, I: IntoIterator<Item = F>>(a: I, mut b: F) -> impl Iterator<Item = F>
 where
     I::IntoIter: DoubleEndedIterator,
@@ -103,7 +179,7 @@ where
 /// Given a number of scalars, returns the ideal bucket size (in bits) for
 /// multiexp, obtained through experimentation. This could probably be optimized
 /// further and for particular compilation targets.
-fn bucket_lookup(n: usize) -> usize {
+pub(crate) fn bucket_lookup(n: usize) -> usize {
     const LN_THRESHOLDS: [usize; 15] = [
         4, 4, 32, 55, 149, 404, 1097, 2981, 8104, 22027, 59875, 162755, 442414, 1202605, 3269018,
     ];
@@ -147,6 +223,13 @@ fn test_bucket_lookup_thresholds() {
 /// $\mathbf{a} \in \mathbb{F}^n$ is a vector of scalars and $\mathbf{G} \in \mathbb{G}^n$
 /// is a vector of bases.
 ///
+/// This uses Pippenger's bucket method with signed-digit windows, after each
+/// scalar is decomposed with the curve endomorphism into two scalars of half
+/// the bit length (the [GLV method](https://www.iacr.org/archive/crypto2001/21390189.pdf)).
+/// The endomorphism is checked against the generator of `C` once per curve;
+/// if it does not act as scalar multiplication by a cube root of unity, the
+/// scalars are used whole.
+///
 /// # Usage
 ///
 /// Ensure that the provided iterators have the same length, or this function may not
@@ -155,37 +238,64 @@ pub fn mul<
     'a,
     C: CurveAffine,
     A: IntoIterator<Item = &'a C::Scalar>,
-    B: IntoIterator<Item = &'a C> + Clone,
+    B: IntoIterator<Item = &'a C>,
 >(
     coeffs: A,
     bases: B,
 ) -> C::Curve {
-    let coeffs: Vec<_> = coeffs.into_iter().map(|a| a.to_repr()).collect();
-
-    let c = bucket_lookup(coeffs.len());
-
-    fn get_at<F: PrimeField>(segment: usize, c: usize, bytes: &F::Repr) -> usize {
-        let skip_bits = segment * c;
-        let skip_bytes = skip_bits / 8;
-
-        if skip_bytes >= 32 {
-            return 0;
+    // Each scalar k is decomposed into k_1 + k_2 \zeta so that the base P and
+    // its image \phi(P) = [\zeta] P under the endomorphism are multiplied by
+    // half-width scalars. Negative halves negate the corresponding base.
+    let (scalars, points) = Endomorphism::<C>::with(|endo| {
+        let mut scalars = Vec::new();
+        let mut points = Vec::new();
+        for (coeff, base) in coeffs.into_iter().zip(bases) {
+            let Some(endo) = endo else {
+                scalars.push(coeff.to_repr());
+                points.push(*base);
+                continue;
+            };
+            let Some(coords) = Option::<Coordinates<C>>::from(base.coordinates()) else {
+                continue;
+            };
+            let image = C::from_xy(*coords.x() * endo.zeta, *coords.y()).unwrap();
+            for ((neg, k), base) in endo.glv.decompose(coeff).into_iter().zip([*base, image]) {
+                scalars.push(k);
+                points.push(if neg { -base } else { base });
+            }
         }
+        (scalars, points)
+    });
 
-        let mut v = [0; 8];
-        for (v, o) in v.iter_mut().zip(bytes.as_ref()[skip_bytes..].iter()) {
-            *v = *o;
+    let bits = scalars
+        .iter()
+        .map(|k| glv::bits(k.as_ref()))
+        .max()
+        .unwrap_or(0);
+    // Each window costs an addition for every point and two for every
+    // bucket.
+    let c = (1..=16)
+        .min_by_key(|&c| (points.len() + (1 << c)) * (bits / c + 1))
+        .unwrap();
+    let segments = bits / c + 1;
+
+    // Signed-digit recoding: each window's digit is in [-2^{c-1}, 2^{c-1}], so
+    // that only 2^{c-1} buckets are needed, with one extra window to absorb
+    // the final carry.
+    let half = 1 << (c - 1);
+    let mut digits = vec![0i32; segments * scalars.len()];
+    for (i, k) in scalars.iter().enumerate() {
+        let mut carry = 0;
+        for segment in 0..segments {
+            let d = get_at::<C::Scalar>(segment, c, k) + carry;
+            (digits[segment * scalars.len() + i], carry) = if d > half {
+                (d as i32 - (1 << c), 1)
+            } else {
+                (d as i32, 0)
+            };
         }
-
-        let mut tmp = u64::from_le_bytes(v);
-        tmp >>= skip_bits - (skip_bytes * 8);
-        tmp %= 1 << c;
-
-        tmp as usize
     }
 
-    let segments = (256 / c) + 1;
-
     let mut acc = C::Curve::identity();
 
     for current_segment in (0..segments).rev() {
@@ -193,57 +303,91 @@ pub fn mul<
             acc = acc.double();
         }
 
-        #[derive(Clone, Copy)]
-        enum Bucket<C: CurveAffine> {
-            None,
-            Affine(C),
-            Projective(C::Curve),
-        }
-
-        impl<C: CurveAffine> Bucket<C> {
-            fn add_assign(&mut self, other: &C) {
-                *self = match *self {
-                    Bucket::None => Bucket::Affine(*other),
-                    Bucket::Affine(a) => Bucket::Projective(a + *other),
-                    Bucket::Projective(mut a) => {
-                        a += *other;
-                        Bucket::Projective(a)
-                    }
-                }
-            }
+        let mut buckets: Vec<Bucket<C>> = vec![Bucket::None; half];
 
-            fn add(self, mut other: C::Curve) -> C::Curve {
-                match self {
-                    Bucket::None => other,
-                    Bucket::Affine(a) => {
-                        other += a;
-                        other
-                    }
-                    Bucket::Projective(a) => other + a,
-                }
+        let digits = &digits[current_segment * points.len()..][..points.len()];
+        for (&digit, base) in digits.iter().zip(points.iter()) {
+            match digit.cmp(&0) {
+                Ordering::Greater => buckets[digit as usize - 1].add_assign(base),
+                Ordering::Less => buckets[(-digit) as usize - 1].add_assign(&-*base),
+                Ordering::Equal => {}
             }
         }
 
-        let mut buckets: Vec<Bucket<C>> = vec![Bucket::None; (1 << c) - 1];
+        acc += sum_buckets(buckets);
+    }
+
+    acc
+}
+
+/// Returns the `c`-bit window of the little-endian scalar representation
+/// `bytes` at index `segment`.
+pub(crate) fn get_at<F: PrimeField>(segment: usize, c: usize, bytes: &F::Repr) -> usize {
+    let bytes = bytes.as_ref();
+    let skip_bits = segment * c;
+    let skip_bytes = skip_bits / 8;
 
-        for (coeff, base) in coeffs.iter().zip(bases.clone().into_iter()) {
-            let coeff = get_at::<C::Scalar>(current_segment, c, coeff);
-            if coeff != 0 {
-                buckets[coeff - 1].add_assign(base);
+    if skip_bytes >= bytes.len() {
+        return 0;
+    }
+
+    let mut v = [0; 8];
+    for (v, o) in v.iter_mut().zip(bytes[skip_bytes..].iter()) {
+        *v = *o;
+    }
+
+    let mut tmp = u64::from_le_bytes(v);
+    tmp >>= skip_bits - (skip_bytes * 8);
+    tmp %= 1 << c;
+
+    tmp as usize
+}
+
+/// An accumulator of bases for multiscalar multiplication that avoids
+/// projective arithmetic until a second base is added.
+#[derive(Clone, Copy)]
+pub(crate) enum Bucket<C: CurveAffine> {
+    None,
+    Affine(C),
+    Projective(C::Curve),
+}
+
+impl<C: CurveAffine> Bucket<C> {
+    pub(crate) fn add_assign(&mut self, other: &C) {
+        *self = match *self {
+            Bucket::None => Bucket::Affine(*other),
+            Bucket::Affine(a) => Bucket::Projective(a + *other),
+            Bucket::Projective(mut a) => {
+                a += *other;
+                Bucket::Projective(a)
             }
         }
+    }
 
-        // Summation by parts
-        // e.g. 3a + 2b + 1c = a +
-        //                    (a) + b +
-        //                    ((a) + b) + c
-        let mut running_sum = C::Curve::identity();
-        for exp in buckets.into_iter().rev() {
-            running_sum = exp.add(running_sum);
-            acc += &running_sum;
+    fn add(self, mut other: C::Curve) -> C::Curve {
+        match self {
+            Bucket::None => other,
+            Bucket::Affine(a) => {
+                other += a;
+                other
+            }
+            Bucket::Projective(a) => other + a,
         }
     }
+}
 
+/// Computes $\sum_i (i + 1) \cdot B_i$ for the buckets $B_i$.
+pub(crate) fn sum_buckets<C: CurveAffine>(buckets: Vec<Bucket<C>>) -> C::Curve {
+    // Summation by parts
+    // e.g. 3a + 2b + 1c = a +
+    //                    (a) + b +
+    //                    ((a) + b) + c
+    let mut acc = C::Curve::identity();
+    let mut running_sum = C::Curve::identity();
+    for exp in buckets.into_iter().rev() {
+        running_sum = exp.add(running_sum);
+        acc += &running_sum;
+    }
     acc
 }
 
@@ -478,6 +622,61 @@ fn test_dot() {
         dot(powers.iter(), coeffs.iter().rev().rev()),
         eval(coeffs.iter(), F::DELTA)
     );
+    assert_eq!(
+        dot_scalar(powers.iter(), coeffs.iter()),
+        eval(coeffs.iter(), F::DELTA)
+    );
+}
+
+#[test]
+fn test_fold_vectors() {
+    use pasta_curves::Fp as F;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let vectors: Vec<Vec<F>> = [5, 0, 12, 7, 12, 1]
+        .into_iter()
+        .map(|len| (0..len).map(|_| F::random(&mut rng)).collect())
+        .collect();
+    let slices: Vec<&[F]> = vectors.iter().map(|v| &v[..]).collect();
+    let scale = F::random(&mut rng);
+
+    for folded in [
+        fold_vectors(&slices, scale),
+        fold_vectors_scalar(&slices, scale),
+    ] {
+        assert_eq!(folded.len(), 12);
+        for (i, coeff) in folded.iter().enumerate() {
+            let column: Vec<F> = vectors
+                .iter()
+                .map(|v| v.get(i).copied().unwrap_or(F::ZERO))
+                .collect();
+            assert_eq!(*coeff, eval(column.iter().rev(), scale));
+        }
+    }
+
+    assert!(fold_vectors::<F>(&[], scale).is_empty());
+    assert!(fold_vectors_scalar::<F>(&[], scale).is_empty());
+}
+
+#[test]
+fn test_scale_by_powers() {
+    use pasta_curves::Fp as F;
+
+    let z = F::from(3);
+    for len in 0..10 {
+        let mut coeffs = vec![F::ONE; len];
+        let mut scalar = coeffs.clone();
+        let next = scale_by_powers(coeffs.iter_mut(), F::from(2), z);
+        for (i, coeff) in coeffs.iter().enumerate() {
+            assert_eq!(*coeff, F::from(2) * z.pow([i as u64]));
+        }
+        assert_eq!(next, F::from(2) * z.pow([len as u64]));
+
+        let next_scalar = scale_by_powers_scalar(scalar.iter_mut(), F::from(2), z);
+        assert_eq!(scalar, coeffs);
+        assert_eq!(next_scalar, next);
+    }
 }
 
 #[test]
@@ -582,3 +781,36 @@ fn test_batched_quotient_streaming() {
         .sum();
     assert_eq!(f_at_y, expected_at_y);
 }
+
+#[test]
+fn test_mul_random() {
+    use pasta_curves::{EpAffine, EqAffine};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn check<C: CurveAffine>(rng: &mut StdRng) {
+        for n in [0, 1, 2, 10, 100] {
+            let mut bases: Vec<C> = (0..n)
+                .map(|_| (C::generator() * C::Scalar::random(&mut *rng)).into())
+                .collect();
+            let mut coeffs: Vec<C::Scalar> = (0..n).map(|_| C::Scalar::random(&mut *rng)).collect();
+            if n > 2 {
+                // Identity bases and extreme scalars.
+                bases[0] = C::identity();
+                coeffs[1] = -C::Scalar::ONE;
+                coeffs[2] = C::Scalar::ZERO;
+            }
+
+            let expected = coeffs
+                .iter()
+                .zip(bases.iter())
+                .fold(C::Curve::identity(), |acc, (coeff, base)| {
+                    acc + *base * coeff
+                });
+            assert_eq!(mul(coeffs.iter(), bases.iter()), expected);
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(1234);
+    check::<EpAffine>(&mut rng);
+    check::<EqAffine>(&mut rng);
+}
@@ -0,0 +1,351 @@
+//! Satisfiability checking of the $r(X)$ witness polynomial.
+//!
+//! The [`check`] function computes $r(X)$ for some witness and determines
+//! whether it satisfies a circuit for a given instance. When it does not, the
+//! returned error describes the multiplication gate or linear constraint that
+//! is violated, which is far more useful when debugging a circuit than a
+//! failing revdot identity.
+//!
+//! # Diagnostics
+//!
+//! The witness is checked by replaying the circuit's synthesis with a driver
+//! (see [`Checker`]) whose wires are the assignments read back out of the
+//! $\mathbf{a}, \mathbf{b}, \mathbf{c}$ vectors of $r(X)$ in its
+//! [`forward`](structured::Polynomial::forward) view, using the same gate
+//! allocation strategy as the $r(X)$ and $s(X, Y)$ evaluators. Each
+//! multiplication gate is checked when the driver reaches it, and each linear
+//! constraint is checked against the coefficient of $k(Y)$ that it is bound
+//! to. Finally, the revdot identity
+//!
+//! $$ \langle r(X), r(zX) + s(X, y) + t(X, z) \rangle = k(y) $$
+//!
+//! is checked at random points $y, z$ as a consistency check of the
+//! polynomials themselves.
+
+use arithmetic::Coeff;
+use ff::Field;
+use ragu_core::{
+    Error, Result,
+    drivers::{Driver, DriverTypes, emulator::Emulator},
+    gadgets::GadgetKind,
+    maybe::Empty,
+    routines::Routine,
+};
+use ragu_primitives::GadgetExt;
+use rand::Rng;
+
+use alloc::{boxed::Box, vec};
+use core::fmt;
+
+use crate::{
+    Circuit, ky, metrics,
+    polynomials::{Rank, structured},
+    registry, rx,
+    s::{
+        self, DriverExt,
+        common::{WireEval, WireEvalSum},
+    },
+};
+
+/// Describes why a witness does not satisfy a circuit, as reported by
+/// [`CircuitExt::check`](crate::CircuitExt::check) within an
+/// [`Error::InvalidWitness`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Unsatisfied {
+    /// The multiplication gate at `index` (the position of its assignment in
+    /// the $\mathbf{a}, \mathbf{b}, \mathbf{c}$ vectors of $r(X)$) does not
+    /// satisfy $a \cdot b = c$. Gate 0 is the registry key gate.
+    Gate {
+        /// The index of the multiplication gate.
+        index: usize,
+    },
+
+    /// The linear constraint at `index` (in the order it was enforced during
+    /// synthesis) does not equal its coefficient of $k(Y)$. Constraint 0 binds
+    /// the registry key, and the final constraints bind the circuit's public
+    /// outputs followed by the `ONE` wire.
+    Linear {
+        /// The index of the linear constraint.
+        index: usize,
+    },
+
+    /// Every constraint is satisfied but the revdot identity does not hold,
+    /// which indicates an inconsistency between $r(X)$, $s(X, Y)$, $t(X, Z)$
+    /// and $k(Y)$ rather than a bad witness.
+    Identity,
+}
+
+impl fmt::Display for Unsatisfied {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unsatisfied::Gate { index } => {
+                write!(f, "multiplication gate {index} is not satisfied")
+            }
+            Unsatisfied::Linear { index } => {
+                write!(f, "linear constraint {index} is not satisfied")
+            }
+            Unsatisfied::Identity => {
+                write!(f, "revdot identity does not hold for satisfied constraints")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Unsatisfied {}
+
+impl From<Unsatisfied> for Error {
+    fn from(unsatisfied: Unsatisfied) -> Self {
+        Error::InvalidWitness(Box::new(unsatisfied))
+    }
+}
+
+/// A [`Driver`] that replays synthesis over the assignments of an $r(X)$
+/// polynomial, checking each constraint as it is encountered.
+struct Checker<'a, F: Field, R: Rank> {
+    /// The assignment being checked.
+    rx: structured::View<'a, F, R, structured::Forward>,
+
+    /// The coefficients of $k(Y)$ for the instance being checked.
+    ky: &'a [F],
+
+    /// The total number of linear constraints in the circuit.
+    num_linear_constraints: usize,
+
+    /// Number of multiplication gates consumed so far.
+    multiplication_constraints: usize,
+
+    /// Number of linear constraints checked so far.
+    linear_constraints: usize,
+
+    /// Assignment of the `ONE` wire, the $c$ wire of gate 0.
+    one: F,
+
+    /// Stashed $b$ wire from paired allocation.
+    available_b: Option<WireEval<F>>,
+}
+
+impl<F: Field, R: Rank> DriverTypes for Checker<'_, F, R> {
+    type MaybeKind = Empty;
+    type LCadd = WireEvalSum<F>;
+    type LCenforce = WireEvalSum<F>;
+    type ImplField = F;
+    type ImplWire = WireEval<F>;
+}
+
+impl<'dr, F: Field, R: Rank> Driver<'dr> for Checker<'_, F, R> {
+    type F = F;
+    type Wire = WireEval<F>;
+
+    const ONE: Self::Wire = WireEval::One;
+
+    fn alloc(&mut self, _: impl Fn() -> Result<Coeff<Self::F>>) -> Result<Self::Wire> {
+        if let Some(wire) = self.available_b.take() {
+            Ok(wire)
+        } else {
+            let (a, b, _) = self.mul(|| unreachable!())?;
+            self.available_b = Some(b);
+
+            Ok(a)
+        }
+    }
+
+    fn mul(
+        &mut self,
+        _: impl Fn() -> Result<(Coeff<F>, Coeff<F>, Coeff<F>)>,
+    ) -> Result<(Self::Wire, Self::Wire, Self::Wire)> {
+        let index = self.multiplication_constraints;
+        if index == self.rx.a.len() {
+            return Err(Error::MultiplicationBoundExceeded(index));
+        }
+        self.multiplication_constraints += 1;
+
+        let (a, b, c) = (self.rx.a[index], self.rx.b[index], self.rx.c[index]);
+        if a * b != c {
+            return Err(Unsatisfied::Gate { index }.into());
+        }
+
+        Ok((WireEval::Value(a), WireEval::Value(b), WireEval::Value(c)))
+    }
+
+    fn add(&mut self, lc: impl Fn(Self::LCadd) -> Self::LCadd) -> Self::Wire {
+        WireEval::Value(lc(WireEvalSum::new(self.one)).value)
+    }
+
+    fn enforce_zero(&mut self, lc: impl Fn(Self::LCenforce) -> Self::LCenforce) -> Result<()> {
+        let index = self.linear_constraints;
+        if index == self.num_linear_constraints {
+            return Err(Error::LinearBoundExceeded(index));
+        }
+        self.linear_constraints += 1;
+
+        // Constraint `index` is scaled by y^{q - 1 - index} in s(X, Y) and so
+        // is bound to the corresponding coefficient of k(Y).
+        let expected = self
+            .ky
+            .get(self.num_linear_constraints - 1 - index)
+            .copied()
+            .unwrap_or(F::ZERO);
+        if lc(WireEvalSum::new(self.one)).value != expected {
+            return Err(Unsatisfied::Linear { index }.into());
+        }
+
+        Ok(())
+    }
+
+    fn routine<Ro: Routine<Self::F> + 'dr>(
+        &mut self,
+        routine: Ro,
+        input: <Ro::Input as GadgetKind<Self::F>>::Rebind<'dr, Self>,
+    ) -> Result<<Ro::Output as GadgetKind<Self::F>>::Rebind<'dr, Self>> {
+        let tmp = self.available_b.take();
+        let mut dummy = Emulator::wireless();
+        let dummy_input = Ro::Input::map_gadget(&input, &mut dummy)?;
+        let aux = routine.predict(&mut dummy, &dummy_input)?.into_aux();
+        let result = routine.execute(self, input, aux)?;
+
+        self.available_b = tmp;
+        Ok(result)
+    }
+}
+
+/// Replays the synthesis of `circuit` over the assignments of `rx`, returning
+/// an [`Unsatisfied`] diagnostic for the first violated constraint.
+fn diagnose<F: Field, C: Circuit<F>, R: Rank>(
+    circuit: &C,
+    rx: &mut structured::Polynomial<F, R>,
+    ky: &[F],
+    key: &registry::Key<F>,
+    num_linear_constraints: usize,
+) -> Result<()> {
+    let rx = rx.forward();
+    let mut checker = Checker::<'_, F, R> {
+        one: rx.c.first().copied().unwrap_or(F::ZERO),
+        rx,
+        ky,
+        num_linear_constraints,
+        multiplication_constraints: 0,
+        linear_constraints: 0,
+        available_b: None,
+    };
+
+    let (key_wire, _, _) = checker.mul(|| unreachable!())?;
+    checker.enforce_registry_key(&key_wire, key)?;
+
+    let mut outputs = vec![];
+    let (io, _) = circuit.witness(&mut checker, Empty)?;
+    io.write(&mut checker, &mut outputs)?;
+
+    checker.enforce_public_outputs(outputs.iter().map(|output| output.wire()))?;
+    checker.enforce_one()
+}
+
+/// Checks that `witness` satisfies `circuit` for the provided `instance`,
+/// returning an [`Error::InvalidWitness`] containing an [`Unsatisfied`]
+/// diagnostic if it does not.
+pub fn check<'witness, F: Field, C: Circuit<F>, R: Rank, RNG: Rng>(
+    circuit: &C,
+    rng: &mut RNG,
+    witness: C::Witness<'witness>,
+    instance: C::Instance<'_>,
+    key: &registry::Key<F>,
+) -> Result<()> {
+    let metrics = metrics::eval(circuit)?;
+    if metrics.num_linear_constraints > R::num_coeffs() {
+        return Err(Error::LinearBoundExceeded(R::num_coeffs()));
+    }
+
+    let (mut rx, _) = rx::eval::<F, C, R>(circuit, witness, key)?;
+    let ky = ky::eval(circuit, instance)?;
+
+    diagnose(circuit, &mut rx, &ky, key, metrics.num_linear_constraints)?;
+
+    let y = F::random(&mut *rng);
+    let z = F::random(&mut *rng);
+    let sy = s::sy::eval::<F, C, R>(circuit, y, key, metrics.num_linear_constraints)?;
+
+    let mut b = rx.clone();
+    b.dilate(z);
+    b.add_assign(&sy);
+    b.add_assign(&R::tz(z));
+
+    if rx.revdot(&b) != arithmetic::eval(&ky, y) {
+        return Err(Unsatisfied::Identity.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CircuitExt, polynomials::R, tests::SquareCircuit};
+    use ragu_pasta::Fp;
+    use rand::thread_rng;
+
+    type MyRank = R<8>;
+
+    fn unsatisfied(result: Result<()>) -> Unsatisfied {
+        match result {
+            Err(Error::InvalidWitness(e)) => e
+                .downcast_ref::<Unsatisfied>()
+                .expect("should be a diagnostic")
+                .clone(),
+            _ => panic!("expected an unsatisfied witness"),
+        }
+    }
+
+    #[test]
+    fn test_check_satisfied() -> Result<()> {
+        let circuit = SquareCircuit { times: 5 };
+        let key = registry::Key::default();
+        let witness = Fp::from(3);
+        let instance = witness.pow_vartime([1 << 5]);
+
+        circuit.check::<MyRank, _>(&mut thread_rng(), witness, instance, &key)
+    }
+
+    #[test]
+    fn test_check_wrong_instance() {
+        let circuit = SquareCircuit { times: 5 };
+        let key = registry::Key::default();
+        let metrics = metrics::eval::<Fp, _>(&circuit).unwrap();
+
+        // The single public output is bound by the second-to-last constraint.
+        assert_eq!(
+            unsatisfied(circuit.check::<MyRank, _>(
+                &mut thread_rng(),
+                Fp::from(3),
+                Fp::from(4),
+                &key
+            )),
+            Unsatisfied::Linear {
+                index: metrics.num_linear_constraints - 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_bad_gate() {
+        let circuit = SquareCircuit { times: 5 };
+        let key = registry::Key::default();
+        let witness = Fp::from(3);
+        let ky = ky::eval(&circuit, witness.pow_vartime([1 << 5])).unwrap();
+        let metrics = metrics::eval::<Fp, _>(&circuit).unwrap();
+
+        let (mut rx, _) = circuit.rx::<MyRank>(witness, &key).unwrap();
+        diagnose(&circuit, &mut rx, &ky, &key, metrics.num_linear_constraints).unwrap();
+
+        rx.forward().c[3] += Fp::ONE;
+        assert_eq!(
+            unsatisfied(diagnose(
+                &circuit,
+                &mut rx,
+                &ky,
+                &key,
+                metrics.num_linear_constraints
+            )),
+            Unsatisfied::Gate { index: 3 }
+        );
+    }
+}
@@ -14,6 +14,10 @@
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+mod check;
 mod ky;
 mod metrics;
 pub mod polynomials;
@@ -42,6 +46,8 @@ use alloc::{boxed::Box, vec::Vec};
 
 use polynomials::{Rank, structured, unstructured};
 
+pub use check::Unsatisfied;
+
 /// Core trait for arithmetic circuits.
 pub trait Circuit<F: Field>: Sized + Send + Sync {
     /// The type of data that is needed to construct the expected output of this
@@ -150,6 +156,40 @@ pub trait CircuitExt<F: Field>: Circuit<F> {
         rx::eval(self, witness, key)
     }
 
+    /// Computes the witness polynomial $r(X)$ given a witness for the circuit,
+    /// synthesizing [routines](ragu_core::routines) with predictable outputs
+    /// on other threads.
+    ///
+    /// The resulting polynomial is identical to the one produced by
+    /// [`rx`](CircuitExt::rx).
+    #[cfg(feature = "std")]
+    fn rx_parallel<'witness, R: Rank>(
+        &self,
+        witness: Self::Witness<'witness>,
+        key: &registry::Key<F>,
+    ) -> Result<(structured::Polynomial<F, R>, Self::Aux<'witness>)> {
+        rx::eval_parallel(self, witness, key)
+    }
+
+    /// Checks that `witness` satisfies this circuit for the provided
+    /// `instance`, which is useful when debugging circuits.
+    ///
+    /// This computes $r(X)$ and verifies it against the circuit's constraints
+    /// and the revdot identity with $s(X, y)$, $t(X, z)$ and $k(Y)$ at random
+    /// points $y, z$ sampled from `rng`. If the witness is not satisfying, an
+    /// [`Error::InvalidWitness`] is returned whose source is an [`Unsatisfied`]
+    /// identifying the first violated multiplication gate or linear
+    /// constraint.
+    fn check<'witness, R: Rank, RNG: rand::Rng>(
+        &self,
+        rng: &mut RNG,
+        witness: Self::Witness<'witness>,
+        instance: Self::Instance<'_>,
+        key: &registry::Key<F>,
+    ) -> Result<()> {
+        check::check::<F, Self, R, RNG>(self, rng, witness, instance, key)
+    }
+
     /// Computes the public input polynomial $k(Y)$ for the given instance.
     fn ky(&self, instance: Self::Instance<'_>) -> Result<Vec<F>> {
         ky::eval(self, instance)
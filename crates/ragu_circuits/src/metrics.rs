@@ -102,6 +102,37 @@ impl<'dr, F: Field> Driver<'dr> for Counter<F> {
     }
 }
 
+/// Counts the number of multiplication gates that a [`Routine`] occupies when
+/// executed on the provided input, without computing any witness values.
+#[cfg(feature = "std")]
+pub(crate) fn routine_gates<'dr, D: Driver<'dr, Wire = ()>, Ro: Routine<D::F>>(
+    routine: &Ro,
+    input: &<Ro::Input as GadgetKind<D::F>>::Rebind<'dr, D>,
+) -> Result<usize> {
+    use ragu_core::drivers::FromDriver;
+
+    struct Rebind;
+
+    impl<'dr, D: Driver<'dr, Wire = ()>> FromDriver<'dr, 'dr, D> for Rebind {
+        type NewDriver = Counter<D::F>;
+
+        fn convert_wire(&mut self, _: &()) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut collector = Counter {
+        available_b: false,
+        num_linear_constraints: 0,
+        num_multiplication_constraints: 0,
+        _marker: PhantomData,
+    };
+    let input = Ro::Input::map_gadget(input, &mut Rebind)?;
+    routine.execute(&mut collector, input, Empty)?;
+
+    Ok(collector.num_multiplication_constraints)
+}
+
 pub fn eval<F: Field, C: Circuit<F>>(circuit: &C) -> Result<CircuitMetrics> {
     let mut collector = Counter {
         available_b: false,
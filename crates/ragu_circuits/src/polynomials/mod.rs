@@ -4,7 +4,17 @@ pub mod structured;
 pub mod txz;
 pub mod unstructured;
 
+use arithmetic::CurveAffine;
 use ff::Field;
+use group::Curve;
+
+/// Converts commitments computed with `commit_projective` to affine form,
+/// sharing a single field inversion among all of them.
+pub fn batch_normalize<C: CurveAffine, const N: usize>(commitments: [C::Curve; N]) -> [C; N] {
+    let mut affine = [C::identity(); N];
+    C::Curve::batch_normalize(&commitments, &mut affine);
+    affine
+}
 
 mod private {
     pub trait Sealed {}
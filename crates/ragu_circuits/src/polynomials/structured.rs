@@ -1,10 +1,10 @@
 //! Polynomials with coefficients in a split structure arrangement.
 
-use arithmetic::CurveAffine;
-use ff::Field;
+use arithmetic::{CurveAffine, Domain, scale_by_powers};
+use ff::{Field, PrimeField};
 use rand::Rng;
 
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use core::borrow::Borrow;
 
 use super::Rank;
@@ -92,15 +92,26 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
     /// Computes a weighted sum of the polynomials yielded by an iterator by the
     /// powers of the provided `scale_factor`.
     ///
-    /// Horner's method is used to evaluate the weighted sum, effectively
-    /// scaling the first element by the highest power of `scale_factor` and the
-    /// last element by nothing at all.
+    /// The result is that of Horner's method, effectively scaling the first
+    /// element by the highest power of `scale_factor` and the last element by
+    /// nothing at all.
     pub fn fold<E: Borrow<Self>>(polys: impl IntoIterator<Item = E>, scale_factor: F) -> Self {
-        polys.into_iter().fold(Self::default(), |mut acc, poly| {
-            acc.scale(scale_factor);
-            acc.add_assign(poly.borrow());
-            acc
-        })
+        let polys = polys.into_iter().collect::<Vec<_>>();
+        let fold = |vector: fn(&Self) -> &[F]| {
+            let vectors = polys
+                .iter()
+                .map(|poly| vector(poly.borrow()))
+                .collect::<Vec<_>>();
+            arithmetic::fold_vectors(&vectors, scale_factor)
+        };
+
+        Polynomial {
+            u: fold(|poly| &poly.u[..]),
+            v: fold(|poly| &poly.v[..]),
+            w: fold(|poly| &poly.w[..]),
+            d: fold(|poly| &poly.d[..]),
+            _marker: core::marker::PhantomData,
+        }
     }
 
     /// Iterate over the coefficients of this polynomial in ascending order of
@@ -122,13 +133,16 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
 
     /// Inner product of `self` with the reversed `other`.
     pub fn revdot(&self, other: &Self) -> F {
-        self.u
-            .iter()
-            .zip(other.v.iter())
-            .chain(self.v.iter().zip(other.u.iter()))
-            .chain(self.w.iter().zip(other.d.iter()))
-            .chain(self.d.iter().zip(other.w.iter()))
-            .fold(F::ZERO, |acc, (a, b)| acc + (*a * *b))
+        // Coefficients beyond the shorter of each pair of vectors are zero.
+        fn dot<F: Field>(a: &[F], b: &[F]) -> F {
+            let len = a.len().min(b.len());
+            arithmetic::dot(&a[..len], &b[..len])
+        }
+
+        dot(&self.u, &other.v)
+            + dot(&self.v, &other.u)
+            + dot(&self.w, &other.d)
+            + dot(&self.d, &other.w)
     }
 
     /// Add the coefficients of `other` to `self`.
@@ -163,25 +177,13 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
     pub fn dilate(&mut self, z: F) {
         self.assert_bounds();
 
-        let mut cur = F::ONE;
-        for c in self.w.iter_mut() {
-            *c *= cur;
-            cur *= z;
-        }
-        cur *= z.pow_vartime([self.first_padding() as u64]);
-        for b in self.v.iter_mut().rev() {
-            *b *= cur;
-            cur *= z;
-        }
-        for a in self.u.iter_mut() {
-            *a *= cur;
-            cur *= z;
-        }
-        cur *= z.pow_vartime([self.second_padding() as u64]);
-        for d in self.d.iter_mut().rev() {
-            *d *= cur;
-            cur *= z;
-        }
+        let first_padding = z.pow_vartime([self.first_padding() as u64]);
+        let second_padding = z.pow_vartime([self.second_padding() as u64]);
+
+        let cur = scale_by_powers(self.w.iter_mut(), F::ONE, z);
+        let cur = scale_by_powers(self.v.iter_mut().rev(), cur * first_padding, z);
+        let cur = scale_by_powers(self.u.iter_mut(), cur, z);
+        scale_by_powers(self.d.iter_mut().rev(), cur * second_padding, z);
     }
 
     /// Evaluate this polynomial at a point `z`.
@@ -213,37 +215,99 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
         result
     }
 
+    /// Evaluate each of `polys` at each of `points`, returning the evaluation
+    /// of the `i`th polynomial at the `j`th point at index
+    /// `i * points.len() + j`.
+    ///
+    /// The powers of each point are computed once and shared between all of
+    /// the polynomials and all four of their coefficient vectors, so that
+    /// every coefficient costs a single multiplication.
+    pub fn eval_batch<'a>(polys: impl IntoIterator<Item = &'a Self>, points: &[F]) -> Vec<F>
+    where
+        Self: 'a,
+    {
+        let polys: Vec<&Self> = polys.into_iter().collect();
+        let len = polys
+            .iter()
+            .map(|poly| {
+                poly.assert_bounds();
+                poly.u
+                    .len()
+                    .max(poly.v.len())
+                    .max(poly.w.len())
+                    .max(poly.d.len())
+            })
+            .max()
+            .unwrap_or(0);
+
+        let dot = |coeffs: &mut dyn Iterator<Item = &F>, powers: &[F]| -> F {
+            coeffs.zip(powers).map(|(c, p)| *c * p).sum()
+        };
+
+        let mut evals = vec![F::ZERO; polys.len() * points.len()];
+        let mut powers = Vec::with_capacity(len);
+        for (j, &z) in points.iter().enumerate() {
+            powers.clear();
+            powers.extend(core::iter::successors(Some(F::ONE), |p| Some(*p * z)).take(len));
+            let z_2n = z.pow_vartime([(R::n() * 2) as u64]);
+
+            for (i, poly) in polys.iter().enumerate() {
+                // The reversed `v` and `d` vectors end at degrees 2n - 1 and
+                // 4n - 1, respectively.
+                let v_shift = z.pow_vartime([(R::n() * 2 - poly.v.len()) as u64]);
+                let d_shift = z.pow_vartime([(R::n() * 2 - poly.d.len()) as u64]);
+
+                evals[i * points.len() + j] = dot(&mut poly.w.iter(), &powers)
+                    + v_shift * dot(&mut poly.v.iter().rev(), &powers)
+                    + z_2n
+                        * (dot(&mut poly.u.iter(), &powers)
+                            + d_shift * dot(&mut poly.d.iter().rev(), &powers));
+            }
+        }
+
+        evals
+    }
+
+    /// Divides this polynomial by $X - u$, returning the (unstructured)
+    /// quotient and the remainder, which is the evaluation of this polynomial
+    /// at $u$.
+    pub fn div_linear(&self, u: F) -> (super::unstructured::Polynomial<F, R>, F) {
+        self.unstructured().div_linear(u)
+    }
+
     /// Compute a commitment to this polynomial using the provided generators.
     pub fn commit<C: CurveAffine<ScalarExt = F>>(
         &self,
         generators: &impl arithmetic::FixedGenerators<C>,
         blind: F,
     ) -> C {
+        self.commit_projective(generators, blind).into()
+    }
+
+    /// Compute a commitment to this polynomial using the provided generators,
+    /// without converting it to affine form.
+    pub fn commit_projective<C: CurveAffine<ScalarExt = F>>(
+        &self,
+        generators: &impl arithmetic::FixedGenerators<C>,
+        blind: F,
+    ) -> C::Curve {
         self.assert_bounds();
 
         assert!(generators.g().len() >= R::num_coeffs()); // TODO(ebfull)
 
-        let w_start = generators.g();
-        let v_start = &w_start[self.w.len() + self.first_padding()..];
-        let u_start = &v_start[self.v.len()..];
-        let d_start = &u_start[self.u.len() + self.second_padding()..];
+        let v_start = self.w.len() + self.first_padding();
+        let u_start = v_start + self.v.len();
+        let d_start = u_start + self.u.len() + self.second_padding();
 
-        arithmetic::mul(
+        generators.commit(
             self.w
                 .iter()
-                .chain(self.v.iter().rev())
-                .chain(self.u.iter())
-                .chain(self.d.iter().rev())
-                .chain(Some(&blind)),
-            w_start
-                .iter()
-                .take(self.w.len())
-                .chain(v_start.iter().take(self.v.len()))
-                .chain(u_start.iter().take(self.u.len()))
-                .chain(d_start.iter().take(self.d.len()))
-                .chain(Some(generators.h())),
+                .enumerate()
+                .chain((v_start..).zip(self.v.iter().rev()))
+                .chain((u_start..).zip(self.u.iter()))
+                .chain((d_start..).zip(self.d.iter().rev())),
+            &blind,
         )
-        .into() // TODO(ebfull)
     }
 
     /// Reduce this polynomial into its unstructured representation,
@@ -309,6 +373,32 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
     }
 }
 
+impl<F: PrimeField, R: Rank> Polynomial<F, R> {
+    /// Multiplies this polynomial by `other` using the FFT, producing an
+    /// unstructured polynomial.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degree of the product exceeds the degree bound of the
+    /// [`Rank`].
+    pub fn mul(&self, other: &Self) -> super::unstructured::Polynomial<F, R> {
+        self.unstructured().mul(&other.unstructured())
+    }
+
+    /// Divides this polynomial by the vanishing polynomial $X^n - 1$ of the
+    /// provided [`Domain`] of size $n$, returning the (unstructured) quotient
+    /// and remainder.
+    pub fn div_vanishing(
+        &self,
+        domain: &Domain<F>,
+    ) -> (
+        super::unstructured::Polynomial<F, R>,
+        super::unstructured::Polynomial<F, R>,
+    ) {
+        self.unstructured().div_vanishing(domain)
+    }
+}
+
 /// Marker trait for distinguishing between different polynomial views.
 pub trait Perspective {}
 
@@ -386,6 +476,90 @@ fn test_eval() {
     }
 }
 
+#[test]
+fn test_eval_batch() {
+    use ragu_pasta::Fp;
+    use rand::thread_rng;
+
+    type R = super::R<6>;
+
+    let polys: Vec<_> = (0..R::n())
+        .map(|i| {
+            let mut poly = Polynomial::<Fp, R>::new();
+            for j in 0..R::n() {
+                if j < i {
+                    poly.u.push(Fp::random(thread_rng()));
+                }
+                if j < (i * 3) % R::n() {
+                    poly.v.push(Fp::random(thread_rng()));
+                }
+                if j < (i * 5) % R::n() {
+                    poly.w.push(Fp::random(thread_rng()));
+                }
+                if j < (i * 7) % R::n() {
+                    poly.d.push(Fp::random(thread_rng()));
+                }
+            }
+            poly
+        })
+        .collect();
+    let points: Vec<_> = (0..5).map(|_| Fp::random(thread_rng())).collect();
+
+    let expected: Vec<_> = polys
+        .iter()
+        .flat_map(|poly| points.iter().map(|&x| poly.eval(x)))
+        .collect();
+    assert_eq!(Polynomial::eval_batch(&polys, &points), expected);
+
+    let unstructured: Vec<_> = polys.iter().map(|poly| poly.unstructured()).collect();
+    assert_eq!(
+        super::unstructured::Polynomial::eval_batch(&unstructured, &points),
+        expected
+    );
+
+    assert!(Polynomial::eval_batch(&polys, &[]).is_empty());
+    assert!(Polynomial::<Fp, R>::eval_batch([], &points).is_empty());
+}
+
+#[test]
+fn test_batch_normalize() {
+    use arithmetic::Cycle;
+    use ragu_pasta::{EqAffine, Fp, Pasta};
+    use rand::thread_rng;
+
+    type R = super::R<6>;
+
+    let generators = Pasta::host_generators(Pasta::baked());
+    let polys = [0, 1, R::n() / 2, R::n() - 1].map(|insertions| {
+        let mut poly = Polynomial::<Fp, R>::new();
+        for _ in 0..insertions {
+            poly.u.push(Fp::random(thread_rng()));
+            poly.v.push(Fp::random(thread_rng()));
+            poly.w.push(Fp::random(thread_rng()));
+            poly.d.push(Fp::random(thread_rng()));
+        }
+        (poly, Fp::random(thread_rng()))
+    });
+
+    let expected = polys
+        .each_ref()
+        .map(|(poly, blind)| poly.unstructured().commit(generators, *blind));
+    assert_eq!(
+        polys
+            .each_ref()
+            .map(|(poly, blind)| poly.commit(generators, *blind)),
+        expected
+    );
+    assert_eq!(
+        super::batch_normalize::<EqAffine, _>(
+            polys
+                .each_ref()
+                .map(|(poly, blind)| poly.commit_projective(generators, *blind))
+        ),
+        expected
+    );
+}
+
 #[test]
 fn test_backward_forward() {
     use ragu_pasta::Fp;
@@ -1,8 +1,8 @@
 //! Polynomials with coefficients in an unstructured (monomial basis)
 //! arrangement.
 
-use arithmetic::CurveAffine;
-use ff::Field;
+use arithmetic::{CurveAffine, Domain};
+use ff::{Field, PrimeField};
 use rand::Rng;
 
 use alloc::{vec, vec::Vec};
@@ -32,6 +32,21 @@ impl<F: Field, R: Rank> DerefMut for Polynomial<F, R> {
     }
 }
 
+impl<F: Field, R: Rank> arithmetic::Ring for Polynomial<F, R> {
+    type R = Self;
+    type F = F;
+
+    fn scale_assign(r: &mut Self, by: Self::F) {
+        r.scale(by);
+    }
+    fn add_assign(r: &mut Self, other: &Self) {
+        r.add_unstructured(other);
+    }
+    fn sub_assign(r: &mut Self, other: &Self) {
+        r.sub_unstructured(other);
+    }
+}
+
 impl<F: Field, R: Rank> Default for Polynomial<F, R> {
     fn default() -> Self {
         Self::new()
@@ -81,6 +96,53 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
         arithmetic::eval(&self.coeffs[..], x)
     }
 
+    /// Evaluate each of `polys` at each of `points`, returning the evaluation
+    /// of the `i`th polynomial at the `j`th point at index
+    /// `i * points.len() + j`.
+    ///
+    /// Each polynomial is evaluated at all of the points in a single pass over
+    /// its coefficients.
+    pub fn eval_batch<'a>(polys: impl IntoIterator<Item = &'a Self>, points: &[F]) -> Vec<F>
+    where
+        Self: 'a,
+    {
+        let mut evals = Vec::new();
+        for poly in polys {
+            let start = evals.len();
+            evals.resize(start + points.len(), F::ZERO);
+            let evals = &mut evals[start..];
+            for coeff in poly.coeffs.iter().rev() {
+                for (eval, point) in evals.iter_mut().zip(points) {
+                    *eval *= point;
+                    *eval += coeff;
+                }
+            }
+        }
+        evals
+    }
+
+    /// Returns the degree of this polynomial, or `None` if it is zero.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs
+            .iter()
+            .rposition(|coeff| !bool::from(coeff.is_zero()))
+    }
+
+    /// Divides this polynomial by $X - u$, returning the quotient and the
+    /// remainder, which is the evaluation of this polynomial at $u$.
+    pub fn div_linear(&self, u: F) -> (Self, F) {
+        let mut quotient = Self::new();
+        let mut remainder = F::ZERO;
+        for (i, coeff) in self.coeffs.iter().enumerate().rev() {
+            remainder = remainder * u + coeff;
+            if i > 0 {
+                quotient.coeffs[i - 1] = remainder;
+            }
+        }
+
+        (quotient, remainder)
+    }
+
     /// Scale the coefficients of the polynomial by the given factor.
     pub fn scale(&mut self, by: F) {
         self.coeffs.iter_mut().for_each(|coeff| {
@@ -99,6 +161,17 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
             .for_each(|(a, b)| *a += b);
     }
 
+    /// Subtract another unstructured polynomial from this one.
+    pub fn sub_unstructured(&mut self, other: &Self) {
+        assert_eq!(self.coeffs.len(), R::num_coeffs());
+        assert_eq!(other.coeffs.len(), R::num_coeffs());
+
+        self.coeffs
+            .iter_mut()
+            .zip(other.coeffs.iter())
+            .for_each(|(a, b)| *a -= b);
+    }
+
     /// Adds a structured polynomial to this unstructured polynomial.
     pub fn add_structured(&mut self, other: &super::structured::Polynomial<F, R>) {
         let v_len = other.v.len();
@@ -133,17 +206,111 @@ impl<F: Field, R: Rank> Polynomial<F, R> {
         generators: &impl arithmetic::FixedGenerators<C>,
         blind: F,
     ) -> C {
+        self.commit_projective(generators, blind).into()
+    }
+
+    /// Compute a commitment to this polynomial using the provided generators,
+    /// without converting it to affine form.
+    pub fn commit_projective<C: CurveAffine<ScalarExt = F>>(
+        &self,
+        generators: &impl arithmetic::FixedGenerators<C>,
+        blind: F,
+    ) -> C::Curve {
         assert!(generators.g().len() >= R::num_coeffs()); // TODO(ebfull)
 
-        arithmetic::mul(
-            self.coeffs.iter().chain(Some(&blind)),
-            generators
-                .g()
-                .iter()
-                .take(self.coeffs.len())
-                .chain(Some(generators.h())),
-        )
-        .into() // TODO(ebfull)
+        generators.commit(self.coeffs.iter().enumerate(), &blind)
+    }
+}
+
+impl<F: PrimeField, R: Rank> Polynomial<F, R> {
+    /// Creates the polynomial that takes the values `evals` over the
+    /// [`Domain`] of size `evals.len()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `evals.len()` is not a power of two or exceeds
+    /// [`Rank::num_coeffs`].
+    pub fn from_domain_evals(mut evals: Vec<F>) -> Self {
+        assert!(evals.len().is_power_of_two());
+        assert!(evals.len() <= R::num_coeffs());
+
+        Domain::new(evals.len().ilog2()).ifft(&mut evals);
+        Self::from_coeffs(evals)
+    }
+
+    /// Creates the lowest degree polynomial passing through each of the
+    /// provided `(x, y)` pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `x` values are not distinct, or if there are more points
+    /// than [`Rank::num_coeffs`].
+    pub fn interpolate(points: &[(F, F)]) -> Self {
+        assert!(points.len() <= R::num_coeffs());
+
+        let xs: Vec<F> = points.iter().map(|(x, _)| *x).collect();
+        let vanishing = arithmetic::poly_with_roots(&xs);
+
+        // Each Lagrange basis polynomial is the vanishing polynomial with the
+        // corresponding root divided out, scaled to be one at that root.
+        let mut coeffs = vec![F::ZERO; points.len()];
+        for &(x, y) in points {
+            let basis = arithmetic::factor(vanishing.iter().copied(), x);
+            let denom = arithmetic::eval(&basis, x)
+                .invert()
+                .expect("interpolation points must be distinct");
+            let scale = y * denom;
+            for (coeff, b) in coeffs.iter_mut().zip(basis.iter()) {
+                *coeff += scale * b;
+            }
+        }
+
+        Self::from_coeffs(coeffs)
+    }
+
+    /// Multiplies this polynomial by `other` using the FFT.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the degree of the product exceeds the degree bound of the
+    /// [`Rank`].
+    pub fn mul(&self, other: &Self) -> Self {
+        let (Some(a), Some(b)) = (self.degree(), other.degree()) else {
+            return Self::new();
+        };
+        assert!(
+            a + b < R::num_coeffs(),
+            "product of polynomials exceeds the degree bound of the rank"
+        );
+
+        let domain = Domain::new((a + b + 1).next_power_of_two().ilog2());
+        let mut lhs = self.coeffs[..=a].to_vec();
+        let mut rhs = other.coeffs[..=b].to_vec();
+        lhs.resize(domain.n(), F::ZERO);
+        rhs.resize(domain.n(), F::ZERO);
+
+        domain.fft(&mut lhs);
+        domain.fft(&mut rhs);
+        lhs.iter_mut().zip(rhs.iter()).for_each(|(l, r)| *l *= r);
+        domain.ifft(&mut lhs);
+
+        Self::from_coeffs(lhs)
+    }
+
+    /// Divides this polynomial by the vanishing polynomial $X^n - 1$ of the
+    /// provided [`Domain`] of size $n$, returning the quotient and the
+    /// remainder.
+    pub fn div_vanishing(&self, domain: &Domain<F>) -> (Self, Self) {
+        let n = domain.n();
+        let mut quotient = Self::new();
+        let mut remainder = self.clone();
+        for i in (n..remainder.coeffs.len()).rev() {
+            let coeff = core::mem::replace(&mut remainder.coeffs[i], F::ZERO);
+            quotient.coeffs[i - n] = coeff;
+            remainder.coeffs[i - n] += coeff;
+        }
+
+        (quotient, remainder)
     }
 }
 
@@ -193,3 +360,111 @@ fn test_add_structured() {
 
     assert_eq!(expected.coeffs, computed.coeffs);
 }
+
+#[test]
+fn test_mul() {
+    use ragu_pasta::Fp;
+    use rand::thread_rng;
+
+    type R = super::R<6>;
+
+    for (a, b) in [(0, 0), (1, 5), (17, 20), (31, 32)] {
+        let p =
+            Polynomial::<Fp, R>::from_coeffs((0..=a).map(|_| Fp::random(thread_rng())).collect());
+        let q =
+            Polynomial::<Fp, R>::from_coeffs((0..=b).map(|_| Fp::random(thread_rng())).collect());
+        let product = p.mul(&q);
+        assert_eq!(product.degree(), Some(a + b));
+
+        let x = Fp::random(thread_rng());
+        assert_eq!(product.eval(x), p.eval(x) * q.eval(x));
+    }
+
+    let p = Polynomial::<Fp, R>::random(&mut thread_rng());
+    assert_eq!(p.mul(&Polynomial::new()), Polynomial::new());
+}
+
+#[test]
+#[should_panic]
+fn test_mul_exceeds_rank() {
+    use ragu_pasta::Fp;
+    use rand::thread_rng;
+
+    type R = super::R<6>;
+
+    let p = Polynomial::<Fp, R>::from_coeffs((0..33).map(|_| Fp::random(thread_rng())).collect());
+    p.mul(&p);
+}
+
+#[test]
+fn test_div_linear() {
+    use ragu_pasta::Fp;
+    use rand::thread_rng;
+
+    type R = super::R<6>;
+
+    let p = Polynomial::<Fp, R>::random(&mut thread_rng());
+    let u = Fp::random(thread_rng());
+    let (quotient, remainder) = p.div_linear(u);
+    assert_eq!(remainder, p.eval(u));
+    assert_eq!(quotient.degree(), Some(R::num_coeffs() - 2));
+
+    let x = Fp::random(thread_rng());
+    assert_eq!(quotient.eval(x) * (x - u) + remainder, p.eval(x));
+
+    let s = super::structured::Polynomial::<Fp, R>::random(&mut thread_rng());
+    assert_eq!(s.div_linear(u), s.unstructured().div_linear(u));
+}
+
+#[test]
+fn test_div_vanishing() {
+    use ragu_pasta::Fp;
+    use rand::thread_rng;
+
+    type R = super::R<6>;
+
+    let p = Polynomial::<Fp, R>::random(&mut thread_rng());
+    for k in 0..=R::RANK {
+        let domain = Domain::new(k);
+        let (quotient, remainder) = p.div_vanishing(&domain);
+        assert!(remainder.degree() < Some(domain.n()));
+
+        let x = Fp::random(thread_rng());
+        let vanishing = x.pow([domain.n() as u64]) - Fp::ONE;
+        assert_eq!(quotient.eval(x) * vanishing + remainder.eval(x), p.eval(x));
+    }
+
+    let s = super::structured::Polynomial::<Fp, R>::random(&mut thread_rng());
+    let domain = Domain::new(3);
+    assert_eq!(
+        s.div_vanishing(&domain),
+        s.unstructured().div_vanishing(&domain)
+    );
+}
+
+#[test]
+fn test_interpolate() {
+    use ragu_pasta::Fp;
+    use rand::thread_rng;
+
+    type R = super::R<6>;
+
+    let points: Vec<_> = (0..20)
+        .map(|_| (Fp::random(thread_rng()), Fp::random(thread_rng())))
+        .collect();
+    let p = Polynomial::<Fp, R>::interpolate(&points);
+    assert!(p.degree() < Some(points.len()));
+    for (x, y) in points {
+        assert_eq!(p.eval(x), y);
+    }
+    assert_eq!(Polynomial::<Fp, R>::interpolate(&[]), Polynomial::new());
+
+    let domain = Domain::<Fp>::new(4);
+    let evals: Vec<_> = (0..domain.n()).map(|_| Fp::random(thread_rng())).collect();
+    let p = Polynomial::<Fp, R>::from_domain_evals(evals.clone());
+    let mut omega_i = Fp::ONE;
+    for eval in evals {
+        assert_eq!(p.eval(omega_i), eval);
+        omega_i *= domain.omega();
+    }
+}
@@ -6,21 +6,26 @@
 //! Individual circuits in Ragu are represented by a bivariate polynomial
 //! $s_i(X, Y)$. Multiple circuits are used over any particular field throughout
 //! Ragu's PCD construction, and so the [`Registry`] structure represents a larger
-//! polynomial $m(W, X, Y)$ that interpolates such that $m(\omega^i, X, Y) =
-//! s_i(X, Y)$ for some $\omega \in \mathbb{F}$ of sufficiently high $2^k$ order
-//! to encode all circuits for both PCD and for application circuits.
+//! polynomial $m(W, X, Y)$ that interpolates such that $m(\omega_i, X, Y) =
+//! s_i(X, Y)$ for distinct domain elements $\omega_i \in \mathbb{F}$, one for
+//! each circuit used for both PCD and for application circuits.
+//!
+//! The domain is a [`CosetDomain`]: a radix-2 domain of sufficiently high
+//! $2^k$ order whenever the field has one, and otherwise a union of cosets of
+//! the largest radix-2 domain in the field. The latter allows registries over
+//! fields of low 2-adicity, such as those of the secp256k1 curve.
 //!
 //! The [`RegistryBuilder`] structure is used to construct a new [`Registry`] by
 //! inserting circuits and performing a [`finalize`](RegistryBuilder::finalize) step
 //! to compile the added circuits into a registry polynomial representation that can
 //! be efficiently evaluated at different restrictions.
 
-use arithmetic::{Domain, PoseidonPermutation, bitreverse};
+use arithmetic::{CosetDomain, PoseidonPermutation};
 use ff::{Field, PrimeField};
 use ragu_core::{Error, Result, drivers::emulator::Emulator, maybe::Maybe};
 use ragu_primitives::{Element, poseidon::Sponge};
 
-use alloc::{boxed::Box, collections::btree_map::BTreeMap, vec::Vec};
+use alloc::{boxed::Box, collections::btree_map::BTreeMap, vec, vec::Vec};
 
 use crate::{
     Circuit, CircuitExt, CircuitObject,
@@ -48,16 +53,16 @@ impl CircuitIndex {
     ///
     /// The $i$th circuit added to any [`Registry`] (for a given [`PrimeField`] `F`) is
     /// assigned the domain element of smallest multiplicative order not yet
-    /// assigned to any circuit prior to $i$. This corresponds with $\Omega^{f(i)}$
-    /// where $f(i)$ is the [`S`](PrimeField::S)-bit reversal of `i` and $\Omega$ is
-    /// the primitive [root of unity](PrimeField::ROOT_OF_UNITY) of order $2^{S}$ in
-    /// `F`.
+    /// assigned to any circuit prior to $i$. For $i < 2^S$ this corresponds with
+    /// $\Omega^{f(i)}$ where $f(i)$ is the [`S`](PrimeField::S)-bit reversal of `i`
+    /// and $\Omega$ is the primitive [root of unity](PrimeField::ROOT_OF_UNITY) of
+    /// order $2^{S}$ in `F`. Circuits beyond the first $2^S$ are assigned to
+    /// cosets of the roots of unity; see [`CosetDomain::element`].
     ///
     /// Notably, the result of this function does not depend on the actual size of
     /// the [`Registry`]'s interpolation polynomial domain.
     pub fn omega_j<F: PrimeField>(self) -> F {
-        let bit_reversal_id = bitreverse(self.0, F::S);
-        F::ROOT_OF_UNITY.pow([bit_reversal_id.into()])
+        CosetDomain::<F>::element(self.0 as usize)
     }
 }
 
@@ -160,6 +165,9 @@ impl<'params, F: PrimeField, R: Rank> RegistryBuilder<'params, F, R> {
     /// This ordering ensures internal masks can be optimized separately while
     /// maintaining proper PCD indexing where internal items occupy indices
     /// $0 \ldots N$ and application steps occupy indices $N$ onward.
+    ///
+    /// Returns [`Error::CircuitBoundExceeded`] if there are more circuits than
+    /// the [`Rank`] supports.
     pub fn finalize<P: PoseidonPermutation<F>>(
         self,
         poseidon: &P,
@@ -169,8 +177,9 @@ impl<'params, F: PrimeField, R: Rank> RegistryBuilder<'params, F, R> {
             return Err(Error::CircuitBoundExceeded(total_circuits));
         }
 
-        let log2_circuits = self.log2_circuits();
-        let domain = Domain::<F>::new(log2_circuits);
+        let domain = CosetDomain::<F>::new(total_circuits);
+        let num_internal_masks = self.internal_masks.len();
+        let num_internal_circuits = self.num_internal_circuits();
 
         let circuits: Vec<_> = self
             .internal_masks
@@ -183,16 +192,13 @@ impl<'params, F: PrimeField, R: Rank> RegistryBuilder<'params, F, R> {
         let mut omega_lookup = BTreeMap::new();
 
         for i in 0..circuits.len() {
-            // Rather than assigning the `i`th circuit to `omega^i` in the final
-            // domain, we will assign it to `omega^j` where `j` is the
-            // `log2_circuits` bit-reversal of `i`. This has the property that
-            // `omega^j` = `F::ROOT_OF_UNITY^m` where `m` is the `F::S` bit
-            // reversal of `i`, which can be computed independently of `omega`
-            // and the actual (ideal) choice of `log2_circuits`. In effect, this
-            // is *implicitly* performing domain extensions as smaller domains
-            // become exhausted.
-            let j = bitreverse(i as u32, log2_circuits) as usize;
-            let omega_j = OmegaKey::from(domain.omega().pow([j as u64]));
+            // Rather than assigning the `i`th circuit to the `i`th element of
+            // the final domain in its natural order, we will assign it to the
+            // element `CircuitIndex::omega_j` which can be computed
+            // independently of the actual (ideal) size of the domain. In
+            // effect, this is *implicitly* performing domain extensions as
+            // smaller domains become exhausted.
+            let omega_j = OmegaKey::from(CircuitIndex::new(i).omega_j::<F>());
             omega_lookup.insert(omega_j, i);
         }
 
@@ -201,6 +207,8 @@ impl<'params, F: PrimeField, R: Rank> RegistryBuilder<'params, F, R> {
             domain,
             circuits,
             omega_lookup,
+            num_internal_masks,
+            num_internal_circuits,
             key: Key::default(),
         };
         registry.key = Key::new(registry.compute_registry_digest(poseidon));
@@ -297,37 +305,55 @@ impl<F: Field> Key<F> {
 /// circuits are combined together using an interpolation polynomial so that
 /// they can be queried efficiently.
 pub struct Registry<'params, F: PrimeField, R: Rank> {
-    domain: Domain<F>,
+    domain: CosetDomain<F>,
     circuits: Vec<Box<dyn CircuitObject<F, R> + 'params>>,
 
     /// Maps from the OmegaKey (which represents some `omega^j`) to the index `i`
     /// of the circuits vector.
-    omega_lookup: BTreeMap<OmegaKey, usize>,
+    omega_lookup: BTreeMap<OmegaKey<F>, usize>,
+
+    /// Number of internal masks, which occupy the first indices.
+    num_internal_masks: usize,
+
+    /// Number of internal circuits (masks + circuits), which precede the
+    /// application steps.
+    num_internal_circuits: usize,
 
     /// Registry key used to bind circuits to this registry.
     key: Key<F>,
 }
 
-/// Represents a key for identifying a unique $\omega^j$ value where $\omega$ is
-/// a $2^k$-th root of unity.
-#[derive(Ord, PartialOrd, PartialEq, Eq)]
-struct OmegaKey(u64);
+/// Represents a key for identifying a unique $\omega^j$ value of the registry
+/// domain.
+///
+/// The key is the canonical encoding of the field element, so that distinct
+/// roots of unity have distinct keys in any field regardless of its
+/// 2-adicity or modulus.
+struct OmegaKey<F: PrimeField>(F::Repr);
 
-impl<F: PrimeField> From<F> for OmegaKey {
+impl<F: PrimeField> From<F> for OmegaKey<F> {
     fn from(f: F) -> Self {
-        // Multiplication by 5 ensures the least significant 64 bits of the
-        // field element can be used as a key for all elements of order 2^k.
-        // TODO: This only holds for the Pasta curves. See issue #51
-        let product = f.double().double() + f;
+        OmegaKey(f.to_repr())
+    }
+}
+
+impl<F: PrimeField> PartialEq for OmegaKey<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref() == other.0.as_ref()
+    }
+}
 
-        let bytes = product.to_repr();
-        let byte_slice = bytes.as_ref();
+impl<F: PrimeField> Eq for OmegaKey<F> {}
 
-        OmegaKey(u64::from_le_bytes(
-            byte_slice[..8]
-                .try_into()
-                .expect("field representation is at least 8 bytes"),
-        ))
+impl<F: PrimeField> PartialOrd for OmegaKey<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: PrimeField> Ord for OmegaKey<F> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.as_ref().cmp(other.0.as_ref())
     }
 }
 
@@ -338,6 +364,18 @@ impl<F: PrimeField, R: Rank> Registry<'_, F, R> {
         &self.key
     }
 
+    /// Returns the number of internal masks, which occupy the first indices
+    /// of the registry.
+    pub fn num_internal_masks(&self) -> usize {
+        self.num_internal_masks
+    }
+
+    /// Returns the number of internal circuits (masks + circuits), which
+    /// precede the application steps in the registry.
+    pub fn num_internal_circuits(&self) -> usize {
+        self.num_internal_circuits
+    }
+
     /// Returns a slice of the circuit objects in this registry.
     pub fn circuits(&self) -> &[Box<dyn CircuitObject<F, R> + '_>] {
         &self.circuits
@@ -347,12 +385,11 @@ impl<F: PrimeField, R: Rank> Registry<'_, F, R> {
     pub fn xy(&self, x: F, y: F) -> unstructured::Polynomial<F, R> {
         let mut coeffs = unstructured::Polynomial::default();
         for (i, circuit) in self.circuits.iter().enumerate() {
-            let j = bitreverse(i as u32, self.domain.log2_n()) as usize;
-            coeffs[j] = circuit.sxy(x, y, &self.key);
+            coeffs[self.domain.slot(i)] = circuit.sxy(x, y, &self.key);
         }
         // Convert from the Lagrange basis.
         let domain = &self.domain;
-        domain.ifft(&mut coeffs[..domain.n()]);
+        domain.interpolate(&mut coeffs[..domain.n()]);
 
         coeffs
     }
@@ -411,6 +448,78 @@ impl<F: PrimeField, R: Rank> Registry<'_, F, R> {
         )
     }
 
+    /// Evaluate the registry polynomial unrestricted at $X$ for each of the
+    /// provided `ws`.
+    ///
+    /// Equivalent to calling [`Registry::wy`] for each point, but each
+    /// circuit polynomial is synthesized only once, so this is faster
+    /// whenever more than one point is requested. In exchange, a polynomial
+    /// is held for every slot of the registry domain (the number of circuits
+    /// rounded up to a power of two, or to a multiple of $2^S$ for fields of
+    /// low 2-adicity), rather than a single accumulator.
+    pub fn wy_many(&self, ws: &[F], y: F) -> Vec<structured::Polynomial<F, R>> {
+        let coeffs = self.w_many(|circuit| circuit.sy(y, &self.key));
+
+        ws.iter()
+            .map(|&w| structured::Polynomial::fold(coeffs.iter().rev(), w))
+            .collect()
+    }
+
+    /// Evaluate the registry polynomial unrestricted at $Y$ for each of the
+    /// provided `ws`.
+    ///
+    /// Equivalent to calling [`Registry::wx`] for each point, but each
+    /// circuit polynomial is synthesized only once, so this is faster
+    /// whenever more than one point is requested. In exchange, a polynomial
+    /// is held for every slot of the registry domain (the number of circuits
+    /// rounded up to a power of two, or to a multiple of $2^S$ for fields of
+    /// low 2-adicity), rather than a single accumulator.
+    pub fn wx_many(&self, ws: &[F], x: F) -> Vec<unstructured::Polynomial<F, R>> {
+        let coeffs = self.w_many(|circuit| circuit.sx(x, &self.key));
+        let vectors = coeffs.iter().rev().map(|c| &c[..]).collect::<Vec<_>>();
+
+        ws.iter()
+            .map(|&w| unstructured::Polynomial::from_coeffs(arithmetic::fold_vectors(&vectors, w)))
+            .collect()
+    }
+
+    /// Interpolates the polynomial-valued circuit evaluations over the
+    /// registry domain, returning the coefficients in $W$ of the registry
+    /// polynomial (in ascending order of degree) as ring elements.
+    ///
+    /// Rather than computing Lagrange coefficients for each requested point
+    /// as [`Registry::w`] does, all of the circuit polynomials are
+    /// transformed at once with [`CosetDomain::ring_interpolate`], after which
+    /// each point can be evaluated with Horner's method.
+    ///
+    /// The transform is dense, so this holds one polynomial for every slot
+    /// of the registry domain, including unoccupied ones. That is
+    /// `self.domain.n()` polynomials of `R::num_coeffs()` coefficients each.
+    /// Over a 256-bit field at `R<13>` this is 256 KiB per slot, or 64 MiB
+    /// for a registry of 256 circuits. The single-point methods instead keep
+    /// only one accumulated polynomial resident.
+    ///
+    /// In exchange, each circuit polynomial is synthesized once rather than
+    /// once per point. Over a radix-2 domain the transform adds only
+    /// $O(\log n)$ ring operations per slot, which is cheap next to synthesis.
+    /// A domain of $m$ cosets adds $O(m)$ more per slot and a second buffer of
+    /// `self.domain.n()` polynomials while it runs. So this is faster whenever
+    /// more than one point is requested; a single point should use
+    /// [`Registry::wy`] or [`Registry::wx`] directly.
+    fn w_many<P: arithmetic::Ring<R = P, F = F> + Clone + Default>(
+        &self,
+        poly: impl Fn(&dyn CircuitObject<F, R>) -> P,
+    ) -> Vec<P> {
+        let mut coeffs = vec![P::default(); self.domain.n()];
+        for (i, circuit) in self.circuits.iter().enumerate() {
+            coeffs[self.domain.slot(i)] = poly(&**circuit);
+        }
+        // Convert from the Lagrange basis.
+        self.domain.ring_interpolate::<P>(&mut coeffs);
+
+        coeffs
+    }
+
     /// Computes the polynomial restricted at $W$ based on the provided
     /// closures.
     fn w<T>(
@@ -420,7 +529,7 @@ impl<F: PrimeField, R: Rank> Registry<'_, F, R> {
         add_poly: impl Fn(&dyn CircuitObject<F, R>, F, &mut T),
     ) -> T {
         // Compute the Lagrange coefficients for the provided `w`.
-        let ell = self.domain.ell(w, self.domain.n());
+        let ell = self.domain.ell(w);
 
         let mut result = init();
 
@@ -428,11 +537,8 @@ impl<F: PrimeField, R: Rank> Registry<'_, F, R> {
             // The provided `w` was not in the domain, and `ell` are the
             // coefficients we need to use to separate each (partial) circuit
             // evaluation.
-            for (j, coeff) in ell.iter().enumerate() {
-                let i = bitreverse(j as u32, self.domain.log2_n()) as usize;
-                if let Some(circuit) = self.circuits.get(i) {
-                    add_poly(&**circuit, *coeff, &mut result);
-                }
+            for (i, circuit) in self.circuits.iter().enumerate() {
+                add_poly(&**circuit, ell[self.domain.slot(i)], &mut result);
             }
         } else if let Some(i) = self.omega_lookup.get(&OmegaKey::from(w)) {
             if let Some(circuit) = self.circuits.get(*i) {
@@ -479,11 +585,13 @@ mod tests {
     use crate::tests::SquareCircuit;
     use alloc::collections::BTreeSet;
     use alloc::collections::btree_map::BTreeMap;
-    use arithmetic::{Cycle, Domain, bitreverse};
+    use alloc::vec::Vec;
+    use arithmetic::{Cycle, Domain, PoseidonPermutation, bitreverse};
     use ff::Field;
-    use ff::PrimeField;
+    use ff::{FromUniformBytes, PrimeField};
     use ragu_core::Result;
     use ragu_pasta::{Fp, Pasta};
+    use ragu_secp::Secp;
     use rand::thread_rng;
 
     type TestRank = R<8>;
@@ -553,12 +661,103 @@ mod tests {
             assert_eq!(wxy_value, wy_poly.eval(x));
             assert_eq!(wxy_value, wx_poly.eval(y));
 
-            w *= registry.domain.omega();
+            w *= registry.domain.subgroup().omega();
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_w_many_consistency() -> Result<()> {
+        let poseidon = Pasta::circuit_poseidon(Pasta::baked());
+
+        let registry = TestRegistryBuilder::new()
+            .register_circuit(SquareCircuit { times: 2 })?
+            .register_circuit(SquareCircuit { times: 5 })?
+            .register_circuit(SquareCircuit { times: 10 })?
+            .register_circuit(SquareCircuit { times: 11 })?
+            .register_circuit(SquareCircuit { times: 19 })?
+            .finalize(poseidon)?;
+
+        let x = Fp::random(thread_rng());
+        let y = Fp::random(thread_rng());
+
+        let mut ws = (0..3).map(|_| Fp::random(thread_rng())).collect::<Vec<_>>();
+        ws.extend((0..registry.circuits().len()).map(|i| CircuitIndex::new(i).omega_j::<Fp>()));
+        ws.push(CircuitIndex::new(7).omega_j());
+
+        let wy_polys = registry.wy_many(&ws, y);
+        let wx_polys = registry.wx_many(&ws, x);
+        assert_eq!(wy_polys.len(), ws.len());
+        assert_eq!(wx_polys.len(), ws.len());
+
+        for ((&w, wy_poly), wx_poly) in ws.iter().zip(&wy_polys).zip(&wx_polys) {
+            let wxy_value = registry.wxy(w, x, y);
+            assert_eq!(wy_poly.eval(x), registry.wy(w, y).eval(x));
+            assert_eq!(wy_poly.eval(x), wxy_value);
+            assert_eq!(*wx_poly, registry.wx(w, x));
+            assert_eq!(wx_poly.eval(y), wxy_value);
         }
 
         Ok(())
     }
 
+    /// Checks a registry over a field whose 2-adicity is smaller than the
+    /// number of circuits, so that the registry domain spans several cosets.
+    fn check_low_two_adicity<F, P>(num_circuits: usize, poseidon: &P) -> Result<()>
+    where
+        F: PrimeField + FromUniformBytes<64>,
+        P: PoseidonPermutation<F>,
+    {
+        use crate::test_fixtures::SquareCircuit;
+
+        assert!(num_circuits > 1 << F::S);
+
+        let mut builder = RegistryBuilder::<F, TestRank>::new();
+        for i in 0..num_circuits {
+            builder = builder.register_circuit(SquareCircuit { times: i % 7 + 1 })?;
+        }
+        let registry = builder.finalize(poseidon)?;
+        assert!(registry.domain.num_cosets() > 1);
+
+        let x = F::random(thread_rng());
+        let y = F::random(thread_rng());
+
+        let mut ws = (0..3).map(|_| F::random(thread_rng())).collect::<Vec<_>>();
+        ws.extend((0..registry.domain.n()).map(|i| CircuitIndex::new(i).omega_j::<F>()));
+
+        let wy_polys = registry.wy_many(&ws, y);
+        let wx_polys = registry.wx_many(&ws, x);
+        let xy_poly = registry.xy(x, y);
+
+        for ((&w, wy_poly), wx_poly) in ws.iter().zip(&wy_polys).zip(&wx_polys) {
+            let wxy_value = registry.wxy(w, x, y);
+            assert_eq!(wxy_value, xy_poly.eval(w));
+            assert_eq!(wxy_value, registry.wy(w, y).eval(x));
+            assert_eq!(wxy_value, wy_poly.eval(x));
+            assert_eq!(*wx_poly, registry.wx(w, x));
+            assert_eq!(wxy_value, wx_poly.eval(y));
+        }
+
+        // Every circuit sits on a distinct element of the domain.
+        let keys = (0..num_circuits)
+            .map(|i| OmegaKey::from(CircuitIndex::new(i).omega_j::<F>()))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(keys.len(), num_circuits);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_low_two_adicity_registry() -> Result<()> {
+        let params = ragu_secp::SecpParams::generate_with_k(0);
+
+        // The base field of secp256k1 has 2-adicity 1 and its scalar field
+        // has 2-adicity 6.
+        check_low_two_adicity::<ragu_secp::Fp, _>(5, Secp::circuit_poseidon(&params))?;
+        check_low_two_adicity::<ragu_secp::Fq, _>(70, Secp::scalar_poseidon(&params))
+    }
+
     #[test]
     fn test_omega_lookup_correctness() -> Result<()> {
         let log2_circuits = 8;
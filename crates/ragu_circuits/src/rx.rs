@@ -16,6 +16,49 @@ use ragu_primitives::GadgetExt;
 
 use super::{Circuit, Rank, registry, structured};
 
+#[cfg(feature = "std")]
+mod parallel;
+
+#[cfg(feature = "std")]
+pub use parallel::eval_parallel;
+
+/// Assigns a wire to the $b$ position of the gate recorded in `available_b`,
+/// or otherwise creates a new gate and assigns the wire to its $a$ position.
+///
+/// This packs two allocations into one multiplication gate when possible,
+/// enabling consecutive allocations to share gates.
+fn alloc<F: Field, R: Rank>(
+    rx: &mut structured::View<'_, F, R, structured::Forward>,
+    available_b: &mut Option<usize>,
+    value: impl Fn() -> Result<Coeff<F>>,
+) -> Result<()> {
+    if let Some(index) = available_b.take() {
+        let a = rx.a[index];
+        let b = value()?;
+        rx.b[index] = b.value();
+        rx.c[index] = a * b.value();
+    } else {
+        let index = rx.a.len();
+        mul(rx, || Ok((value()?, Coeff::Zero, Coeff::Zero)))?;
+        *available_b = Some(index);
+    }
+
+    Ok(())
+}
+
+/// Appends a multiplication gate with the provided assignment.
+fn mul<F: Field, R: Rank>(
+    rx: &mut structured::View<'_, F, R, structured::Forward>,
+    values: impl Fn() -> Result<(Coeff<F>, Coeff<F>, Coeff<F>)>,
+) -> Result<((), (), ())> {
+    let (a, b, c) = values()?;
+    rx.a.push(a.value());
+    rx.b.push(b.value());
+    rx.c.push(c.value());
+
+    Ok(((), (), ()))
+}
+
 struct Evaluator<'a, F: Field, R: Rank> {
     rx: structured::View<'a, F, R, structured::Forward>,
     available_b: Option<usize>,
@@ -35,32 +78,14 @@ impl<'a, F: Field, R: Rank> Driver<'a> for Evaluator<'a, F, R> {
     const ONE: Self::Wire = ();
 
     fn alloc(&mut self, value: impl Fn() -> Result<Coeff<Self::F>>) -> Result<Self::Wire> {
-        // Packs two allocations into one multiplication gate when possible, enabling consecutive
-        // allocations to share gates.
-        if let Some(index) = self.available_b.take() {
-            let a = self.rx.a[index];
-            let b = value()?;
-            self.rx.b[index] = b.value();
-            self.rx.c[index] = a * b.value();
-            Ok(())
-        } else {
-            let index = self.rx.a.len();
-            self.mul(|| Ok((value()?, Coeff::Zero, Coeff::Zero)))?;
-            self.available_b = Some(index);
-            Ok(())
-        }
+        alloc(&mut self.rx, &mut self.available_b, value)
     }
 
     fn mul(
         &mut self,
         values: impl Fn() -> Result<(Coeff<Self::F>, Coeff<Self::F>, Coeff<Self::F>)>,
     ) -> Result<((), (), ())> {
-        let (a, b, c) = values()?;
-        self.rx.a.push(a.value());
-        self.rx.b.push(b.value());
-        self.rx.c.push(c.value());
-
-        Ok(((), (), ()))
+        mul(&mut self.rx, values)
     }
 
     fn add(&mut self, _: impl Fn(Self::LCadd) -> Self::LCadd) -> Self::Wire {}
@@ -0,0 +1,351 @@
+//! Multithreaded assembly of the $r(X)$ witness polynomial.
+//!
+//! The [`eval_parallel`] function produces the same $r(X)$ polynomial as
+//! [`eval`](super::eval), but leverages [routine
+//! predictions](Routine::predict) to synthesize routines concurrently. When a
+//! routine reports a [`Prediction::Known`] output, the driver reserves the
+//! multiplication gates that the routine occupies, continues synthesis with
+//! the predicted output immediately, and synthesizes the routine's witness on
+//! another thread. The resulting gate assignments are spliced into the
+//! reserved positions once the circuit has been fully synthesized.
+//!
+//! Threads are only spawned for routines occupying at least
+//! [`MIN_PARALLEL_GATES`] gates, and at most one thread per available core is
+//! live at any time; other routines are synthesized inline.
+
+use arithmetic::Coeff;
+use ff::Field;
+use ragu_core::{
+    Error, Result,
+    drivers::{Driver, DriverTypes, FromDriver, emulator::Emulator},
+    gadgets::GadgetKind,
+    maybe::{Always, Maybe, MaybeKind},
+    routines::{Prediction, Routine},
+};
+use ragu_primitives::GadgetExt;
+
+use alloc::vec::Vec;
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::thread::{Scope, ScopedJoinHandle};
+
+use crate::{Circuit, Rank, metrics, registry, structured};
+
+/// The $a, b, c$ assignments of a contiguous range of multiplication gates.
+type Gates<F> = (Vec<F>, Vec<F>, Vec<F>);
+
+/// A routine being synthesized on another thread, along with the range of
+/// multiplication gates reserved for it.
+struct Pending<'a, F: Field> {
+    offset: usize,
+    len: usize,
+    handle: ScopedJoinHandle<'a, Result<Gates<F>>>,
+}
+
+/// Routines that occupy fewer multiplication gates than this are synthesized
+/// inline, as the cost of spawning a thread would exceed that of synthesis.
+const MIN_PARALLEL_GATES: usize = 16;
+
+/// A gadget of kind `K` bound to the [`ParallelEvaluator`], which can be sent
+/// to another thread.
+///
+/// Gadgets are required to be [`Send`] whenever their driver's wires are, see
+/// the safety requirements of [`GadgetKind`]. The [`ParallelEvaluator`] has
+/// `()` wires, but this cannot be expressed generically to the compiler.
+struct SendGadget<'a, 'env, F: Field, R: Rank, K: GadgetKind<F>>(
+    K::Rebind<'a, ParallelEvaluator<'a, 'env, F, R>>,
+);
+
+// SAFETY: the wires of the `ParallelEvaluator` are `()`, which is `Send`, and
+// so gadgets bound to it are `Send` by the contract of the (unsafe)
+// `GadgetKind` trait.
+unsafe impl<F: Field, R: Rank, K: GadgetKind<F>> Send for SendGadget<'_, '_, F, R, K> {}
+
+/// Conversion utility for moving gadgets between drivers that both have `()`
+/// wires.
+struct Rebind<ND>(PhantomData<ND>);
+
+impl<'dr, 'new_dr, D: Driver<'dr, Wire = ()>, ND: Driver<'new_dr, F = D::F, Wire = ()>>
+    FromDriver<'dr, 'new_dr, D> for Rebind<ND>
+{
+    type NewDriver = ND;
+
+    fn convert_wire(&mut self, _: &()) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct ParallelEvaluator<'a, 'env, F: Field, R: Rank> {
+    scope: &'a Scope<'a, 'env>,
+    /// Number of additional threads that may still be spawned, shared by all
+    /// evaluators of the same circuit.
+    threads: &'env AtomicUsize,
+    rx: structured::Polynomial<F, R>,
+    available_b: Option<usize>,
+    pending: Vec<Pending<'a, F>>,
+}
+
+impl<'a, 'env, F: Field, R: Rank> ParallelEvaluator<'a, 'env, F, R> {
+    fn new(scope: &'a Scope<'a, 'env>, threads: &'env AtomicUsize) -> Self {
+        ParallelEvaluator {
+            scope,
+            threads,
+            rx: structured::Polynomial::new(),
+            available_b: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Claims one of the remaining threads, if any.
+    fn claim_thread(&self) -> bool {
+        self.threads
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Waits for all routines synthesized on other threads and splices their
+    /// gate assignments into the reserved positions.
+    fn finish(mut self) -> Result<structured::Polynomial<F, R>> {
+        let rx = &mut self.rx.forward();
+        for pending in self.pending {
+            let (a, b, c) = pending
+                .handle
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e))?;
+
+            if a.len() != pending.len {
+                return Err(Error::InvalidWitness(
+                    "routine synthesized an unexpected number of gates".into(),
+                ));
+            }
+
+            let range = pending.offset..pending.offset + pending.len;
+            rx.a[range.clone()].copy_from_slice(&a);
+            rx.b[range.clone()].copy_from_slice(&b);
+            rx.c[range].copy_from_slice(&c);
+        }
+
+        Ok(self.rx)
+    }
+
+    /// Reserves the `len` gates occupied by `routine` and synthesizes its
+    /// witness on another thread, which must have been claimed with
+    /// [`Self::claim_thread`].
+    fn spawn<Ro: Routine<F> + 'a>(
+        &mut self,
+        routine: Ro,
+        input: <Ro::Input as GadgetKind<F>>::Rebind<'a, Self>,
+        aux: Always<Ro::Aux<'a>>,
+        len: usize,
+    ) -> Result<()> {
+        let rx = self.rx.forward();
+        let offset = rx.a.len();
+        rx.a.resize(offset + len, F::ZERO);
+        rx.b.resize(offset + len, F::ZERO);
+        rx.c.resize(offset + len, F::ZERO);
+
+        let (scope, threads) = (self.scope, self.threads);
+        let input = SendGadget::<F, R, Ro::Input>(input);
+        let handle = scope.spawn(move || {
+            let input = input;
+            let result = (|| {
+                let mut dr = ParallelEvaluator::<'a, 'env, F, R>::new(scope, threads);
+                routine.execute(&mut dr, input.0, aux)?;
+                let mut rx = dr.finish()?;
+                let rx = rx.forward();
+
+                Ok((
+                    core::mem::take(rx.a),
+                    core::mem::take(rx.b),
+                    core::mem::take(rx.c),
+                ))
+            })();
+
+            // Release the thread before waiting to be joined.
+            threads.fetch_add(1, Ordering::AcqRel);
+            result
+        });
+
+        self.pending.push(Pending {
+            offset,
+            len,
+            handle,
+        });
+
+        Ok(())
+    }
+}
+
+impl<F: Field, R: Rank> DriverTypes for ParallelEvaluator<'_, '_, F, R> {
+    type ImplField = F;
+    type ImplWire = ();
+    type MaybeKind = Always<()>;
+    type LCadd = ();
+    type LCenforce = ();
+}
+
+impl<'a, F: Field, R: Rank> Driver<'a> for ParallelEvaluator<'a, '_, F, R> {
+    type F = F;
+    type Wire = ();
+    const ONE: Self::Wire = ();
+
+    fn alloc(&mut self, value: impl Fn() -> Result<Coeff<Self::F>>) -> Result<Self::Wire> {
+        super::alloc(&mut self.rx.forward(), &mut self.available_b, value)
+    }
+
+    fn mul(
+        &mut self,
+        values: impl Fn() -> Result<(Coeff<Self::F>, Coeff<Self::F>, Coeff<Self::F>)>,
+    ) -> Result<((), (), ())> {
+        super::mul(&mut self.rx.forward(), values)
+    }
+
+    fn add(&mut self, _: impl Fn(Self::LCadd) -> Self::LCadd) -> Self::Wire {}
+
+    fn enforce_zero(&mut self, _: impl Fn(Self::LCenforce) -> Self::LCenforce) -> Result<()> {
+        Ok(())
+    }
+
+    fn routine<Ro: Routine<Self::F> + 'a>(
+        &mut self,
+        routine: Ro,
+        input: <Ro::Input as GadgetKind<Self::F>>::Rebind<'a, Self>,
+    ) -> Result<<Ro::Output as GadgetKind<Self::F>>::Rebind<'a, Self>> {
+        // Temporarily store currently `available_b` to reset the allocation
+        // logic within the routine.
+        let tmp = self.available_b.take();
+        let mut dummy = Emulator::wireless();
+        let dummy_input = Ro::Input::map_gadget(&input, &mut dummy)?;
+        let result = match routine.predict(&mut dummy, &dummy_input)? {
+            Prediction::Known(output, aux) => {
+                let len = metrics::routine_gates(&routine, &input)?;
+                if len >= MIN_PARALLEL_GATES && self.claim_thread() {
+                    let output = Ro::Output::map_gadget(&output, &mut Rebind::<Self>(PhantomData))?;
+                    self.spawn(routine, input, aux, len)?;
+                    output
+                } else {
+                    routine.execute(self, input, aux)?
+                }
+            }
+            Prediction::Unknown(aux) => routine.execute(self, input, aux)?,
+        };
+
+        // Restore the allocation logic state, discarding the state from within
+        // the routine.
+        self.available_b = tmp;
+        Ok(result)
+    }
+}
+
+/// Computes the same $r(X)$ polynomial as [`eval`](super::eval), synthesizing
+/// routines with [`Prediction::Known`] outputs on other threads.
+pub fn eval_parallel<'witness, F: Field, C: Circuit<F>, R: Rank>(
+    circuit: &C,
+    witness: C::Witness<'witness>,
+    key: &registry::Key<F>,
+) -> Result<(structured::Polynomial<F, R>, C::Aux<'witness>)> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    eval_with_threads(circuit, witness, key, threads)
+}
+
+/// Implementation of [`eval_parallel`] which spawns at most `threads`
+/// concurrent threads.
+fn eval_with_threads<'witness, F: Field, C: Circuit<F>, R: Rank>(
+    circuit: &C,
+    witness: C::Witness<'witness>,
+    key: &registry::Key<F>,
+    threads: usize,
+) -> Result<(structured::Polynomial<F, R>, C::Aux<'witness>)> {
+    let threads = AtomicUsize::new(threads);
+    std::thread::scope(|scope| {
+        let mut dr = ParallelEvaluator::<F, R>::new(scope, &threads);
+        dr.mul(|| {
+            Ok((
+                Coeff::Arbitrary(key.value()),
+                Coeff::Arbitrary(key.inverse()),
+                Coeff::One,
+            ))
+        })?;
+        let (io, aux) = circuit.witness(&mut dr, Always::maybe_just(|| witness))?;
+        io.write(&mut dr, &mut ())?;
+
+        let mut rx = dr.finish()?;
+        let view = rx.forward();
+        if view.a.len() > R::n() || view.b.len() > R::n() || view.c.len() > R::n() {
+            return Err(Error::MultiplicationBoundExceeded(R::n()));
+        }
+
+        Ok((rx, aux.take()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::{R, txz};
+    use ragu_core::{drivers::DriverValue, gadgets::Kind};
+    use ragu_pasta::Fp;
+    use ragu_primitives::Element;
+
+    type MyRank = R<10>;
+
+    /// Interleaves predictable routines with ordinary allocations so that
+    /// spliced gates are surrounded by (and share state with) other gates.
+    struct RoutineCircuit;
+
+    impl Circuit<Fp> for RoutineCircuit {
+        type Instance<'source> = ();
+        type Witness<'source> = (Fp, Fp);
+        type Output = Kind![Fp; Element<'_, _>];
+        type Aux<'source> = ();
+
+        fn instance<'dr, 'source: 'dr, D: Driver<'dr, F = Fp>>(
+            &self,
+            _: &mut D,
+            _: DriverValue<D, Self::Instance<'source>>,
+        ) -> Result<<Self::Output as GadgetKind<Fp>>::Rebind<'dr, D>> {
+            unreachable!()
+        }
+
+        fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = Fp>>(
+            &self,
+            dr: &mut D,
+            witness: DriverValue<D, Self::Witness<'source>>,
+        ) -> Result<(
+            <Self::Output as GadgetKind<Fp>>::Rebind<'dr, D>,
+            DriverValue<D, Self::Aux<'source>>,
+        )> {
+            let (x, z) = witness.cast();
+            let mut x = Element::alloc(dr, x)?;
+            let z = Element::alloc(dr, z)?;
+            for _ in 0..3 {
+                let t = dr.routine(txz::Evaluate::<MyRank>::new(), (x.clone(), z.clone()))?;
+                let _ = Element::alloc(dr, t.value().map(|v| v.double()))?;
+                x = t.mul(dr, &x)?;
+            }
+
+            Ok((x, D::just(|| ())))
+        }
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() -> Result<()> {
+        let witness = (Fp::from(3), Fp::from(5));
+        let key = registry::Key::default();
+
+        let (expected, _) = super::super::eval::<_, _, MyRank>(&RoutineCircuit, witness, &key)?;
+        let expected = expected.iter_coeffs().collect::<Vec<_>>();
+
+        // Exercise synthesis with every routine inline, with some routines
+        // inline and with every routine on its own thread.
+        for threads in [0, 1, 3] {
+            let (actual, _) =
+                eval_with_threads::<_, _, MyRank>(&RoutineCircuit, witness, &key, threads)?;
+            assert_eq!(expected, actual.iter_coeffs().collect::<Vec<_>>());
+        }
+
+        Ok(())
+    }
+}
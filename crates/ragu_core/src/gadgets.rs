@@ -42,7 +42,9 @@
 //!    of `Vec`.
 //!
 //! 2. **No enum discriminants.** Which variant is active constitutes instance
-//!    state that affects synthesis.
+//!    state that affects synthesis. Enums of `Element`s can instead derive a
+//!    kind whose gadgets are tagged unions of fixed size; see
+//!    `ragu_primitives::tagged`.
 //!
 //! 3. **No non-witness runtime state.** Any runtime data must be _stable_
 //!    (identical across all instances of that type).
@@ -208,9 +210,12 @@ pub unsafe trait GadgetKind<F: Field>: core::any::Any {
 /// Automatically derives the [`Gadget`], [`GadgetKind`] and [`Clone`] traits
 /// for common gadget types.
 ///
-/// This only works for structs with named fields. Enums are disallowed because
-/// their discriminants constitute instance state that would violate the
-/// fungibility requirement.
+/// This works for structs with named fields. An enum cannot itself be a gadget
+/// because its discriminant constitutes instance state that would violate the
+/// fungibility requirement. Instead, deriving this on an enum whose variants
+/// consist only of `Element`s makes the enum's kind a `Variants` whose gadgets
+/// are `Tagged` unions: a tag element followed by the variant's elements,
+/// padded to the size of the largest variant. See `ragu_primitives::tagged`.
 ///
 /// ## Example
 ///
@@ -337,4 +342,7 @@ pub trait Consistent<'dr, D: Driver<'dr>>: Gadget<'dr, D> {
 }
 
 /// Derives [`Consistent`] by calling `enforce_consistent` on `#[ragu(gadget)]` fields.
+///
+/// On an enum of `Element`s, this instead makes its `Tagged` unions enforce that
+/// the tag is a valid variant index and that the padding is zero.
 pub use ragu_macros::Consistent;
@@ -35,7 +35,7 @@ impl GenericDriver {
         parse_quote!( < #( #args ),* > )
     }
 
-    fn rebind_arguments(
+    pub(super) fn rebind_arguments(
         &self,
         ty_generics: &AngleBracketedGenericArguments,
     ) -> AngleBracketedGenericArguments {
@@ -1,6 +1,7 @@
 pub mod consistent;
 pub mod gadget;
 pub mod gadgetwrite;
+pub mod variants;
 
 use proc_macro2::Span;
 use syn::{AngleBracketedGenericArguments, GenericArgument, Lifetime, Type, parse_quote};
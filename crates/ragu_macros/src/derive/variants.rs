@@ -0,0 +1,506 @@
+//! Derives for enums whose variants consist of `Element`s.
+//!
+//! The kind of such an enum is a `ragu_primitives::tagged::Variants` whose
+//! gadgets are `Tagged` unions, and the enum itself is a typed view of a single
+//! variant. See the `ragu_primitives::tagged` module for details.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    AngleBracketedGenericArguments, Data, DeriveInput, Error, Fields, GenericParam, Generics,
+    Ident, Result, parse_quote, spanned::Spanned,
+};
+
+use crate::{
+    helpers::{GenericDriver, attr_is},
+    path_resolution::{RaguCorePath, RaguPrimitivesPath},
+    substitution::replace_driver_field_in_generic_param,
+};
+
+struct Variant {
+    ident: Ident,
+    fields: Fields,
+    bindings: Vec<Ident>,
+}
+
+impl Variant {
+    /// Constructor for this variant using `values` for its fields.
+    fn build<'a>(
+        &self,
+        enum_ident: &Ident,
+        values: impl IntoIterator<Item = &'a TokenStream>,
+    ) -> TokenStream {
+        let ident = &self.ident;
+        let values = values.into_iter();
+        match &self.fields {
+            Fields::Named(named) => {
+                let names = named.named.iter().map(|f| &f.ident);
+                quote! { #enum_ident::#ident { #( #names: #values ),* } }
+            }
+            Fields::Unnamed(_) => quote! { #enum_ident::#ident ( #( #values ),* ) },
+            Fields::Unit => quote! { #enum_ident::#ident },
+        }
+    }
+
+    /// Pattern binding the fields of this variant to `self.bindings`.
+    fn pattern(&self, enum_ident: &Ident) -> TokenStream {
+        let ident = &self.ident;
+        let bindings = &self.bindings;
+        match &self.fields {
+            Fields::Named(_) => quote! { #enum_ident::#ident { #( #bindings ),* } },
+            Fields::Unnamed(_) => quote! { #enum_ident::#ident ( #( #bindings ),* ) },
+            Fields::Unit => quote! { #enum_ident::#ident },
+        }
+    }
+}
+
+struct Input {
+    enum_ident: Ident,
+    driver: GenericDriver,
+    impl_generics: Generics,
+    ty_generics: AngleBracketedGenericArguments,
+    variants: Vec<Variant>,
+}
+
+impl Input {
+    fn parse(input: &DeriveInput, derive: &str) -> Result<Self> {
+        let DeriveInput {
+            ident: enum_ident,
+            generics,
+            data,
+            ..
+        } = input;
+
+        let driver = GenericDriver::extract(generics)?;
+
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        if let Some(wc) = where_clause {
+            return Err(Error::new(
+                wc.span(),
+                format!("{derive} derive does not yet support where clauses"),
+            ));
+        }
+        let impl_generics = {
+            let mut impl_generics: Generics = parse_quote!( #impl_generics );
+            impl_generics.params.iter_mut().for_each(|gp| match gp {
+                GenericParam::Type(ty) if ty.ident == driver.ident => {
+                    // Strip out driver attribute if present
+                    ty.attrs.retain(|a| !attr_is(a, "driver"));
+                }
+                _ => {}
+            });
+            impl_generics
+        };
+        let ty_generics: AngleBracketedGenericArguments = { parse_quote!( #ty_generics ) };
+
+        let Data::Enum(e) = data else {
+            return Err(Error::new(
+                Span::call_site(),
+                format!("{derive} derive expected an enum"),
+            ));
+        };
+        if e.variants.is_empty() {
+            return Err(Error::new(
+                e.enum_token.span(),
+                format!("{derive} derive does not support enums without variants"),
+            ));
+        }
+
+        let mut variants = vec![];
+        for v in &e.variants {
+            if let Some((_, discriminant)) = &v.discriminant {
+                return Err(Error::new(
+                    discriminant.span(),
+                    format!("{derive} derive does not support explicit discriminants"),
+                ));
+            }
+            if let Some(f) = v.fields.iter().find(|f| {
+                ["value", "wire", "gadget", "phantom", "skip"]
+                    .iter()
+                    .any(|needle| f.attrs.iter().any(|a| attr_is(a, needle)))
+            }) {
+                return Err(Error::new(
+                    f.span(),
+                    format!("{derive} derive only supports enum variants of `Element`s"),
+                ));
+            }
+            let bindings = v
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, f)| f.ident.clone().unwrap_or_else(|| format_ident!("field{i}")))
+                .collect();
+            variants.push(Variant {
+                ident: v.ident.clone(),
+                fields: v.fields.clone(),
+                bindings,
+            });
+        }
+
+        Ok(Input {
+            enum_ident: enum_ident.clone(),
+            driver,
+            impl_generics,
+            ty_generics,
+            variants,
+        })
+    }
+
+    fn gadget_kind_generic_params(&self, driverfield_ident: &Ident) -> Generics {
+        let driver = &self.driver;
+        let mut params: Vec<GenericParam> = self
+            .impl_generics
+            .clone()
+            .params
+            .into_iter()
+            .filter(|gp| match gp {
+                // strip out driver
+                GenericParam::Type(ty) if ty.ident == driver.ident => false,
+                // strip out driver lifetime
+                GenericParam::Lifetime(lt) if lt.lifetime.ident == driver.lifetime.ident => false,
+                _ => true,
+            })
+            .collect();
+        for param in &mut params {
+            replace_driver_field_in_generic_param(param, &driver.ident, driverfield_ident);
+        }
+        params.push(parse_quote!( #driverfield_ident: ::ff::Field ));
+
+        parse_quote!( < #( #params ),* >)
+    }
+}
+
+pub fn derive_gadget(
+    input: DeriveInput,
+    ragu_core_path: RaguCorePath,
+    ragu_primitives_path: RaguPrimitivesPath,
+) -> Result<TokenStream> {
+    let input = Input::parse(&input, "Gadget")?;
+    let Input {
+        enum_ident,
+        driver,
+        impl_generics,
+        ty_generics,
+        variants,
+    } = &input;
+    let driverfield_ident = format_ident!("DriverField");
+    let driver_ident = &driver.ident;
+    let driver_lifetime = &driver.lifetime;
+
+    let clone_arms = variants.iter().map(|v| {
+        let pattern = v.pattern(enum_ident);
+        let inits = v
+            .bindings
+            .iter()
+            .map(|b| quote! { ::core::clone::Clone::clone(#b) })
+            .collect::<Vec<_>>();
+        let init = v.build(enum_ident, &inits);
+        quote! { #pattern => #init }
+    });
+
+    let clone_impl = quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::clone::Clone for #enum_ident #ty_generics {
+            fn clone(&self) -> Self {
+                match self {
+                    #( #clone_arms, )*
+                }
+            }
+        }
+    };
+
+    let gadget_kind_generic_params = input.gadget_kind_generic_params(&driverfield_ident);
+    let kind_subst_arguments = driver.kind_subst_arguments(ty_generics);
+    let rebind_arguments = driver.rebind_arguments(ty_generics);
+
+    let lengths = variants.iter().map(|v| v.bindings.len());
+
+    let into_parts_arms = variants.iter().enumerate().map(|(index, v)| {
+        let pattern = v.pattern(enum_ident);
+        let bindings = &v.bindings;
+        quote! {
+            #pattern => {
+                #( elements(#bindings); )*
+                #index
+            }
+        }
+    });
+
+    let from_parts_arms = variants.iter().enumerate().map(|(index, v)| {
+        let next = quote! { elements.next().expect("too few elements for variant") };
+        let values = v.bindings.iter().map(|_| next.clone()).collect::<Vec<_>>();
+        let init = v.build(enum_ident, &values);
+        quote! { #index => #init }
+    });
+
+    let variants_impl = quote! {
+        #[automatically_derived]
+        impl #gadget_kind_generic_params #ragu_primitives_path::tagged::Variants<#driverfield_ident> for #enum_ident #kind_subst_arguments {
+            const LENGTHS: &'static [usize] = &[ #( #lengths ),* ];
+
+            type View<#driver_lifetime, #driver_ident: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident>> = #enum_ident #rebind_arguments;
+
+            #[allow(unused_variables)]
+            fn into_parts<#driver_lifetime, #driver_ident: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident>>(
+                view: Self::View<#driver_lifetime, #driver_ident>,
+                elements: &mut impl FnMut(#ragu_primitives_path::Element<#driver_lifetime, #driver_ident>),
+            ) -> usize {
+                match view {
+                    #( #into_parts_arms )*
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn from_parts<#driver_lifetime, #driver_ident: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident>>(
+                index: usize,
+                elements: &mut impl Iterator<Item = #ragu_primitives_path::Element<#driver_lifetime, #driver_ident>>,
+            ) -> Self::View<#driver_lifetime, #driver_ident> {
+                match index {
+                    #( #from_parts_arms, )*
+                    _ => ::core::panic!("not the index of a variant"),
+                }
+            }
+        }
+    };
+
+    let gadgetkind_impl = quote! {
+        #[automatically_derived]
+        unsafe impl #gadget_kind_generic_params #ragu_core_path::gadgets::GadgetKind<#driverfield_ident> for #enum_ident #kind_subst_arguments {
+            type Rebind<#driver_lifetime, #driver_ident: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident>> = #ragu_primitives_path::Tagged<#driver_lifetime, #driver_ident, Self>;
+
+            fn map_gadget<#driver_lifetime, 'new_dr, #driver_ident: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident>, ND: #ragu_core_path::drivers::FromDriver<#driver_lifetime, 'new_dr, #driver_ident>>(
+                this: &Self::Rebind<#driver_lifetime, #driver_ident>,
+                ndr: &mut ND,
+            ) -> #ragu_core_path::Result<Self::Rebind<'new_dr, ND::NewDriver>> {
+                this.map_gadget(ndr)
+            }
+
+            fn enforce_equal_gadget<#driver_lifetime, D1: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident>, D2: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident, Wire = <D1 as #ragu_core_path::drivers::Driver<#driver_lifetime>>::Wire>>(
+                dr: &mut D1,
+                a: &Self::Rebind<#driver_lifetime, D2>,
+                b: &Self::Rebind<#driver_lifetime, D2>,
+            ) -> #ragu_core_path::Result<()> {
+                #ragu_primitives_path::Tagged::enforce_equal_gadget(dr, a, b)
+            }
+        }
+    };
+
+    Ok(quote! {
+        #clone_impl
+
+        #variants_impl
+
+        #gadgetkind_impl
+    })
+}
+
+pub fn derive_write(
+    input: DeriveInput,
+    ragu_core_path: RaguCorePath,
+    ragu_primitives_path: RaguPrimitivesPath,
+) -> Result<TokenStream> {
+    let input = Input::parse(&input, "Write")?;
+    let driverfield_ident = format_ident!("DriverField");
+    let enum_ident = &input.enum_ident;
+    let driver_ident = &input.driver.ident;
+    let driver_lifetime = &input.driver.lifetime;
+    let gadget_kind_generic_params = input.gadget_kind_generic_params(&driverfield_ident);
+    let kind_subst_arguments = input.driver.kind_subst_arguments(&input.ty_generics);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #gadget_kind_generic_params #ragu_primitives_path::io::Write<#driverfield_ident> for #enum_ident #kind_subst_arguments {
+            fn write_gadget<#driver_lifetime, #driver_ident: #ragu_core_path::drivers::Driver<#driver_lifetime, F = #driverfield_ident>, B: #ragu_primitives_path::io::Buffer<#driver_lifetime, #driver_ident> >(
+                this: &<Self as #ragu_core_path::gadgets::GadgetKind<#driverfield_ident>>::Rebind<#driver_lifetime, #driver_ident>,
+                dr: &mut #driver_ident,
+                buf: &mut B
+            ) -> #ragu_core_path::Result<()> {
+                this.write_gadget(dr, buf)
+            }
+        }
+    })
+}
+
+pub fn derive_consistent(
+    input: DeriveInput,
+    ragu_primitives_path: RaguPrimitivesPath,
+) -> Result<TokenStream> {
+    let input = Input::parse(&input, "Consistent")?;
+    let driverfield_ident = format_ident!("DriverField");
+    let enum_ident = &input.enum_ident;
+    let gadget_kind_generic_params = input.gadget_kind_generic_params(&driverfield_ident);
+    let kind_subst_arguments = input.driver.kind_subst_arguments(&input.ty_generics);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #gadget_kind_generic_params #ragu_primitives_path::tagged::ConsistentVariants<#driverfield_ident> for #enum_ident #kind_subst_arguments {}
+    })
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_enum_gadget_derive() {
+    let input: DeriveInput = parse_quote! {
+        #[derive(Gadget)]
+        enum Shape<'my_dr, #[ragu(driver)] MyD: Driver<'my_dr>, const N: usize> {
+            Circle { radius: Element<'my_dr, MyD> },
+            Rect(Element<'my_dr, MyD>, Element<'my_dr, MyD>),
+            Empty,
+        }
+    };
+
+    let result = derive_gadget(input, RaguCorePath::default(), RaguPrimitivesPath::default()).unwrap();
+
+    assert_eq!(
+        result.to_string(),
+        quote!(
+            #[automatically_derived]
+            impl<'my_dr, MyD: Driver<'my_dr>, const N: usize> ::core::clone::Clone for Shape<'my_dr, MyD, N> {
+                fn clone(&self) -> Self {
+                    match self {
+                        Shape::Circle { radius } => Shape::Circle { radius: ::core::clone::Clone::clone(radius) },
+                        Shape::Rect(field0, field1) => Shape::Rect(::core::clone::Clone::clone(field0), ::core::clone::Clone::clone(field1)),
+                        Shape::Empty => Shape::Empty,
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl<const N: usize, DriverField: ::ff::Field> ::ragu_primitives::tagged::Variants<DriverField>
+                for Shape<'static, ::core::marker::PhantomData<DriverField>, N>
+            {
+                const LENGTHS: &'static [usize] = &[1usize, 2usize, 0usize];
+
+                type View<'my_dr, MyD: ::ragu_core::drivers::Driver<'my_dr, F = DriverField>> = Shape<'my_dr, MyD, N>;
+
+                #[allow(unused_variables)]
+                fn into_parts<'my_dr, MyD: ::ragu_core::drivers::Driver<'my_dr, F = DriverField>>(
+                    view: Self::View<'my_dr, MyD>,
+                    elements: &mut impl FnMut(::ragu_primitives::Element<'my_dr, MyD>),
+                ) -> usize {
+                    match view {
+                        Shape::Circle { radius } => {
+                            elements(radius);
+                            0usize
+                        }
+                        Shape::Rect(field0, field1) => {
+                            elements(field0);
+                            elements(field1);
+                            1usize
+                        }
+                        Shape::Empty => {
+                            2usize
+                        }
+                    }
+                }
+
+                #[allow(unused_variables)]
+                fn from_parts<'my_dr, MyD: ::ragu_core::drivers::Driver<'my_dr, F = DriverField>>(
+                    index: usize,
+                    elements: &mut impl Iterator<Item = ::ragu_primitives::Element<'my_dr, MyD>>,
+                ) -> Self::View<'my_dr, MyD> {
+                    match index {
+                        0usize => Shape::Circle { radius: elements.next().expect("too few elements for variant") },
+                        1usize => Shape::Rect(elements.next().expect("too few elements for variant"), elements.next().expect("too few elements for variant")),
+                        2usize => Shape::Empty,
+                        _ => ::core::panic!("not the index of a variant"),
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            unsafe impl<const N: usize, DriverField: ::ff::Field> ::ragu_core::gadgets::GadgetKind<DriverField>
+                for Shape<'static, ::core::marker::PhantomData<DriverField>, N>
+            {
+                type Rebind<'my_dr, MyD: ::ragu_core::drivers::Driver<'my_dr, F = DriverField>> = ::ragu_primitives::Tagged<'my_dr, MyD, Self>;
+
+                fn map_gadget<'my_dr, 'new_dr, MyD: ::ragu_core::drivers::Driver<'my_dr, F = DriverField>, ND: ::ragu_core::drivers::FromDriver<'my_dr, 'new_dr, MyD>>(
+                    this: &Self::Rebind<'my_dr, MyD>,
+                    ndr: &mut ND,
+                ) -> ::ragu_core::Result<Self::Rebind<'new_dr, ND::NewDriver>> {
+                    this.map_gadget(ndr)
+                }
+
+                fn enforce_equal_gadget<'my_dr, D1: ::ragu_core::drivers::Driver<'my_dr, F = DriverField>, D2: ::ragu_core::drivers::Driver<'my_dr, F = DriverField, Wire = <D1 as ::ragu_core::drivers::Driver<'my_dr>>::Wire>>(
+                    dr: &mut D1,
+                    a: &Self::Rebind<'my_dr, D2>,
+                    b: &Self::Rebind<'my_dr, D2>,
+                ) -> ::ragu_core::Result<()> {
+                    ::ragu_primitives::Tagged::enforce_equal_gadget(dr, a, b)
+                }
+            }
+        ).to_string()
+    );
+}
+
+#[rustfmt::skip]
+#[test]
+fn test_enum_write_and_consistent_derive() {
+    let input: DeriveInput = parse_quote! {
+        enum Shape<'my_dr, #[ragu(driver)] MyD: Driver<'my_dr>> {
+            Circle { radius: Element<'my_dr, MyD> },
+            Empty,
+        }
+    };
+
+    let result = derive_write(input.clone(), RaguCorePath::default(), RaguPrimitivesPath::default()).unwrap();
+    assert_eq!(
+        result.to_string(),
+        quote!(
+            #[automatically_derived]
+            impl<DriverField: ::ff::Field> ::ragu_primitives::io::Write<DriverField>
+                for Shape<'static, ::core::marker::PhantomData<DriverField> >
+            {
+                fn write_gadget<'my_dr, MyD: ::ragu_core::drivers::Driver<'my_dr, F = DriverField>, B: ::ragu_primitives::io::Buffer<'my_dr, MyD> >(
+                    this: &<Self as ::ragu_core::gadgets::GadgetKind<DriverField>>::Rebind<'my_dr, MyD>,
+                    dr: &mut MyD,
+                    buf: &mut B
+                ) -> ::ragu_core::Result<()> {
+                    this.write_gadget(dr, buf)
+                }
+            }
+        ).to_string()
+    );
+
+    let result = derive_consistent(input, RaguPrimitivesPath::default()).unwrap();
+    assert_eq!(
+        result.to_string(),
+        quote!(
+            #[automatically_derived]
+            impl<DriverField: ::ff::Field> ::ragu_primitives::tagged::ConsistentVariants<DriverField>
+                for Shape<'static, ::core::marker::PhantomData<DriverField> >
+            {}
+        ).to_string()
+    );
+}
+
+#[test]
+fn test_fail_enum() {
+    let inputs: [DeriveInput; 3] = [
+        parse_quote! {
+            enum Empty<'my_dr, #[ragu(driver)] MyD: Driver<'my_dr>> {}
+        },
+        parse_quote! {
+            enum Discriminant<'my_dr, #[ragu(driver)] MyD: Driver<'my_dr>> {
+                A(Element<'my_dr, MyD>) = 1,
+            }
+        },
+        parse_quote! {
+            enum Annotated<'my_dr, #[ragu(driver)] MyD: Driver<'my_dr>> {
+                A(#[ragu(wire)] MyD::Wire),
+            }
+        },
+    ];
+
+    for input in inputs {
+        assert!(
+            derive_gadget(
+                input,
+                RaguCorePath::default(),
+                RaguPrimitivesPath::default()
+            )
+            .is_err(),
+            "Expected error for unsupported enum"
+        );
+    }
+}
@@ -17,7 +17,7 @@ mod proc;
 mod substitution;
 
 use proc_macro::TokenStream;
-use syn::{DeriveInput, LitInt, parse_macro_input};
+use syn::{Data, DeriveInput, LitInt, parse_macro_input};
 
 use helpers::macro_body;
 
@@ -59,6 +59,10 @@ pub fn derive_gadget(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     macro_body(|| {
         let ragu_core_path = path_resolution::RaguCorePath::resolve()?;
+        if let Data::Enum(_) = input.data {
+            let ragu_primitives_path = path_resolution::RaguPrimitivesPath::resolve()?;
+            return derive::variants::derive_gadget(input, ragu_core_path, ragu_primitives_path);
+        }
         derive::gadget::derive(input, ragu_core_path)
     })
 }
@@ -75,6 +79,9 @@ pub fn derive_write(input: TokenStream) -> TokenStream {
     macro_body(|| {
         let ragu_core_path = path_resolution::RaguCorePath::resolve()?;
         let ragu_primitives_path = path_resolution::RaguPrimitivesPath::resolve()?;
+        if let Data::Enum(_) = input.data {
+            return derive::variants::derive_write(input, ragu_core_path, ragu_primitives_path);
+        }
         derive::gadgetwrite::derive(input, ragu_core_path, ragu_primitives_path)
     })
 }
@@ -89,6 +96,10 @@ use ragu_core::gadgets::Consistent as _;
 pub fn derive_consistent(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     macro_body(|| {
+        if let Data::Enum(_) = input.data {
+            let ragu_primitives_path = path_resolution::RaguPrimitivesPath::resolve()?;
+            return derive::variants::derive_consistent(input, ragu_primitives_path);
+        }
         let ragu_core_path = path_resolution::RaguCorePath::resolve()?;
         derive::consistent::derive(input, ragu_core_path)
     })
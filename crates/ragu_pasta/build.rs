@@ -9,6 +9,8 @@ use std::{
     path::Path,
 };
 
+// Precomputed tables are only used at runtime.
+#[allow(dead_code)]
 mod common {
     include!("pasta_common.rs");
 }
@@ -33,6 +35,10 @@ fn write_params_for_curve<C: CurveAffine, W: Write>(v: &mut W, g: &[C], h: &C) -
 }
 
 fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=pasta_common.rs");
+    println!("cargo:rerun-if-env-changed=RAGU_PASTA_BAKED_K");
+
     if env::var("CARGO_FEATURE_BAKED").is_err() {
         return;
     }
@@ -40,7 +46,14 @@ fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
     let out_path = Path::new(&out_dir).join("pasta_parameters.bin");
 
-    let params = common::PastaParams::generate();
+    // The number of baked generators can be raised (to support larger ranks)
+    // by setting `RAGU_PASTA_BAKED_K` at build time.
+    let params = match env::var("RAGU_PASTA_BAKED_K") {
+        Ok(k) => common::PastaParams::generate_with_k(
+            k.parse().expect("RAGU_PASTA_BAKED_K should be an integer"),
+        ),
+        Err(_) => common::PastaParams::generate(),
+    };
 
     let mut f = File::create(out_path).unwrap();
     write_params_for_curve(&mut f, &params.pallas.g, &params.pallas.h).unwrap();
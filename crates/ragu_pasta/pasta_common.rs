@@ -1,4 +1,4 @@
-use arithmetic::CurveExt;
+use arithmetic::{CurveExt, FixedBaseTable};
 use group::{Curve, prime::PrimeCurveAffine};
 use pasta_curves::{
     EpAffine,
@@ -9,7 +9,7 @@ use pasta_curves::{
 
 use alloc::{vec, vec::Vec};
 
-const DOMAIN_PREFIX: &str = "Ragu-Parameters";
+pub(crate) const DOMAIN_PREFIX: &str = "Ragu-Parameters";
 
 pub const DEFAULT_EP_K: usize = 13;
 pub const DEFAULT_EQ_K: usize = 13;
@@ -25,29 +25,39 @@ pub struct PastaParams {
 pub struct PallasGenerators {
     pub(crate) g: Vec<EpAffine>,
     pub(crate) h: EpAffine,
+    pub(crate) table: Option<FixedBaseTable<EpAffine>>,
 }
 
 /// Fixed generators for the Vesta curve.
 pub struct VestaGenerators {
     pub(crate) g: Vec<EqAffine>,
     pub(crate) h: EqAffine,
+    pub(crate) table: Option<FixedBaseTable<EqAffine>>,
 }
 
-fn params_for_curve<C: CurveExt>(n: usize) -> (Vec<C::AffineExt>, C::AffineExt) {
+/// The hash-to-curve message for the `i`th `g` generator.
+pub(crate) fn g_message(i: u32) -> [u8; 5] {
+    let mut message = [0u8; 5];
+    message[1..5].copy_from_slice(&i.to_le_bytes());
+    message
+}
+
+/// The hash-to-curve message for the `h` generator.
+pub(crate) const H_MESSAGE: &[u8] = &[1];
+
+pub(crate) fn params_for_curve<C: CurveExt>(n: usize) -> (Vec<C::AffineExt>, C::AffineExt) {
     let g_projective = {
         let hasher = C::hash_to_curve(DOMAIN_PREFIX);
         let mut g = Vec::with_capacity(n);
         for i in 0..(n as u32) {
-            let mut message = [0u8; 5];
-            message[1..5].copy_from_slice(&i.to_le_bytes());
-            g.push(hasher(&message));
+            g.push(hasher(&g_message(i)));
         }
         g
     };
     let mut g = vec![C::AffineExt::identity(); n];
     Curve::batch_normalize(&g_projective[..], &mut g);
 
-    let h: C::AffineExt = C::hash_to_curve(DOMAIN_PREFIX)(&[1]).into();
+    let h: C::AffineExt = C::hash_to_curve(DOMAIN_PREFIX)(H_MESSAGE).into();
 
     (g, h)
 }
@@ -55,17 +65,36 @@ fn params_for_curve<C: CurveExt>(n: usize) -> (Vec<C::AffineExt>, C::AffineExt)
 impl PastaParams {
     /// Generate Pasta parameters at runtime via hash-to-curve.
     pub(crate) fn generate() -> Self {
-        let (ep_g, ep_h) = params_for_curve::<Ep>(1usize << DEFAULT_EP_K);
-        let (eq_g, eq_h) = params_for_curve::<Eq>(1usize << DEFAULT_EQ_K);
+        Self::generate_for_curves(DEFAULT_EP_K, DEFAULT_EQ_K)
+    }
+
+    /// Generate Pasta parameters at runtime via hash-to-curve, with $2^k$
+    /// generators for each curve.
+    ///
+    /// This supports committing to polynomials with up to $2^k$ coefficients,
+    /// and thus polynomial ranks up to `R<k>`. The generators are always a
+    /// prefix of those generated for any larger `k`.
+    ///
+    /// The result has no precomputed tables, even with the `precompute`
+    /// feature; see [`PastaParams::precompute`].
+    pub fn generate_with_k(k: usize) -> Self {
+        Self::generate_for_curves(k, k)
+    }
+
+    fn generate_for_curves(ep_k: usize, eq_k: usize) -> Self {
+        let (ep_g, ep_h) = params_for_curve::<Ep>(1usize << ep_k);
+        let (eq_g, eq_h) = params_for_curve::<Eq>(1usize << eq_k);
 
         PastaParams {
             pallas: PallasGenerators {
                 g: ep_g,
                 h: ep_h,
+                table: None,
             },
             vesta: VestaGenerators {
                 g: eq_g,
                 h: eq_h,
+                table: None,
             }
         }
     }
@@ -0,0 +1,428 @@
+//! Serialization of [`PastaParams`] to and from files.
+//!
+//! The encoding consists of a magic string, the number $n$ of `g` generators
+//! per curve as a little-endian `u32`, the Pallas `g` and `h` generators, the
+//! Vesta `g` and `h` generators, and finally a BLAKE2b digest of everything
+//! that precedes it. Each point is encoded as the canonical encodings of its
+//! affine $x$ and $y$ coordinates, as in the parameters baked by `build.rs`.
+//!
+//! When reading, every point is checked to be on its curve and the digest is
+//! checked to detect corruption. Because anyone can recompute the digest, it
+//! is also compared against the known digest of the canonical parameters with
+//! $n = 2^k$ generators (for $k$ up to [`MAX_CANONICAL_K`]), so that a file is
+//! accepted only if it is exactly the canonical parameters. Files with any
+//! other $n$ are instead checked by rederiving every generator via
+//! hash-to-curve, which costs as much as generating them.
+
+use arithmetic::CurveExt;
+use ff::PrimeField;
+use pasta_curves::arithmetic::CurveAffine;
+use pasta_curves::{Ep, Eq};
+
+use alloc::vec::Vec;
+use std::io::{self, Read, Write};
+
+use crate::common::{self, PallasGenerators, PastaParams, VestaGenerators};
+
+const MAGIC: [u8; 8] = *b"RAGUPAS1";
+const PERSONALIZATION: &[u8] = b"Ragu-Parameters";
+const DIGEST_LEN: usize = 64;
+
+/// The largest $k$ for which the digest of the canonical parameters with $2^k$
+/// generators per curve is known.
+const MAX_CANONICAL_K: usize = 20;
+
+/// Digests of the canonical parameters with $2^k$ generators per curve, as
+/// written by [`PastaParams::write`], indexed by $k$.
+const CANONICAL_DIGESTS: [[u8; DIGEST_LEN]; MAX_CANONICAL_K + 1] = [
+    hex(concat!(
+        "af020d000c049a801c85e83abc5c397feec8888d1b322508378771a971d512df",
+        "74c688494339d59cc61c1bfc8a97648989412b56ea642d633eb9e8cb482c23f1",
+    )),
+    hex(concat!(
+        "11544cee9b20a6e6080dc3bb72f9e57fdecf70537c520287726a57164206bbc7",
+        "d85dd4f17acd65a32be1ba78e7883f85ee51691ae8342d453efe2e36fd28953d",
+    )),
+    hex(concat!(
+        "3c449a3b805471c5e7b7a79d46aef58b5ad9d812ef0d74e5a56c48a5ca4853f0",
+        "bd9c2f77599f47641d6578f41c45bb69c032e2df9f4a94610fc1b599343e13ce",
+    )),
+    hex(concat!(
+        "bfc3a15e6f015914fc53ea12f8b74df1b602d41aefeeff8b84b8bf35958d92c7",
+        "74b4aabfc23a52462c836becd4113dabe6082918c8ccc75c4d6fe5eb1808b95e",
+    )),
+    hex(concat!(
+        "d86fe3f8b7b386783868ff683721918c4e677109dae4b8f4a60700f3f3868dc1",
+        "8db1cf19f5e9c8c4163a112ec2a8d93803539b704ddaac2194dc07fe33e7ef9a",
+    )),
+    hex(concat!(
+        "ad98d0602657eef541f12d91f3daf051a9de5191115098b4468d1408f2562e4c",
+        "6b50aa33d923e1131b7c9a68221b439ffb429b4ec0e22eb211725b2b8bc8b921",
+    )),
+    hex(concat!(
+        "86c18fd1843b5e7e100f7cc62d7e851b41a26ca7491df0a5bc898b8f4be680bb",
+        "86987bd4a67057d7c7266e37cc2854ad20e5417bfd7479c72075bdf923d9cfd5",
+    )),
+    hex(concat!(
+        "7a6a304f6807979800ee6918ffc0189fb86f01c9ec046e6f892f119ca069eefa",
+        "679ecd5e55d135d6965f04dd94fd5e399e6cbd058e282ba55cbfa7c150f34350",
+    )),
+    hex(concat!(
+        "33c5802f9bedd098878ad4a95907a190a8291eb8d532c69f332b0d7d133fda46",
+        "a9fc60855d1fa9eb6af2552bf40a5d3091d58d33ce842a67e17f61e4d6c2eeeb",
+    )),
+    hex(concat!(
+        "9eb84a3af49e7e48947e0e59e5b90d38b7dcf9e5af388842ac9eca00424c4104",
+        "267a9aaf8be198b3483c42c43d225b6565c89eb678d17fd0d0e141000b7f5f86",
+    )),
+    hex(concat!(
+        "bb07377e0623cdbd8aa22bda65b66c775fd0bf0280e006c8199ec03a677869f5",
+        "d4f407e69423fe8e13fdbc55bb0541ed4ba68a6b8cd1873a6cbf69cc4968abe1",
+    )),
+    hex(concat!(
+        "2fb7af190d070e22311361f8a0b5b61e08dea346a1c37c4037cedcc94b4ffe64",
+        "7d68d1d9bd5e4fdf51a8ac00c30d3b293d0c7751a18ec46080a05d454fd4152f",
+    )),
+    hex(concat!(
+        "ca363de1317117a0ec0fd2e5581a30cc95c82da45511b4a67fa89969326e18f7",
+        "0982044c9ee8bdf57fc1191e0780de14ba2f6615b72e0435f9797dfd74e8fb06",
+    )),
+    hex(concat!(
+        "652ca7a7cdc474774fdf414983658ec951b6d83e425c16ff61c0f56fcb5952e3",
+        "7ec6f9a8035a954f01c3bb98cc8388113e08aa663af66c5a1c93831cfdc30d2d",
+    )),
+    hex(concat!(
+        "25ceb6fde6e4641d45967633e617d60d5f7980e1e060826a45d51f62c53fbc9f",
+        "deb3a2e5e2c81f95ce563414b86c735f793b38d6024978c54b39eb88df9356dd",
+    )),
+    hex(concat!(
+        "ae29b4dc6e752ddb6893ddbf687e0641c418a4cdb19712895c5da77b9108433e",
+        "7858c88049e0bb1bd95732054fbfa10532c2ccb650faec0af959e501a58e1849",
+    )),
+    hex(concat!(
+        "e712d83a7337f19b6334cc8c0f4ab809d5c4509865faaf5fa38a89c0662ca77e",
+        "946a08cf39d9b89a8942cc6ff1d71809f4c759a9f7ba21e262926f4c80d92add",
+    )),
+    hex(concat!(
+        "e50a99e141fa556687d65f12b105c0916622c41e11dcdb0d53a62da44649bbc1",
+        "2aba7f35cede6db6fa78bea6efffa91a7d55cdc29a09f54714913808fddc967b",
+    )),
+    hex(concat!(
+        "454e7a7581076200061fc7c5e6f9a870ce4a88a9cca9e5e935eeba1a2948d44e",
+        "5f8fcb4fc1e080b10da6406b3f86c4e41af6c7baec6d36c0e9f99c044f3a3ab2",
+    )),
+    hex(concat!(
+        "8838c9e4cbebfb96a83c2c2dbfc4acc4e5a60e58dddd7d96765708ab1f9c510c",
+        "db4d7547f65b6e4d3ebb9f017a8046bde3764a43c00d9ae247572e4d1b8ab463",
+    )),
+    hex(concat!(
+        "613f825b6f3d7042214f890d8cc26ec31ae56b71be51e387981a5b39bc8ae4ad",
+        "fce916cafdf4bd5a79928b31738b0f9d2b4627852755aa664cd258da061912e5",
+    )),
+];
+
+const fn hex(s: &str) -> [u8; DIGEST_LEN] {
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            _ => panic!("invalid hex digit"),
+        }
+    }
+
+    let s = s.as_bytes();
+    assert!(s.len() == 2 * DIGEST_LEN);
+    let mut out = [0u8; DIGEST_LEN];
+    let mut i = 0;
+    while i < DIGEST_LEN {
+        out[i] = (nibble(s[2 * i]) << 4) | nibble(s[2 * i + 1]);
+        i += 1;
+    }
+    out
+}
+
+fn hasher() -> blake2b_simd::State {
+    blake2b_simd::Params::new()
+        .hash_length(DIGEST_LEN)
+        .personal(PERSONALIZATION)
+        .to_state()
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Writes to `inner` while hashing everything that is written.
+struct HashingWriter<'a, W> {
+    inner: &'a mut W,
+    state: blake2b_simd::State,
+}
+
+impl<W: Write> HashingWriter<'_, W> {
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.state.update(bytes);
+        self.inner.write_all(bytes)
+    }
+
+    fn write_point<C: CurveAffine>(&mut self, point: &C) -> io::Result<()> {
+        let xy = point
+            .coordinates()
+            .into_option()
+            .ok_or_else(|| invalid("parameters should not contain the identity"))?;
+        self.write_all(xy.x().to_repr().as_ref())?;
+        self.write_all(xy.y().to_repr().as_ref())
+    }
+
+    fn write_curve<C: CurveAffine>(&mut self, g: &[C], h: &C) -> io::Result<()> {
+        for point in g {
+            self.write_point(point)?;
+        }
+        self.write_point(h)
+    }
+}
+
+/// Reads from `inner` while hashing everything that is read.
+struct HashingReader<'a, R> {
+    inner: &'a mut R,
+    state: blake2b_simd::State,
+}
+
+impl<R: Read> HashingReader<'_, R> {
+    fn read_exact(&mut self, bytes: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(bytes)?;
+        self.state.update(bytes);
+        Ok(())
+    }
+
+    fn read_point<C: CurveAffine>(&mut self) -> io::Result<C> {
+        let mut x = <C::Base as PrimeField>::Repr::default();
+        let mut y = <C::Base as PrimeField>::Repr::default();
+        self.read_exact(x.as_mut())?;
+        self.read_exact(y.as_mut())?;
+
+        let x = Option::from(C::Base::from_repr(x)).ok_or_else(|| invalid("invalid coordinate"))?;
+        let y = Option::from(C::Base::from_repr(y)).ok_or_else(|| invalid("invalid coordinate"))?;
+
+        Option::from(C::from_xy(x, y)).ok_or_else(|| invalid("point is not on the curve"))
+    }
+
+    fn read_curve<C: CurveAffine>(&mut self, n: usize) -> io::Result<(Vec<C>, C)> {
+        let mut g = Vec::with_capacity(n.min(1 << 20));
+        for _ in 0..n {
+            g.push(self.read_point()?);
+        }
+        let h = self.read_point()?;
+
+        Ok((g, h))
+    }
+}
+
+/// Rederives all of the generators via hash-to-curve, comparing them against
+/// the provided ones.
+fn verify_full<C: CurveExt>(g: &[C::AffineExt], h: &C::AffineExt) -> io::Result<()> {
+    let (expected_g, expected_h) = common::params_for_curve::<C>(g.len());
+    if expected_g != g || expected_h != *h {
+        return Err(invalid(
+            "parameters do not match their hash-to-curve derivation",
+        ));
+    }
+
+    Ok(())
+}
+
+impl PastaParams {
+    /// Writes these parameters to `writer`, so that they can be loaded with
+    /// [`PastaParams::read`] instead of being regenerated.
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let n = self.pallas.g.len();
+        if self.vesta.g.len() != n {
+            return Err(invalid(
+                "both curves should have the same number of generators",
+            ));
+        }
+        let n = u32::try_from(n).map_err(|_| invalid("too many generators"))?;
+
+        let mut writer = HashingWriter {
+            inner: writer,
+            state: hasher(),
+        };
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&n.to_le_bytes())?;
+        writer.write_curve(&self.pallas.g, &self.pallas.h)?;
+        writer.write_curve(&self.vesta.g, &self.vesta.h)?;
+
+        let digest = writer.state.finalize();
+        writer.inner.write_all(digest.as_bytes())
+    }
+
+    /// Reads parameters previously written with [`PastaParams::write`].
+    ///
+    /// Returns an error of kind [`InvalidData`](io::ErrorKind::InvalidData)
+    /// if any point is invalid, if the digest does not match, or if the
+    /// parameters are not the canonical ones derived via hash-to-curve. This
+    /// is cheap when the number of generators is $2^k$ for $k$ up to 20, and
+    /// otherwise rederives every generator.
+    pub fn read<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut reader = HashingReader {
+            inner: reader,
+            state: hasher(),
+        };
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid("not a Pasta parameters file"));
+        }
+
+        let mut n = [0u8; 4];
+        reader.read_exact(&mut n)?;
+        let n = u32::from_le_bytes(n) as usize;
+        if n == 0 {
+            return Err(invalid("parameters should contain generators"));
+        }
+
+        let (ep_g, ep_h) = reader.read_curve(n)?;
+        let (eq_g, eq_h) = reader.read_curve(n)?;
+
+        let expected = reader.state.finalize();
+        let mut digest = [0u8; DIGEST_LEN];
+        reader.inner.read_exact(&mut digest)?;
+        if digest != expected.as_bytes() {
+            return Err(invalid("parameters digest mismatch"));
+        }
+
+        match CANONICAL_DIGESTS.get(n.trailing_zeros() as usize) {
+            Some(canonical) if n.is_power_of_two() => {
+                if digest != *canonical {
+                    return Err(invalid("parameters are not the canonical parameters"));
+                }
+            }
+            _ => {
+                verify_full::<Ep>(&ep_g, &ep_h)?;
+                verify_full::<Eq>(&eq_g, &eq_h)?;
+            }
+        }
+
+        #[allow(unused_mut)]
+        let mut params = PastaParams {
+            pallas: PallasGenerators {
+                g: ep_g,
+                h: ep_h,
+                table: None,
+            },
+            vesta: VestaGenerators {
+                g: eq_g,
+                h: eq_h,
+                table: None,
+            },
+        };
+        #[cfg(feature = "precompute")]
+        params.precompute();
+
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn written(params: &PastaParams) -> Vec<u8> {
+        let mut bytes = vec![];
+        params.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let params = PastaParams::generate_with_k(4);
+        let bytes = written(&params);
+        assert_eq!(bytes.len(), 8 + 4 + 2 * 17 * 64 + DIGEST_LEN);
+
+        let read = PastaParams::read(&mut &bytes[..]).unwrap();
+        assert_eq!(read.pallas.g, params.pallas.g);
+        assert_eq!(read.pallas.h, params.pallas.h);
+        assert_eq!(read.vesta.g, params.vesta.g);
+        assert_eq!(read.vesta.h, params.vesta.h);
+    }
+
+    #[test]
+    fn test_corrupted() {
+        let bytes = written(&PastaParams::generate_with_k(4));
+
+        // Flipping a bit in a coordinate yields an invalid point.
+        let mut corrupted = bytes.clone();
+        corrupted[12 + 64 * 3] ^= 1;
+        assert!(PastaParams::read(&mut &corrupted[..]).is_err());
+
+        // Truncated files are rejected.
+        assert!(PastaParams::read(&mut &bytes[..bytes.len() - 1]).is_err());
+
+        // Valid points with a stale digest are rejected.
+        let mut swapped = PastaParams::generate_with_k(4);
+        swapped.vesta.g.swap(2, 3);
+        let mut corrupted = written(&swapped);
+        let len = corrupted.len();
+        corrupted[len - DIGEST_LEN..].copy_from_slice(&bytes[len - DIGEST_LEN..]);
+        assert!(PastaParams::read(&mut &corrupted[..]).is_err());
+    }
+
+    #[test]
+    fn test_canonical_digests() {
+        for k in (0..=8).chain([common::DEFAULT_EP_K]) {
+            let bytes = written(&PastaParams::generate_with_k(k));
+            assert_eq!(bytes[bytes.len() - DIGEST_LEN..], CANONICAL_DIGESTS[k]);
+        }
+    }
+
+    /// Checks every entry of [`CANONICAL_DIGESTS`]. Generators are derived by
+    /// index, so the canonical parameters for each $k$ are a prefix of those
+    /// for [`MAX_CANONICAL_K`], which are generated only once.
+    #[test]
+    #[ignore = "generates 2^20 generators per curve"]
+    fn test_all_canonical_digests() {
+        let params = PastaParams::generate_with_k(MAX_CANONICAL_K);
+        for (k, canonical) in CANONICAL_DIGESTS.iter().enumerate() {
+            let n = 1 << k;
+            let prefix = PastaParams {
+                pallas: PallasGenerators {
+                    g: params.pallas.g[..n].to_vec(),
+                    h: params.pallas.h,
+                    table: None,
+                },
+                vesta: VestaGenerators {
+                    g: params.vesta.g[..n].to_vec(),
+                    h: params.vesta.h,
+                    table: None,
+                },
+            };
+            let bytes = written(&prefix);
+            assert_eq!(bytes[bytes.len() - DIGEST_LEN..], *canonical, "k = {k}");
+        }
+    }
+
+    #[test]
+    fn test_tampered() {
+        // Valid points with a recomputed digest are rejected because the
+        // digest is not the canonical one, however few generators differ.
+        let mut tampered = PastaParams::generate_with_k(4);
+        tampered.pallas.g[9] = tampered.pallas.h;
+        let bytes = written(&tampered);
+        assert!(PastaParams::read(&mut &bytes[..]).is_err());
+    }
+
+    #[test]
+    fn test_non_canonical_length() {
+        // Parameters whose length has no known digest are fully rederived.
+        let mut params = PastaParams::generate_with_k(4);
+        params.pallas.g.truncate(5);
+        params.vesta.g.truncate(5);
+        let read = PastaParams::read(&mut &written(&params)[..]).unwrap();
+        assert_eq!(read.pallas.g, params.pallas.g);
+        assert_eq!(read.vesta.g, params.vesta.g);
+
+        params.vesta.g.swap(1, 3);
+        assert!(PastaParams::read(&mut &written(&params)[..]).is_err());
+    }
+}
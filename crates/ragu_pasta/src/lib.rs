@@ -15,6 +15,23 @@
 //! [`Pasta::baked`] can then be used to obtain a `&'static PastaParams` with
 //! substantially lower computational cost for initialization, at the expense of
 //! a larger binary size.
+//!
+//! ## Precomputed Tables
+//!
+//! Commitments are multiscalar multiplications against the same fixed
+//! generators, which can be accelerated with a [`FixedBaseTable`] built by
+//! [`PastaParams::precompute`]. Tables for the default $2^{13}$ generators
+//! occupy about 10 MiB per curve and take a couple of seconds to build, so
+//! they are not built by default. The crate feature `precompute` builds them in
+//! [`Pasta::generate`], [`Pasta::baked`] (once, on first use) and
+//! [`PastaParams::read`]. Parameters returned by
+//! [`PastaParams::generate_with_k`] never have tables; call
+//! [`PastaParams::precompute`] on them if needed.
+//!
+//! Because `precompute` is covered by `--all-features`, every test process that
+//! uses [`Pasta::baked`] under that configuration builds the tables once. This
+//! is intentional, so that commitments through the tables are exercised end to
+//! end.
 
 #![no_std]
 #![allow(rustdoc::broken_intra_doc_links)]
@@ -26,6 +43,9 @@
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[macro_use]
 mod macros;
 
@@ -33,10 +53,12 @@ mod common {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/pasta_common.rs"));
 }
 
+#[cfg(feature = "std")]
+mod io;
 mod poseidon_fp;
 mod poseidon_fq;
 
-use arithmetic::{Cycle, FixedGenerators};
+use arithmetic::{Cycle, FixedBaseTable, FixedGenerators};
 
 pub use common::{PallasGenerators, PastaParams, VestaGenerators};
 pub use pasta_curves::{Ep, EpAffine, Eq, EqAffine, Fp, Fq};
@@ -82,7 +104,20 @@ impl Cycle for Pasta {
     }
 
     fn generate() -> Self::Params {
-        PastaParams::generate()
+        #[allow(unused_mut)]
+        let mut params = PastaParams::generate();
+        #[cfg(feature = "precompute")]
+        params.precompute();
+        params
+    }
+}
+
+impl PastaParams {
+    /// Precomputes [`FixedBaseTable`]s for the generators of both curves,
+    /// which are then used to compute commitments.
+    pub fn precompute(&mut self) {
+        self.pallas.precompute();
+        self.vesta.precompute();
     }
 }
 
@@ -94,6 +129,18 @@ impl FixedGenerators<pasta_curves::EpAffine> for PallasGenerators {
     fn h(&self) -> &pasta_curves::EpAffine {
         &self.h
     }
+
+    fn table(&self) -> Option<&FixedBaseTable<pasta_curves::EpAffine>> {
+        self.table.as_ref()
+    }
+}
+
+impl PallasGenerators {
+    fn precompute(&mut self) {
+        if self.table.is_none() {
+            self.table = Some(FixedBaseTable::new(self.g.iter().chain(Some(&self.h))));
+        }
+    }
 }
 
 impl FixedGenerators<pasta_curves::EqAffine> for VestaGenerators {
@@ -104,6 +151,47 @@ impl FixedGenerators<pasta_curves::EqAffine> for VestaGenerators {
     fn h(&self) -> &pasta_curves::EqAffine {
         &self.h
     }
+
+    fn table(&self) -> Option<&FixedBaseTable<pasta_curves::EqAffine>> {
+        self.table.as_ref()
+    }
+}
+
+impl VestaGenerators {
+    fn precompute(&mut self) {
+        if self.table.is_none() {
+            self.table = Some(FixedBaseTable::new(self.g.iter().chain(Some(&self.h))));
+        }
+    }
+}
+
+#[test]
+fn test_generate_with_k() {
+    let small = PastaParams::generate_with_k(3);
+    let large = PastaParams::generate_with_k(4);
+
+    assert_eq!(small.pallas.g.len(), 8);
+    assert_eq!(small.vesta.g.len(), 8);
+    assert_eq!(large.pallas.g.len(), 16);
+    assert_eq!(small.pallas.g[..], large.pallas.g[..8]);
+    assert_eq!(small.vesta.g[..], large.vesta.g[..8]);
+    assert_eq!(small.pallas.h, large.pallas.h);
+    assert_eq!(small.vesta.h, large.vesta.h);
+}
+
+#[test]
+fn test_precompute() {
+    let mut params = PastaParams::generate_with_k(6);
+    let coeffs: alloc::vec::Vec<Fq> = (0..40u64).map(|i| Fq::from(i * i + 7)).collect();
+    let terms = || (3..).zip(coeffs.iter());
+    let blind = Fq::from(1234);
+
+    let expected = params.pallas.commit(terms(), &blind);
+    assert!(params.pallas.table().is_none());
+
+    params.precompute();
+    assert_eq!(params.pallas.table().unwrap().len(), 65);
+    assert_eq!(params.pallas.commit(terms(), &blind), expected);
 }
 
 #[cfg(feature = "baked")]
@@ -144,20 +232,32 @@ mod baked {
         static ref PASTA_PARAMETERS: PastaParams = {
             let mut params = RAW_PARAMETERS;
 
-            let (ep_g, ep_h) = get_points_for_curve(&mut params, 1 << crate::common::DEFAULT_EP_K);
-            let (eq_g, eq_h) = get_points_for_curve(&mut params, 1 << crate::common::DEFAULT_EQ_K);
+            // Both curves have the same number of baked generators, followed
+            // by their `h` generator.
+            let n = RAW_PARAMETERS.len() / 64 / 2 - 1;
+            let (ep_g, ep_h) = get_points_for_curve(&mut params, n);
+            let (eq_g, eq_h) = get_points_for_curve(&mut params, n);
 
             assert_eq!(params.len(), 0);
 
-            PastaParams {
-                pallas: PallasGenerators { g: ep_g, h: ep_h },
-                vesta: VestaGenerators { g: eq_g, h: eq_h },
-            }
+            #[allow(unused_mut)]
+            let mut params = PastaParams {
+                pallas: PallasGenerators { g: ep_g, h: ep_h, table: None },
+                vesta: VestaGenerators { g: eq_g, h: eq_h, table: None },
+            };
+            #[cfg(feature = "precompute")]
+            params.precompute();
+
+            params
         };
     }
 
     impl Pasta {
         /// Returns a static reference to the baked-in parameters for the Pasta cycle.
+        ///
+        /// These contain $2^{13}$ generators per curve unless the crate was
+        /// built with the `RAGU_PASTA_BAKED_K` environment variable set to
+        /// some other $k$.
         pub fn baked() -> &'static PastaParams {
             &PASTA_PARAMETERS
         }
@@ -168,17 +268,12 @@ mod baked {
         use arithmetic::{Cycle, FixedGenerators};
 
         let params = Pasta::baked();
+        let n = Pasta::nested_generators(params).g().len();
 
-        assert_eq!(
-            Pasta::nested_generators(params).g().len(),
-            1 << crate::common::DEFAULT_EP_K
-        );
-        assert_eq!(
-            Pasta::host_generators(params).g().len(),
-            1 << crate::common::DEFAULT_EQ_K
-        );
+        assert!(n.is_power_of_two());
+        assert_eq!(Pasta::host_generators(params).g().len(), n);
 
-        let regenerated = Pasta::generate();
+        let regenerated = PastaParams::generate_with_k(n.trailing_zeros() as usize);
 
         assert_eq!(
             Pasta::nested_generators(params).g(),
@@ -23,6 +23,14 @@
 //! initial proofs that don't yet carry meaningful revdot claims. The constraint
 //! is enforced only when [`is_base_case`] returns false.
 //!
+//! ### Valid circuit IDs
+//!
+//! The circuit IDs in the [`preamble`] are enforced to be elements of the
+//! registry domain (the domain over which circuits are indexed), which are
+//! roots of unity unless the field has too few of them for every circuit.
+//! Other circuits can thus assume this check has been performed. It is done
+//! here rather than in [`hashes_1`], which is the largest internal circuit.
+//!
 //! ## Staging
 //!
 //! This circuit uses [`error_n`] as its final stage, which inherits in the
@@ -36,6 +44,7 @@
 //! inputs, providing the unified instance fields needed for verification.
 //!
 //! [`partial_collapse`]: super::partial_collapse
+//! [`hashes_1`]: super::hashes_1
 //! [$\mu'$]: unified::Output::mu_prime
 //! [$\nu'$]: unified::Output::nu_prime
 //! [$\mu$]: unified::Output::mu
@@ -46,7 +55,7 @@
 //! [`FoldProducts::fold_products_n`]: fold_revdot::FoldProducts::fold_products_n
 //! [`is_base_case`]: super::stages::preamble::Output::is_base_case
 
-use arithmetic::Cycle;
+use arithmetic::{CosetDomain, Cycle};
 use ragu_circuits::{
     polynomials::Rank,
     staging::{MultiStage, MultiStageCircuit, StageBuilder},
@@ -64,7 +73,7 @@ use super::{
     stages::{error_n, preamble},
     unified::{self, OutputBuilder},
 };
-use crate::components::fold_revdot;
+use crate::components::{fold_revdot, root_of_unity};
 
 pub(crate) use super::InternalCircuitIndex::FullCollapseCircuit as CIRCUIT_ID;
 
@@ -75,6 +84,7 @@ pub(crate) use super::InternalCircuitIndex::FullCollapseCircuit as CIRCUIT_ID;
 ///
 /// [module-level documentation]: self
 pub struct Circuit<C: Cycle, R, const HEADER_SIZE: usize, FP: fold_revdot::Parameters> {
+    domain: CosetDomain<C::CircuitField>,
     _marker: PhantomData<(C, R, FP)>,
 }
 
@@ -82,8 +92,14 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize, FP: fold_revdot::Parameters>
     Circuit<C, R, HEADER_SIZE, FP>
 {
     /// Creates a new multi-stage circuit for layer 2 revdot verification.
-    pub fn new() -> MultiStage<C::CircuitField, R, Self> {
+    ///
+    /// # Parameters
+    ///
+    /// - `total_circuits`: Number of circuits in the registry, which determines
+    ///   its domain. Used to verify circuit IDs are valid domain elements.
+    pub fn new(total_circuits: usize) -> MultiStage<C::CircuitField, R, Self> {
         MultiStage::new(Circuit {
+            domain: CosetDomain::new(total_circuits),
             _marker: PhantomData,
         })
     }
@@ -101,8 +117,9 @@ pub struct Witness<'a, C: Cycle, R: Rank, const HEADER_SIZE: usize, FP: fold_rev
     /// Witness for the [`preamble`] stage
     /// (unenforced).
     ///
-    /// Provides access to [`is_base_case`](super::stages::preamble::Output::is_base_case)
-    /// for conditional constraint enforcement.
+    /// Provides the circuit IDs of the child proofs and access to
+    /// [`is_base_case`](super::stages::preamble::Output::is_base_case) for
+    /// conditional constraint enforcement.
     pub preamble_witness: &'a preamble::Witness<'a, C, R, HEADER_SIZE>,
 
     /// Witness for the [`error_n`] stage
@@ -151,6 +168,10 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize, FP: fold_revdot::Parameters>
         let preamble = preamble.unenforced(dr, witness.view().map(|w| w.preamble_witness))?;
         let error_n = error_n.unenforced(dr, witness.view().map(|w| w.error_n_witness))?;
 
+        // Verify circuit IDs are elements of the registry domain.
+        root_of_unity::enforce(dr, preamble.left.circuit_id.clone(), &self.domain)?;
+        root_of_unity::enforce(dr, preamble.right.circuit_id.clone(), &self.domain)?;
+
         let unified_instance = &witness.view().map(|w| w.unified_instance);
         let mut unified_output = OutputBuilder::new();
 
@@ -30,13 +30,6 @@
 //! are witnessed in the [`error_n`][super::stages::error_n] stage and
 //! enforced to be consistent by this circuit.
 //!
-//! ### Valid circuit IDs
-//!
-//! The circuit IDs in the [`preamble`][super::stages::preamble] are
-//! enforced to be valid roots of unity in the registry domain (the domain over
-//! which circuits are indexed). Other circuits can thus assume this check has
-//! been performed.
-//!
 //! ## Staging
 //!
 //! This circuit is a multi-stage circuit based on the
@@ -49,13 +42,14 @@
 //!
 //! The public inputs are special for this internal circuit: they contain a
 //! concatenation of the unified instance and the `left` and `right` child
-//! proofs' output headers from the [`preamble`][super::stages::preamble]
-//! stage (i.e., the headers that the
+//! proofs' output headers and instances from the
+//! [`preamble`][super::stages::preamble] stage (i.e., the headers that the
 //! child steps produced, not the headers they consumed). This allows the
-//! verifier to ensure consistency with the headers enforced on the application
-//! (step) circuit. The other internal circuits mainly use the unified instance
-//! only to avoid the extra overhead of witnessing the `left`/`right` output
-//! headers in circuits that do not use the preamble stage.
+//! verifier to ensure consistency with the headers and child instances
+//! enforced on the application (step) circuit. The other internal circuits
+//! mainly use the unified instance only to avoid the extra overhead of
+//! witnessing the `left`/`right` output headers in circuits that do not use
+//! the preamble stage.
 //!
 //! The output is wrapped in a [`WithSuffix`] with a zero element appended. This
 //! ensures the public input serialization matches the $k(y)$ computation for
@@ -96,16 +90,16 @@ use super::{
     stages::{error_n as native_error_n, preamble as native_preamble},
     unified::{self, OutputBuilder},
 };
-use crate::components::{fold_revdot, root_of_unity, suffix::WithSuffix};
+use crate::components::{fold_revdot, suffix::WithSuffix};
 
 pub(crate) use super::InternalCircuitIndex::Hashes1Circuit as CIRCUIT_ID;
 
 /// Public output of the first hash circuit.
 ///
-/// This circuit uniquely includes the `left` and `right` output headers from
-/// the child proofs alongside the unified instance. The headers are needed as
-/// public inputs so the verifier can check consistency with the application
-/// (step) circuit's headers.
+/// This circuit uniquely includes the `left` and `right` output headers and
+/// instances from the child proofs alongside the unified instance. They are
+/// needed as public inputs so the verifier can check consistency with the
+/// application (step) circuit's headers and child instances.
 ///
 /// Other internal circuits use only the [`unified::Output`] to avoid the
 /// overhead of witnessing headers in circuits that do not require them.
@@ -120,6 +114,12 @@ pub struct Output<'dr, D: Driver<'dr>, C: Cycle<CircuitField = D::F>, const HEAD
     /// The right child proof's output header.
     #[ragu(gadget)]
     pub right_header: FixedVec<Element<'dr, D>, ConstLen<HEADER_SIZE>>,
+    /// The left child proof's instance.
+    #[ragu(gadget)]
+    pub left_instance: Element<'dr, D>,
+    /// The right child proof's instance.
+    #[ragu(gadget)]
+    pub right_instance: Element<'dr, D>,
 }
 
 /// First hash circuit for Fiat-Shamir challenge derivation.
@@ -130,7 +130,6 @@ pub struct Output<'dr, D: Driver<'dr>, C: Cycle<CircuitField = D::F>, const HEAD
 /// [module-level documentation]: self
 pub struct Circuit<'params, C: Cycle, R, const HEADER_SIZE: usize, FP: fold_revdot::Parameters> {
     params: &'params C::Params,
-    log2_circuits: u32,
     _marker: PhantomData<(R, FP)>,
 }
 
@@ -142,15 +141,9 @@ impl<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize, FP: fold_revdot::Para
     /// # Parameters
     ///
     /// - `params`: Curve cycle parameters providing Poseidon configuration.
-    /// - `log2_circuits`: Log₂ of the registry domain size (number of circuits).
-    ///   Used to verify circuit IDs are valid roots of unity.
-    pub fn new(
-        params: &'params C::Params,
-        log2_circuits: u32,
-    ) -> MultiStage<C::CircuitField, R, Self> {
+    pub fn new(params: &'params C::Params) -> MultiStage<C::CircuitField, R, Self> {
         MultiStage::new(Circuit {
             params,
-            log2_circuits,
             _marker: PhantomData,
         })
     }
@@ -219,10 +212,6 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize, FP: fold_revdot::Parameters>
         let preamble = preamble.unenforced(dr, witness.view().map(|w| w.preamble_witness))?;
         let error_n = error_n.unenforced(dr, witness.view().map(|w| w.error_n_witness))?;
 
-        // Verify circuit IDs are valid roots of unity in the registry domain.
-        root_of_unity::enforce(dr, preamble.left.circuit_id.clone(), self.log2_circuits)?;
-        root_of_unity::enforce(dr, preamble.right.circuit_id.clone(), self.log2_circuits)?;
-
         let unified_instance = &witness.view().map(|w| w.unified_instance);
         let mut unified_output = OutputBuilder::new();
 
@@ -287,12 +276,14 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize, FP: fold_revdot::Parameters>
                 .enforce_equal(dr, &error_n.sponge_state)?;
         }
 
-        // Output headers from preamble + unified instance. Verification with
-        // `unified_bridge_ky` ensures preamble headers match ApplicationProof
-        // headers.
+        // Output headers and instances from preamble + unified instance.
+        // Verification with `unified_bridge_ky` ensures preamble headers and
+        // instances match the ApplicationProof headers and child instances.
         let output = Output {
             left_header: preamble.left.output_header,
             right_header: preamble.right.output_header,
+            left_instance: preamble.left.instance,
+            right_instance: preamble.right.instance,
             unified: unified_output.finish_no_suffix(dr, unified_instance)?,
         };
 
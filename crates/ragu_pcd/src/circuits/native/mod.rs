@@ -44,12 +44,10 @@ pub(crate) enum InternalCircuitIndex {
 /// equal to the number of variants in [`InternalCircuitIndex`].
 pub(crate) const NUM_INTERNAL_CIRCUITS: usize = 13;
 
-/// Compute the total circuit count and log2 domain size from the number of
-/// application-defined steps.
-pub(crate) const fn total_circuit_counts(num_application_steps: usize) -> (usize, u32) {
-    let total_circuits = num_application_steps + step::NUM_INTERNAL_STEPS + NUM_INTERNAL_CIRCUITS;
-    let log2_circuits = total_circuits.next_power_of_two().trailing_zeros();
-    (total_circuits, log2_circuits)
+/// Compute the total circuit count from the number of application-defined
+/// steps.
+pub(crate) const fn total_circuit_count(num_application_steps: usize) -> usize {
+    num_application_steps + step::NUM_INTERNAL_STEPS + NUM_INTERNAL_CIRCUITS
 }
 
 impl InternalCircuitIndex {
@@ -67,7 +65,7 @@ impl InternalCircuitIndex {
 pub(crate) fn register_all<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>(
     mut registry: RegistryBuilder<'params, C::CircuitField, R>,
     params: &'params C::Params,
-    log2_circuits: u32,
+    total_circuits: usize,
 ) -> Result<RegistryBuilder<'params, C::CircuitField, R>> {
     let initial_internal_circuits = registry.num_internal_circuits();
 
@@ -128,7 +126,7 @@ pub(crate) fn register_all<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
             R,
             HEADER_SIZE,
             NativeParameters,
-        >::new(params, log2_circuits))?;
+        >::new(params))?;
 
         // hashes_2
         registry = registry.register_internal_circuit(hashes_2::Circuit::<
@@ -152,7 +150,7 @@ pub(crate) fn register_all<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
             R,
             HEADER_SIZE,
             NativeParameters,
-        >::new())?;
+        >::new(total_circuits))?;
 
         // compute_v
         registry =
@@ -13,6 +13,7 @@ use ragu_primitives::{Element, io::Write};
 
 use core::marker::PhantomData;
 
+#[cfg(feature = "prover")]
 use crate::Proof;
 
 pub(crate) use crate::circuits::native::InternalCircuitIndex::EvalStage as STAGING_ID;
@@ -39,23 +40,24 @@ pub struct ChildEvaluationsWitness<F> {
 
 impl<F: PrimeField> ChildEvaluationsWitness<F> {
     /// Create child evaluations witness from a proof evaluated at point u.
+    #[cfg(feature = "prover")]
     pub fn from_proof<C: Cycle<CircuitField = F>, R: Rank>(proof: &Proof<C, R>, u: F) -> Self {
         ChildEvaluationsWitness {
-            application: proof.application.rx.eval(u),
-            preamble: proof.preamble.native_rx.eval(u),
-            error_n: proof.error_n.native_rx.eval(u),
-            error_m: proof.error_m.native_rx.eval(u),
-            a_poly: proof.ab.a_poly.eval(u),
-            b_poly: proof.ab.b_poly.eval(u),
-            query: proof.query.native_rx.eval(u),
-            registry_xy_poly: proof.query.registry_xy_poly.eval(u),
-            eval: proof.eval.native_rx.eval(u),
-            p_poly: proof.p.poly.eval(u),
-            hashes_1: proof.circuits.hashes_1_rx.eval(u),
-            hashes_2: proof.circuits.hashes_2_rx.eval(u),
-            partial_collapse: proof.circuits.partial_collapse_rx.eval(u),
-            full_collapse: proof.circuits.full_collapse_rx.eval(u),
-            compute_v: proof.circuits.compute_v_rx.eval(u),
+            application: proof.application.rx.resident().eval(u),
+            preamble: proof.preamble.native_rx.resident().eval(u),
+            error_n: proof.error_n.native_rx.resident().eval(u),
+            error_m: proof.error_m.native_rx.resident().eval(u),
+            a_poly: proof.ab.a_poly.resident().eval(u),
+            b_poly: proof.ab.b_poly.resident().eval(u),
+            query: proof.query.native_rx.resident().eval(u),
+            registry_xy_poly: proof.query.registry_xy_poly.resident().eval(u),
+            eval: proof.eval.native_rx.resident().eval(u),
+            p_poly: proof.p.poly.resident().eval(u),
+            hashes_1: proof.circuits.hashes_1_rx.resident().eval(u),
+            hashes_2: proof.circuits.hashes_2_rx.resident().eval(u),
+            partial_collapse: proof.circuits.partial_collapse_rx.resident().eval(u),
+            full_collapse: proof.circuits.full_collapse_rx.resident().eval(u),
+            compute_v: proof.circuits.compute_v_rx.resident().eval(u),
         }
     }
 }
@@ -15,12 +15,9 @@ use ragu_primitives::{
     vec::{CollectFixed, ConstLen, FixedVec},
 };
 
-use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use crate::{
-    Proof, circuits::native::unified, components::ky::Ky, header::Header, step::internal::padded,
-};
+use crate::{Proof, circuits::native::unified, components::ky::Ky};
 
 pub(crate) use crate::circuits::native::InternalCircuitIndex::PreambleStage as STAGING_ID;
 
@@ -47,6 +44,7 @@ pub struct Witness<'a, C: Cycle, R: Rank, const HEADER_SIZE: usize> {
 
 impl<'a, C: Cycle, R: Rank, const HEADER_SIZE: usize> Witness<'a, C, R, HEADER_SIZE> {
     /// Create a witness from child proof references and pre-computed output headers.
+    #[cfg(feature = "prover")]
     pub fn new(
         left: &'a Proof<C, R>,
         right: &'a Proof<C, R>,
@@ -66,7 +64,8 @@ impl<'a, C: Cycle, R: Rank, const HEADER_SIZE: usize> Witness<'a, C, R, HEADER_S
     }
 }
 
-/// Headers claimed by a child proof for its own left and right children.
+/// Headers and instances claimed by a child proof for its own left and right
+/// children.
 #[derive(Gadget, Consistent)]
 pub struct ChildHeaders<'dr, D: Driver<'dr>, const HEADER_SIZE: usize> {
     /// Left child header (grandchild from current perspective).
@@ -75,6 +74,12 @@ pub struct ChildHeaders<'dr, D: Driver<'dr>, const HEADER_SIZE: usize> {
     /// Right child header (grandchild from current perspective).
     #[ragu(gadget)]
     pub right: HeaderVec<'dr, D, HEADER_SIZE>,
+    /// Left child instance (grandchild from current perspective).
+    #[ragu(gadget)]
+    pub left_instance: Element<'dr, D>,
+    /// Right child instance (grandchild from current perspective).
+    #[ragu(gadget)]
+    pub right_instance: Element<'dr, D>,
 }
 
 /// Processed inputs from a single child proof in the preamble stage.
@@ -87,6 +92,9 @@ pub struct ProofInputs<'dr, D: Driver<'dr>, C: Cycle<CircuitField = D::F>, const
     /// Output header of this child proof.
     #[ragu(gadget)]
     pub output_header: HeaderVec<'dr, D, HEADER_SIZE>,
+    /// Public instance of this child proof's application step.
+    #[ragu(gadget)]
+    pub instance: Element<'dr, D>,
     #[ragu(gadget)]
     pub circuit_id: Element<'dr, D>,
     #[ragu(gadget)]
@@ -101,7 +109,8 @@ impl<'dr, D: Driver<'dr, F = C::CircuitField>, C: Cycle, const HEADER_SIZE: usiz
     ///
     /// Returns `(unified_ky, unified_bridge_ky)` where:
     /// - `unified_ky` = k(y) for `(unified, 0)`
-    /// - `unified_bridge_ky` = k(y) for `(unified, children.left, children.right, 0)`
+    /// - `unified_bridge_ky` = k(y) for `(unified, children.left,
+    ///   children.right, children.left_instance, children.right_instance, 0)`
     pub fn unified_ky_values(
         &self,
         dr: &mut D,
@@ -119,6 +128,8 @@ impl<'dr, D: Driver<'dr, F = C::CircuitField>, C: Cycle, const HEADER_SIZE: usiz
             ({
                 self.children.left.write(dr, &mut ky)?;
                 self.children.right.write(dr, &mut ky)?;
+                self.children.left_instance.write(dr, &mut ky)?;
+                self.children.right_instance.write(dr, &mut ky)?;
                 Element::zero(dr).write(dr, &mut ky)?;
                 ky.finish(dr)?
             }),
@@ -127,12 +138,17 @@ impl<'dr, D: Driver<'dr, F = C::CircuitField>, C: Cycle, const HEADER_SIZE: usiz
 
     /// Compute k(y) for the application circuit instance.
     ///
-    /// Returns `application_ky` = k(y) for `(children.left, children.right, output_header)`.
+    /// Returns `application_ky` = k(y) for `(children.left, children.right,
+    /// output_header, instance, children.left_instance,
+    /// children.right_instance)`.
     pub fn application_ky(&self, dr: &mut D, y: &Element<'dr, D>) -> Result<Element<'dr, D>> {
         let mut ky = Ky::new(y);
         self.children.left.write(dr, &mut ky)?;
         self.children.right.write(dr, &mut ky)?;
         self.output_header.write(dr, &mut ky)?;
+        self.instance.write(dr, &mut ky)?;
+        self.children.left_instance.write(dr, &mut ky)?;
+        self.children.right_instance.write(dr, &mut ky)?;
         ky.finish(dr)
     }
 
@@ -146,11 +162,13 @@ impl<'dr, D: Driver<'dr, F = C::CircuitField>, C: Cycle, const HEADER_SIZE: usiz
 impl<'dr, D: Driver<'dr, F = C::CircuitField>, C: Cycle, const HEADER_SIZE: usize>
     ProofInputs<'dr, D, C, HEADER_SIZE>
 {
-    /// Allocate ProofInputs from a proof reference and pre-computed output header.
+    /// Allocate ProofInputs from a proof reference, pre-computed output header
+    /// and the public instance of the proof's application step.
     pub fn alloc<R: Rank>(
         dr: &mut D,
         proof: DriverValue<D, &Proof<C, R>>,
         output_header: DriverValue<D, &FixedVec<D::F, ConstLen<HEADER_SIZE>>>,
+        instance: DriverValue<D, D::F>,
     ) -> Result<Self> {
         fn alloc_header<'dr, D: Driver<'dr>, const N: usize>(
             dr: &mut D,
@@ -181,8 +199,17 @@ impl<'dr, D: Driver<'dr, F = C::CircuitField>, C: Cycle, const HEADER_SIZE: usiz
                     dr,
                     proof.view().map(|p| p.application.right_header.as_slice()),
                 )?,
+                left_instance: Element::alloc(
+                    dr,
+                    proof.view().map(|p| p.application.left_instance),
+                )?,
+                right_instance: Element::alloc(
+                    dr,
+                    proof.view().map(|p| p.application.right_instance),
+                )?,
             },
             output_header: alloc_header(dr, output_header.view().map(|h| &h[..]))?,
+            instance: Element::alloc(dr, instance)?,
             circuit_id: Element::alloc(
                 dr,
                 proof.view().map(|p| p.application.circuit_id.omega_j()),
@@ -190,35 +217,6 @@ impl<'dr, D: Driver<'dr, F = C::CircuitField>, C: Cycle, const HEADER_SIZE: usiz
             unified: unified::Output::alloc_from_proof(dr, proof)?,
         })
     }
-
-    /// Allocate ProofInputs from a proof reference and some unprocessed header
-    /// data.
-    pub fn alloc_for_verify<'source, R: Rank, H: Header<C::CircuitField>>(
-        dr: &mut D,
-        proof: DriverValue<D, &Proof<C, R>>,
-        header_data: DriverValue<D, H::Data<'source>>,
-    ) -> Result<Self>
-    where
-        'source: 'dr,
-    {
-        let header_data = D::with(|| {
-            use ragu_core::drivers::emulator::{Emulator, Wireless};
-            let emulator = &mut Emulator::<Wireless<D::MaybeKind, D::F>>::wireless();
-
-            let output = H::encode(emulator, header_data)?;
-            let output = padded::for_header::<H, HEADER_SIZE, _>(emulator, output)?;
-
-            let mut header_data = Vec::with_capacity(HEADER_SIZE);
-            output.write(emulator, &mut header_data)?;
-
-            header_data
-                .into_iter()
-                .map(|e| *e.value().take())
-                .collect_fixed()
-        })?;
-
-        Self::alloc(dr, proof, header_data.view())
-    }
 }
 
 /// Output of the native preamble stage.
@@ -254,8 +252,8 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> staging::Stage<C::CircuitField
     type OutputKind = Kind![C::CircuitField; Output<'_, _, C, HEADER_SIZE>];
 
     fn values() -> usize {
-        // 2 proofs * (3 headers * HEADER_SIZE + 1 circuit_id + unified instance wires)
-        2 * (3 * HEADER_SIZE + 1 + unified::NUM_WIRES)
+        // 2 proofs * (3 headers * HEADER_SIZE + 3 instances + 1 circuit_id + unified instance wires)
+        2 * (3 * HEADER_SIZE + 4 + unified::NUM_WIRES)
     }
 
     fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>>(
@@ -270,12 +268,14 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> staging::Stage<C::CircuitField
             dr,
             witness.view().map(|w| w.left.proof),
             witness.view().map(|w| &w.left.output_header),
+            witness.view().map(|w| w.left.proof.application.instance),
         )?;
 
         let right = ProofInputs::alloc(
             dr,
             witness.view().map(|w| w.right.proof),
             witness.view().map(|w| &w.right.output_header),
+            witness.view().map(|w| w.right.proof.application.instance),
         )?;
 
         Ok(Output { left, right })
@@ -16,10 +16,9 @@
 
 use arithmetic::Cycle;
 use ff::PrimeField;
-use ragu_circuits::{
-    polynomials::{Rank, structured, unstructured},
-    staging,
-};
+#[cfg(feature = "prover")]
+use ragu_circuits::polynomials::{structured, unstructured};
+use ragu_circuits::{polynomials::Rank, staging};
 use ragu_core::{
     Result,
     drivers::{Driver, DriverValue},
@@ -30,6 +29,7 @@ use ragu_primitives::Element;
 
 use core::marker::PhantomData;
 
+#[cfg(feature = "prover")]
 use crate::Proof;
 
 use crate::circuits::native::{InternalCircuitIndex, NUM_INTERNAL_CIRCUITS};
@@ -106,6 +106,7 @@ pub struct ChildEvaluationsWitness<F> {
 
 impl<F: PrimeField> ChildEvaluationsWitness<F> {
     /// Creates a child evaluations witness from a proof evaluated at the given points.
+    #[cfg(feature = "prover")]
     pub fn from_proof<C: Cycle<CircuitField = F>, R: Rank>(
         proof: &Proof<C, R>,
         w: F,
@@ -115,20 +116,20 @@ impl<F: PrimeField> ChildEvaluationsWitness<F> {
         registry_wy: &structured::Polynomial<F, R>,
     ) -> Self {
         ChildEvaluationsWitness {
-            preamble: proof.preamble.native_rx.eval(xz),
-            error_m: proof.error_m.native_rx.eval(xz),
-            error_n: proof.error_n.native_rx.eval(xz),
-            query: proof.query.native_rx.eval(xz),
-            eval: proof.eval.native_rx.eval(xz),
-            application: proof.application.rx.eval(xz),
-            hashes_1: proof.circuits.hashes_1_rx.eval(xz),
-            hashes_2: proof.circuits.hashes_2_rx.eval(xz),
-            partial_collapse: proof.circuits.partial_collapse_rx.eval(xz),
-            full_collapse: proof.circuits.full_collapse_rx.eval(xz),
-            compute_v: proof.circuits.compute_v_rx.eval(xz),
-            a_poly_at_xz: proof.ab.a_poly.eval(xz),
-            b_poly_at_x: proof.ab.b_poly.eval(x),
-            child_registry_xy_at_current_w: proof.query.registry_xy_poly.eval(w),
+            preamble: proof.preamble.native_rx.resident().eval(xz),
+            error_m: proof.error_m.native_rx.resident().eval(xz),
+            error_n: proof.error_n.native_rx.resident().eval(xz),
+            query: proof.query.native_rx.resident().eval(xz),
+            eval: proof.eval.native_rx.resident().eval(xz),
+            application: proof.application.rx.resident().eval(xz),
+            hashes_1: proof.circuits.hashes_1_rx.resident().eval(xz),
+            hashes_2: proof.circuits.hashes_2_rx.resident().eval(xz),
+            partial_collapse: proof.circuits.partial_collapse_rx.resident().eval(xz),
+            full_collapse: proof.circuits.full_collapse_rx.resident().eval(xz),
+            compute_v: proof.circuits.compute_v_rx.resident().eval(xz),
+            a_poly_at_xz: proof.ab.a_poly.resident().eval(xz),
+            b_poly_at_x: proof.ab.b_poly.resident().eval(x),
+            child_registry_xy_at_current_w: proof.query.registry_xy_poly.resident().eval(w),
             current_registry_xy_at_child_circuit_id: registry_xy
                 .eval(proof.application.circuit_id.omega_j()),
             current_registry_wy_at_child_x: registry_wy.eval(proof.challenges.x),
@@ -53,6 +53,7 @@ impl InternalCircuitIndex {
     }
 }
 
+#[cfg(feature = "prover")]
 pub mod stages;
 
 /// Registers internal nested circuits into the provided registry.
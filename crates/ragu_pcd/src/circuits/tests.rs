@@ -4,8 +4,11 @@ use native::{
     InternalCircuitIndex,
     stages::{error_m, error_n, eval, preamble, query},
 };
-use ragu_circuits::staging::{Stage, StageExt};
-use ragu_pasta::{Pasta, fp, fq};
+use ragu_circuits::{
+    polynomials::Rank,
+    staging::{Stage, StageExt},
+};
+use ragu_pasta::Pasta;
 
 pub(crate) type R = ragu_circuits::polynomials::R<13>;
 
@@ -14,6 +17,11 @@ pub(crate) type R = ragu_circuits::polynomials::R<13>;
 // Then copy-paste the output into the check_constraints! calls in the test below.
 pub(crate) const HEADER_SIZE: usize = 65;
 
+// The constraint count, registry digest and circuit report snapshots depend on
+// the endoscalar width, so they are kept for both the default 128-bit and the
+// `wide-endoscalars` 136-bit endoscalars. Regenerate both with and without
+// `--features wide-endoscalars` when either changes.
+
 // Number of dummy application circuits to register before testing internal
 // circuits. This ensures the tests work correctly even when application
 // steps are present.
@@ -43,6 +51,13 @@ fn test_internal_circuit_constraint_counts() {
             let idx: usize = InternalCircuitIndex::$variant.circuit_index().into();
             let circuit = &circuits[idx];
             let (actual_mul, actual_lin) = circuit.constraint_counts();
+            assert!(
+                actual_mul <= R::n(),
+                "{}: {} multiplication constraints exceed the budget of {}",
+                stringify!($variant),
+                actual_mul,
+                R::n()
+            );
             assert_eq!(
                 actual_mul,
                 $mul,
@@ -62,11 +77,19 @@ fn test_internal_circuit_constraint_counts() {
         }};
     }
 
-    check_constraints!(Hashes1Circuit,         mul = 2045, lin = 3423);
-    check_constraints!(Hashes2Circuit,         mul = 1879, lin = 2952);
-    check_constraints!(PartialCollapseCircuit, mul = 1756, lin = 1919);
-    check_constraints!(FullCollapseCircuit,    mul = 811 , lin = 809);
-    check_constraints!(ComputeVCircuit,        mul = 1140, lin = 1774);
+    // Every internal circuit must fit in R::n() = 2048 multiplication gates.
+    // Hashes1Circuit is the largest, with 16 gates to spare: anything added to
+    // the preamble stage or to the child k(y) computations it performs must
+    // fit in that budget, or move work into another circuit (as was done for
+    // the circuit ID checks, now in FullCollapseCircuit).
+    check_constraints!(Hashes1Circuit,         mul = 2032, lin = 3391);
+    check_constraints!(Hashes2Circuit,         mul = 1882, lin = 2952);
+    check_constraints!(PartialCollapseCircuit, mul = 1759, lin = 1919);
+    check_constraints!(FullCollapseCircuit,    mul = 840 , lin = 863);
+    #[cfg(not(feature = "wide-endoscalars"))]
+    check_constraints!(ComputeVCircuit,        mul = 1143, lin = 1774);
+    #[cfg(feature = "wide-endoscalars")]
+    check_constraints!(ComputeVCircuit,        mul = 1171, lin = 1830);
 }
 
 #[rustfmt::skip]
@@ -79,11 +102,11 @@ fn test_internal_stage_parameters() {
         }};
     }
 
-    check_stage!(Preamble, skip =   0, num = 225);
-    check_stage!(ErrorN,  skip = 225, num = 186);
-    check_stage!(ErrorM,  skip = 411, num = 399);
-    check_stage!(Query,   skip = 225, num =  23);
-    check_stage!(Eval,    skip = 248, num =  18);
+    check_stage!(Preamble, skip =   0, num = 228);
+    check_stage!(ErrorN,  skip = 228, num = 186);
+    check_stage!(ErrorM,  skip = 414, num = 399);
+    check_stage!(Query,   skip = 228, num =  23);
+    check_stage!(Eval,    skip = 251, num =  18);
 }
 
 /// Helper test to print current constraint counts in copy-pasteable format.
@@ -160,6 +183,8 @@ fn print_internal_stage_parameters() {
 /// then it's mathematically equivalent.
 #[test]
 fn test_native_registry_digest() {
+    use ragu_pasta::fp;
+
     let pasta = Pasta::baked();
 
     let app = ApplicationBuilder::<Pasta, R, HEADER_SIZE>::new()
@@ -168,7 +193,10 @@ fn test_native_registry_digest() {
         .finalize(pasta)
         .unwrap();
 
-    let expected = fp!(0x1d78df0c4390635d85b7c1d659f1d262f027961ff724c225b90dbdc6a2d426cc);
+    #[cfg(not(feature = "wide-endoscalars"))]
+    let expected = fp!(0x2b9112c6ac40c31e8c9a784c41dcab5625573703c5104310d4e106aba091694a);
+    #[cfg(feature = "wide-endoscalars")]
+    let expected = fp!(0x3ca2bf552ed106b1da9db7c05b65bcd18a9adbafc12102dfef30637bd675ff64);
 
     assert_eq!(
         app.native_registry.key().value(),
@@ -184,6 +212,8 @@ fn test_native_registry_digest() {
 /// then it's mathematically equivalent.
 #[test]
 fn test_nested_registry_digest() {
+    use ragu_pasta::fq;
+
     let pasta = Pasta::baked();
 
     let app = ApplicationBuilder::<Pasta, R, HEADER_SIZE>::new()
@@ -192,7 +222,10 @@ fn test_nested_registry_digest() {
         .finalize(pasta)
         .unwrap();
 
+    #[cfg(not(feature = "wide-endoscalars"))]
     let expected = fq!(0x0a8eeda61431380b0121a2a396891b6441a8314d1ceb4c59b595a369933d3403);
+    #[cfg(feature = "wide-endoscalars")]
+    let expected = fq!(0x0dcf1b4eff09438e2e73977218a515791a9b591d4760589ff17ff6d4f3a8f421);
 
     assert_eq!(
         app.nested_registry.key().value(),
@@ -252,3 +285,51 @@ fn print_registry_digests() {
             .collect::<String>()
     );
 }
+
+/// Number of dummy application circuits registered for the circuit report
+/// snapshot, kept small so that the snapshot remains reviewable.
+const NUM_REPORT_APP_STEPS: usize = 2;
+
+/// Test that the constraint counts and `omega_j` assignments of every
+/// registered circuit haven't changed unexpectedly.
+///
+/// When changing any circuit, update the snapshot by running:
+///   cargo test -p ragu_pcd --release print_circuit_report -- --nocapture
+/// Then copy-paste the output into `circuit_report.snap`, or into
+/// `circuit_report_wide.snap` with `--features wide-endoscalars`.
+#[test]
+fn test_circuit_report() {
+    let pasta = Pasta::baked();
+
+    let app = ApplicationBuilder::<Pasta, R, HEADER_SIZE>::new()
+        .register_dummy_circuits(NUM_REPORT_APP_STEPS)
+        .unwrap()
+        .finalize(pasta)
+        .unwrap();
+
+    #[cfg(not(feature = "wide-endoscalars"))]
+    let expected = include_str!("circuit_report.snap");
+    #[cfg(feature = "wide-endoscalars")]
+    let expected = include_str!("circuit_report_wide.snap");
+
+    assert_eq!(
+        app.circuit_report().to_string(),
+        expected,
+        "Circuit report changed unexpectedly!"
+    );
+}
+
+/// Helper test to print the current circuit report in copy-pasteable format.
+/// Run with: `cargo test -p ragu_pcd --release print_circuit_report -- --nocapture`
+#[test]
+fn print_circuit_report() {
+    let pasta = Pasta::baked();
+
+    let app = ApplicationBuilder::<Pasta, R, HEADER_SIZE>::new()
+        .register_dummy_circuits(NUM_REPORT_APP_STEPS)
+        .unwrap()
+        .finalize(pasta)
+        .unwrap();
+
+    print!("{}", app.circuit_report());
+}
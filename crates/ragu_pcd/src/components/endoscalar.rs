@@ -2,7 +2,8 @@
 //!
 //! This module provides the [`EndoscalingStep`] multi-stage circuit, which computes
 //! iterated endoscalar multiplications using Horner's rule. Each step performs
-//! up to 4 endoscalings, storing the result in an interstitial slot.
+//! up to `ENDOSCALINGS_PER_STEP` endoscalings (4 with 128-bit endoscalars),
+//! storing the result in an interstitial slot.
 //!
 //! The structure separates points into:
 //! - `initial`: The base case accumulator for step 0
@@ -10,7 +11,7 @@
 //! - `interstitials`: Output points, one per step
 //!
 //! All steps are uniform: step N initializes from `interstitials[N-1]` (or
-//! `initial` for step 0) and iterates over `inputs[4*N..4*(N+1)]`.
+//! `initial` for step 0) and iterates over its slice of `inputs`.
 //!
 //! This component is reused for both fields in the curve cycle. Because they
 //! will vary in the number of steps and points, the code is generic over the
@@ -18,7 +19,9 @@
 
 use arithmetic::{CurveAffine, Uendo};
 use ff::{Field, WithSmallOrderMulGroup};
-use pasta_curves::group::{Curve, prime::PrimeCurveAffine};
+#[cfg(feature = "prover")]
+use pasta_curves::group::Curve;
+use pasta_curves::group::prime::PrimeCurveAffine;
 use ragu_circuits::{
     polynomials::Rank,
     staging::{MultiStageCircuit, Stage, StageBuilder},
@@ -29,16 +32,37 @@ use ragu_core::{
     gadgets::{Gadget, GadgetKind, Kind},
     maybe::Maybe,
 };
+#[cfg(feature = "prover")]
+use ragu_primitives::compute_endoscalar;
 use ragu_primitives::{
-    Element, Endoscalar, Point, compute_endoscalar,
+    Element, Endoscalar, Point,
     vec::{FixedVec, Len},
 };
 
+#[cfg(feature = "prover")]
 use alloc::vec;
 
+use crate::circuits::nested::NUM_ENDOSCALING_POINTS;
+
+/// Number of multiplication constraints in our target circuit size.
+const TARGET_MULTIPLICATIONS: usize = 1 << 11;
+
+/// Number of multiplication constraints used by each endoscaling: the scaling
+/// itself (see [`Endoscalar::group_scale`]) and an incomplete addition.
+const ENDOSCALING_MULTIPLICATIONS: usize = 7 * (1 + Uendo::BITS as usize / 2) + 4;
+
+/// Number of multiplication constraints used by each step besides its
+/// endoscalings: the endoscalar stage, the points stage (which holds at most
+/// one interstitial per input point) and two for the final checks.
+const RESERVED_MULTIPLICATIONS: usize = Uendo::BITS as usize / 2 + 2 * NUM_ENDOSCALING_POINTS + 2;
+
 /// Number of endoscaling operations per step. This is how many we can fit into
-/// a single circuit in our target circuit size.
-const ENDOSCALINGS_PER_STEP: usize = 4;
+/// a single circuit in our target circuit size, which depends on the width of
+/// [`Uendo`].
+const ENDOSCALINGS_PER_STEP: usize =
+    (TARGET_MULTIPLICATIONS - RESERVED_MULTIPLICATIONS) / ENDOSCALING_MULTIPLICATIONS;
+
+const _: () = assert!(ENDOSCALINGS_PER_STEP > 0);
 
 /// Number of inputs (excluding initial) for `NUM_POINTS`.
 pub(crate) struct InputsLen<const NUM_POINTS: usize>;
@@ -111,6 +135,7 @@ where
     /// # Panics
     ///
     /// Panics if `points.len() != NUM_POINTS`.
+    #[cfg(feature = "prover")]
     pub fn new(endoscalar: Uendo, points: &[C]) -> Self {
         assert_eq!(points.len(), NUM_POINTS, "expected {NUM_POINTS} points");
 
@@ -204,7 +229,7 @@ impl<C: CurveAffine, R: Rank, const NUM_POINTS: usize> Stage<C::Base, R>
 /// Step-based endoscaling component.
 ///
 /// Each step performs up to [`ENDOSCALINGS_PER_STEP`] endoscalings via Horner's rule:
-/// - Step 0 initializes from `initial`, iterates over its slice of `inputs[0..4]`
+/// - Step 0 initializes from `initial`, iterates over `inputs[0..ENDOSCALINGS_PER_STEP]`
 /// - Step N (N > 0) initializes from `interstitials[N-1]`, iterates over
 ///   `inputs[N*ENDOSCALINGS_PER_STEP..(N+1)*ENDOSCALINGS_PER_STEP]` (clamped to bounds)
 ///
@@ -318,11 +343,12 @@ impl<C: CurveAffine, R: Rank, const NUM_POINTS: usize> MultiStageCircuit<C::Base
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod tests {
     use super::{
-        ENDOSCALINGS_PER_STEP, EndoscalarStage, EndoscalingStep, EndoscalingStepWitness, InputsLen,
-        NumStepsLen, PointsStage, PointsWitness,
+        ENDOSCALING_MULTIPLICATIONS, ENDOSCALINGS_PER_STEP, EndoscalarStage, EndoscalingStep,
+        EndoscalingStepWitness, InputsLen, NUM_ENDOSCALING_POINTS, NumStepsLen, PointsStage,
+        PointsWitness, RESERVED_MULTIPLICATIONS, TARGET_MULTIPLICATIONS,
     };
     use arithmetic::Uendo;
     use ff::Field;
@@ -470,19 +496,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_endoscalings_per_step() -> Result<()> {
+        /// Returns the multiplications used by the first step, both in total
+        /// and excluding its stages.
+        fn multiplications<const NUM_POINTS: usize>() -> Result<(usize, usize)> {
+            let step = EndoscalingStep::<EpAffine, R, NUM_POINTS>::new(0);
+            let (total, _) = MultiStage::new(step)
+                .into_object::<R>()?
+                .constraint_counts();
+            let stages = <EndoscalarStage as StageExt<Fp, R>>::num_multiplications()
+                + <PointsStage<EpAffine, NUM_POINTS> as StageExt<Fp, R>>::num_multiplications();
+            Ok((total, total - stages))
+        }
+
+        assert_eq!(
+            <EndoscalarStage as StageExt<Fp, R>>::num_multiplications(),
+            Uendo::BITS as usize / 2
+        );
+        assert!(
+            <PointsStage<EpAffine, NUM_ENDOSCALING_POINTS> as StageExt<Fp, R>>::num_multiplications(
+            ) <= 2 * NUM_ENDOSCALING_POINTS
+        );
+        assert_eq!(multiplications::<2>()?.1, ENDOSCALING_MULTIPLICATIONS + 2);
+
+        // The first registered step performs a full set of endoscalings.
+        let (total, own) = multiplications::<NUM_ENDOSCALING_POINTS>()?;
+        assert_eq!(own, ENDOSCALINGS_PER_STEP * ENDOSCALING_MULTIPLICATIONS + 2);
+        assert!(
+            total <= RESERVED_MULTIPLICATIONS + ENDOSCALINGS_PER_STEP * ENDOSCALING_MULTIPLICATIONS
+        );
+        assert!(total <= TARGET_MULTIPLICATIONS);
+
+        Ok(())
+    }
+
     #[test]
     fn test_endoscaling_variable_length() -> Result<()> {
-        // Test with 11 total points (1 initial + 10 inputs, not divisible by 4)
-        // Step 0: initial + inputs[0..4], output interstitial[0]
-        // Step 1: interstitial[0] + inputs[4..8], output interstitial[1]
-        // Step 2: interstitial[1] + inputs[8..10], output interstitial[2]
+        // Test with 11 total points (1 initial + 10 inputs, not divisible by
+        // the number of endoscalings per step)
         const NUM_POINTS: usize = 11;
         let num_steps = NumStepsLen::<NUM_POINTS>::len();
 
         // Verify computed constants match expectations
-        // With 10 inputs, we need ceil(10/4) = 3 steps
-        assert_eq!(num_steps, 3);
-        assert_eq!(NumStepsLen::<NUM_POINTS>::len(), 3);
+        assert_eq!(num_steps, 10usize.div_ceil(ENDOSCALINGS_PER_STEP));
         assert_eq!(InputsLen::<NUM_POINTS>::len(), 10);
 
         // Generate random endoscalar and base input points.
@@ -534,42 +591,46 @@ mod tests {
 
     #[test]
     fn test_num_steps_len() {
-        // With uniform steps, each step consumes up to 4 inputs.
-        // InputsLen = NUM_POINTS - 1, NumSteps = max(ceil(InputsLen / 4), 1)
+        // Each step consumes up to ENDOSCALINGS_PER_STEP inputs, so
+        // NumSteps = max(ceil((NUM_POINTS - 1) / ENDOSCALINGS_PER_STEP), 1).
         // Assumes NUM_POINTS > 0.
-
-        // 1 total point = 0 inputs = 1 step (base case)
-        assert_eq!(NumStepsLen::<1>::len(), 1);
-
-        // 2-5 total points = 1-4 inputs = 1 step
-        assert_eq!(NumStepsLen::<2>::len(), 1);
-        assert_eq!(NumStepsLen::<3>::len(), 1);
-        assert_eq!(NumStepsLen::<4>::len(), 1);
-        assert_eq!(NumStepsLen::<5>::len(), 1);
-
-        // 6-9 total points = 5-8 inputs = 2 steps
-        assert_eq!(NumStepsLen::<6>::len(), 2);
-        assert_eq!(NumStepsLen::<7>::len(), 2);
-        assert_eq!(NumStepsLen::<8>::len(), 2);
-        assert_eq!(NumStepsLen::<9>::len(), 2);
-
-        // 10-13 total points = 9-12 inputs = 3 steps
-        assert_eq!(NumStepsLen::<10>::len(), 3);
-        assert_eq!(NumStepsLen::<11>::len(), 3);
-        assert_eq!(NumStepsLen::<12>::len(), 3);
-        assert_eq!(NumStepsLen::<13>::len(), 3);
-
-        // 14-17 total points = 13-16 inputs = 4 steps
-        assert_eq!(NumStepsLen::<14>::len(), 4);
-        assert_eq!(NumStepsLen::<15>::len(), 4);
-        assert_eq!(NumStepsLen::<16>::len(), 4);
-        assert_eq!(NumStepsLen::<17>::len(), 4);
-
-        // 18-21 total points = 17-20 inputs = 5 steps
-        assert_eq!(NumStepsLen::<18>::len(), 5);
-        assert_eq!(NumStepsLen::<19>::len(), 5);
-        assert_eq!(NumStepsLen::<20>::len(), 5);
-        assert_eq!(NumStepsLen::<21>::len(), 5);
+        let actual = [
+            NumStepsLen::<1>::len(),
+            NumStepsLen::<2>::len(),
+            NumStepsLen::<3>::len(),
+            NumStepsLen::<4>::len(),
+            NumStepsLen::<5>::len(),
+            NumStepsLen::<6>::len(),
+            NumStepsLen::<7>::len(),
+            NumStepsLen::<8>::len(),
+            NumStepsLen::<9>::len(),
+            NumStepsLen::<10>::len(),
+            NumStepsLen::<11>::len(),
+            NumStepsLen::<12>::len(),
+            NumStepsLen::<13>::len(),
+            NumStepsLen::<14>::len(),
+            NumStepsLen::<15>::len(),
+            NumStepsLen::<16>::len(),
+            NumStepsLen::<17>::len(),
+            NumStepsLen::<18>::len(),
+            NumStepsLen::<19>::len(),
+            NumStepsLen::<20>::len(),
+            NumStepsLen::<21>::len(),
+        ];
+
+        // 1 total point = 0 inputs = 1 step (base case), then one more step
+        // for every 4 inputs with 128-bit endoscalars and every 3 inputs with
+        // 136-bit endoscalars.
+        #[cfg(not(feature = "wide-endoscalars"))]
+        let expected = [
+            1, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5,
+        ];
+        #[cfg(feature = "wide-endoscalars")]
+        let expected = [
+            1, 1, 1, 1, 2, 2, 2, 3, 3, 3, 4, 4, 4, 5, 5, 5, 6, 6, 6, 7, 7,
+        ];
+
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -586,38 +647,62 @@ mod tests {
         // NUM_POINTS = 2: 1 input, 1 step
         assert_eq!(range::<2>(0), 0..1);
 
-        // NUM_POINTS = 5: 4 inputs, 1 step (exactly fills one step)
-        assert_eq!(range::<5>(0), 0..4);
-
-        // NUM_POINTS = 6: 5 inputs, 2 steps
-        // Step 0: inputs[0..4]
-        // Step 1: inputs[4..5]
-        assert_eq!(range::<6>(0), 0..4);
-        assert_eq!(range::<6>(1), 4..5);
-
-        // NUM_POINTS = 9: 8 inputs, 2 steps (exactly fills two steps)
-        assert_eq!(range::<9>(0), 0..4);
-        assert_eq!(range::<9>(1), 4..8);
-
-        // NUM_POINTS = 11: 10 inputs, 3 steps
-        // Step 0: inputs[0..4]
-        // Step 1: inputs[4..8]
-        // Step 2: inputs[8..10]
-        assert_eq!(range::<11>(0), 0..4);
-        assert_eq!(range::<11>(1), 4..8);
-        assert_eq!(range::<11>(2), 8..10);
-
-        // NUM_POINTS = 13: 12 inputs, 3 steps (exactly fills three steps)
-        assert_eq!(range::<13>(0), 0..4);
-        assert_eq!(range::<13>(1), 4..8);
-        assert_eq!(range::<13>(2), 8..12);
-
-        // NUM_POINTS = 14: 13 inputs, 4 steps
-        // Step 3 has only 1 input
-        assert_eq!(range::<14>(0), 0..4);
-        assert_eq!(range::<14>(1), 4..8);
-        assert_eq!(range::<14>(2), 8..12);
-        assert_eq!(range::<14>(3), 12..13);
+        #[cfg(not(feature = "wide-endoscalars"))]
+        {
+            // NUM_POINTS = 5: 4 inputs, 1 step (exactly fills one step)
+            assert_eq!(range::<5>(0), 0..4);
+
+            // NUM_POINTS = 6: 5 inputs, 2 steps
+            // Step 0: inputs[0..4]
+            // Step 1: inputs[4..5]
+            assert_eq!(range::<6>(0), 0..4);
+            assert_eq!(range::<6>(1), 4..5);
+
+            // NUM_POINTS = 9: 8 inputs, 2 steps (exactly fills two steps)
+            assert_eq!(range::<9>(0), 0..4);
+            assert_eq!(range::<9>(1), 4..8);
+
+            // NUM_POINTS = 11: 10 inputs, 3 steps
+            // Step 0: inputs[0..4]
+            // Step 1: inputs[4..8]
+            // Step 2: inputs[8..10]
+            assert_eq!(range::<11>(0), 0..4);
+            assert_eq!(range::<11>(1), 4..8);
+            assert_eq!(range::<11>(2), 8..10);
+
+            // NUM_POINTS = 13: 12 inputs, 3 steps (exactly fills three steps)
+            assert_eq!(range::<13>(0), 0..4);
+            assert_eq!(range::<13>(1), 4..8);
+            assert_eq!(range::<13>(2), 8..12);
+
+            // NUM_POINTS = 14: 13 inputs, 4 steps
+            // Step 3 has only 1 input
+            assert_eq!(range::<14>(0), 0..4);
+            assert_eq!(range::<14>(1), 4..8);
+            assert_eq!(range::<14>(2), 8..12);
+            assert_eq!(range::<14>(3), 12..13);
+        }
+
+        #[cfg(feature = "wide-endoscalars")]
+        {
+            // NUM_POINTS = 4: 3 inputs, 1 step (exactly fills one step)
+            assert_eq!(range::<4>(0), 0..3);
+
+            // NUM_POINTS = 5: 4 inputs, 2 steps
+            assert_eq!(range::<5>(0), 0..3);
+            assert_eq!(range::<5>(1), 3..4);
+
+            // NUM_POINTS = 7: 6 inputs, 2 steps (exactly fills two steps)
+            assert_eq!(range::<7>(0), 0..3);
+            assert_eq!(range::<7>(1), 3..6);
+
+            // NUM_POINTS = 11: 10 inputs, 4 steps
+            // Step 3 has only 1 input
+            assert_eq!(range::<11>(0), 0..3);
+            assert_eq!(range::<11>(1), 3..6);
+            assert_eq!(range::<11>(2), 6..9);
+            assert_eq!(range::<11>(3), 9..10);
+        }
     }
 
     #[test]
@@ -1,17 +1,23 @@
 //! Operations and utilities for reasoning about folded revdot claims.
 
+#[cfg(feature = "prover")]
 use ff::Field;
+#[cfg(feature = "prover")]
 use ragu_circuits::polynomials::{Rank, structured};
 use ragu_core::{Result, drivers::Driver};
+#[cfg(feature = "prover")]
+use ragu_primitives::vec::CollectFixed;
 use ragu_primitives::{
     Element,
     io::Buffer,
-    vec::{CollectFixed, ConstLen, FixedVec, Len},
+    vec::{ConstLen, FixedVec, Len},
 };
 
 use super::horner::Horner;
 
-use core::{borrow::Borrow, iter, marker::PhantomData};
+#[cfg(feature = "prover")]
+use core::borrow::Borrow;
+use core::{iter, marker::PhantomData};
 
 /// The parameters $(m, n)$ that dictate the multi-layer revdot reduction.
 ///
@@ -57,6 +63,7 @@ impl<L: Len> Len for ErrorTermsLen<L> {
 }
 
 /// Returns an iterator over off-diagonal (i, j) pairs where i != j.
+#[cfg(feature = "prover")]
 fn off_diagonal_pairs(n: usize) -> impl Iterator<Item = (usize, usize)> {
     (0..n).flat_map(move |i| (0..n).filter_map(move |j| (i != j).then_some((i, j))))
 }
@@ -70,6 +77,7 @@ fn off_diagonal_pairs(n: usize) -> impl Iterator<Item = (usize, usize)> {
 /// # Panics
 ///
 /// Panics if `source.len()` exceeds `M * N`, which would cause silent truncation.
+#[cfg(feature = "prover")]
 pub fn fold_polys_m<F: Field, R: Rank, P: Parameters>(
     source: &[impl Borrow<structured::Polynomial<F, R>>],
     scale_factor: F,
@@ -104,6 +112,7 @@ pub fn fold_polys_m<F: Field, R: Rank, P: Parameters>(
 /// This takes a length-N vector of polynomials and performs a simple folding
 /// procedure with the scaling factor. This function exists mainly to complement
 /// fold_polys_m as its behavior is trivial.
+#[cfg(feature = "prover")]
 pub fn fold_polys_n<F: Field, R: Rank, P: Parameters>(
     source: FixedVec<structured::Polynomial<F, R>, P::N>,
     scale_factor: F,
@@ -115,6 +124,7 @@ pub fn fold_polys_n<F: Field, R: Rank, P: Parameters>(
 ///
 /// This computes off-diagonal revdot products for each group of `Inner`
 /// polynomials, producing `Outer` groups of error terms.
+#[cfg(feature = "prover")]
 fn compute_errors_impl<F: Field, R: Rank, Outer: Len, Inner: Len>(
     a: &[impl Borrow<structured::Polynomial<F, R>>],
     b: &[impl Borrow<structured::Polynomial<F, R>>],
@@ -153,6 +163,7 @@ fn compute_errors_impl<F: Field, R: Rank, Outer: Len, Inner: Len>(
 }
 
 /// Compute errors_m: N groups of M*(M-1) off-diagonal revdot products.
+#[cfg(feature = "prover")]
 pub fn compute_errors_m<F: Field, R: Rank, P: Parameters>(
     a: &[impl Borrow<structured::Polynomial<F, R>>],
     b: &[impl Borrow<structured::Polynomial<F, R>>],
@@ -161,6 +172,7 @@ pub fn compute_errors_m<F: Field, R: Rank, P: Parameters>(
 }
 
 /// Compute errors_n: N*(N-1) off-diagonal revdot products.
+#[cfg(feature = "prover")]
 pub fn compute_errors_n<F: Field, R: Rank, P: Parameters>(
     a: &[impl Borrow<structured::Polynomial<F, R>>],
     b: &[impl Borrow<structured::Polynomial<F, R>>],
@@ -265,7 +277,7 @@ pub fn fold_two_layer<'dr, D: Driver<'dr>, P: Parameters>(
     Element::fold(dr, results.iter(), layer2_scale)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod tests {
     use super::*;
     use ff::Field;
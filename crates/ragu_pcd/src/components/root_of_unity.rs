@@ -1,19 +1,45 @@
+use arithmetic::CosetDomain;
+use ff::{Field, PrimeField};
 use ragu_core::{Result, drivers::Driver};
 use ragu_primitives::Element;
 
-/// Checks that the provided value `omega` is a valid $2^k$ root of unity.
-pub fn enforce<'dr, D: Driver<'dr>>(dr: &mut D, omega: Element<'dr, D>, k: u32) -> Result<()> {
-    // This works by constraining that `omega`^(2^k) - 1 == 0.
+/// Checks that the provided value `omega` is an element of `domain`.
+///
+/// For a radix-2 domain of size $2^k$ this is the check that `omega` is a $2^k$
+/// root of unity.
+pub fn enforce<'dr, D: Driver<'dr>>(
+    dr: &mut D,
+    omega: Element<'dr, D>,
+    domain: &CosetDomain<D::F>,
+) -> Result<()>
+where
+    D::F: PrimeField,
+{
+    // This works by constraining that \prod_c (omega^(2^k) - t_c) == 0, which
+    // is omega^(2^k) - 1 == 0 for a single coset.
 
     let mut value = omega;
-    for _ in 0..k {
+    for _ in 0..domain.subgroup().log2_n() {
         value = value.square(dr)?;
     }
 
-    let one = Element::one();
-    let diff = value.sub(dr, &one);
+    let mut product: Option<Element<'dr, D>> = None;
+    for &t in domain.coset_powers() {
+        let t = if t == D::F::ONE {
+            Element::one()
+        } else {
+            Element::constant(dr, t)
+        };
+        let diff = value.sub(dr, &t);
+        product = Some(match product {
+            Some(product) => product.mul(dr, &diff)?,
+            None => diff,
+        });
+    }
 
-    diff.enforce_zero(dr)?;
+    product
+        .expect("domains have at least one coset")
+        .enforce_zero(dr)?;
 
     Ok(())
 }
@@ -21,11 +47,11 @@ pub fn enforce<'dr, D: Driver<'dr>>(dr: &mut D, omega: Element<'dr, D>, k: u32)
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ff::Field;
+    use ff::{Field, PrimeField};
     use ragu_pasta::{Fp, fp};
     use ragu_primitives::Simulator;
 
-    // (omega, k, should_pass)
+    // (omega, k, should_pass), checked against the domain of size 2^k
     fn test_cases() -> Vec<(Fp, u32, bool)> {
         // 2^32 primitive roots of unity
         let root_of_unity1 =
@@ -46,7 +72,13 @@ mod tests {
             (Fp::ONE, 30, true),
             (Fp::ONE, 31, true),
             (Fp::ONE, 32, true),
-            (Fp::ONE, 1000, true),
+            // Beyond 2^S elements, the domain extends to cosets of the roots of
+            // unity
+            (Fp::ONE, 33, true),
+            (Fp::MULTIPLICATIVE_GENERATOR, 33, true),
+            (Fp::MULTIPLICATIVE_GENERATOR, 32, false),
+            (root_of_unity1 * Fp::MULTIPLICATIVE_GENERATOR, 33, true),
+            (Fp::MULTIPLICATIVE_GENERATOR.square(), 33, false),
             // -1 is a 2^k root of unity where k >= 1
             (-Fp::ONE, 0, false),
             (-Fp::ONE, 1, true),
@@ -81,7 +113,7 @@ mod tests {
         for (i, (omega, k, should_pass)) in test_cases().into_iter().enumerate() {
             let result = Simulator::simulate(omega, |dr, witness| {
                 let omega = Element::alloc(dr, witness)?;
-                enforce(dr, omega, k)?;
+                enforce(dr, omega, &CosetDomain::new(1 << k))?;
                 Ok(())
             });
 
@@ -94,4 +126,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_enforce_cosets() -> Result<()> {
+        use ragu_secp::Fq;
+
+        // The secp256k1 scalar field has 2-adicity 6, so 100 elements span
+        // two cosets of the subgroup of order 64.
+        let domain = CosetDomain::<Fq>::new(100);
+        assert_eq!(domain.num_cosets(), 2);
+
+        let cases = (0..domain.n())
+            .map(|i| (CosetDomain::<Fq>::element(i), true))
+            .chain([
+                (Fq::ZERO, false),
+                (Fq::MULTIPLICATIVE_GENERATOR.square(), false),
+                (Fq::from(0xdeadbeef), false),
+            ]);
+        for (omega, should_pass) in cases {
+            let result = Simulator::simulate(omega, |dr, witness| {
+                let omega = Element::alloc(dr, witness)?;
+                enforce(dr, omega, &domain)?;
+                Ok(())
+            });
+            assert_eq!(result.is_ok(), should_pass, "omega={omega:?}");
+        }
+
+        Ok(())
+    }
 }
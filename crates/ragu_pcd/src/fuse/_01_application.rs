@@ -1,14 +1,14 @@
 //! Evaluate the [`Step`] circuit.
 //!
-//! This creates a witness for the step circuit given the two input [`Pcd`]s and
-//! the step witness. This produces the [`proof::Application`] component of the
-//! proof. The inputs are all consumed, and the `left` and `right proofs are
-//! returned to the caller along with the auxiliary data from the application
-//! synthesis.
+//! This creates a witness for the step circuit given the two input [`Pcd`]s,
+//! the step witness and the public instances of the step and of both inputs.
+//! This produces the [`proof::Application`] component of the proof. The inputs
+//! are all consumed, and the `left` and `right` proofs are returned to the
+//! caller along with the auxiliary data from the application synthesis.
 
 use arithmetic::Cycle;
 use ff::Field;
-use ragu_circuits::{CircuitExt, polynomials::Rank};
+use ragu_circuits::polynomials::Rank;
 use ragu_core::Result;
 use rand::Rng;
 
@@ -23,6 +23,7 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         rng: &mut RNG,
         step: S,
         witness: S::Witness<'source>,
+        instance: C::CircuitField,
         left: Pcd<'source, C, R, S::Left>,
         right: Pcd<'source, C, R, S::Right>,
     ) -> Result<(
@@ -31,8 +32,18 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         proof::Application<C, R>,
         S::Aux<'source>,
     )> {
-        let (rx, aux) = Adapter::<C, S, R, HEADER_SIZE>::new(step)
-            .rx::<R>((left.data, right.data, witness), self.native_registry.key())?;
+        let left_instance = left.proof.application.instance;
+        let right_instance = right.proof.application.instance;
+        let (rx, aux) = super::rx::<_, _, R>(
+            &Adapter::<C, S, R, HEADER_SIZE>::new(step),
+            (
+                left.data,
+                right.data,
+                witness,
+                (instance, left_instance, right_instance),
+            ),
+            self.native_registry.key(),
+        )?;
         let blind = C::CircuitField::random(&mut *rng);
         let commitment = rx.commit(C::host_generators(self.params), blind);
 
@@ -42,10 +53,13 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
             left.proof,
             right.proof,
             proof::Application {
-                circuit_id: S::INDEX.circuit_index(self.num_application_steps)?,
+                circuit_id: S::INDEX.circuit_index(self.num_application_steps, &self.step_sets)?,
                 left_header: left_header.into_inner(),
                 right_header: right_header.into_inner(),
-                rx,
+                instance,
+                left_instance,
+                right_instance,
+                rx: rx.into(),
                 blind,
                 commitment,
             },
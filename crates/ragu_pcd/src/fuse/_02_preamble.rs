@@ -50,10 +50,9 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         Ok((
             proof::Preamble {
-                native_rx,
+                native_rx: native_rx.into(),
                 native_blind,
                 native_commitment,
-                nested_rx,
                 nested_blind,
                 nested_commitment,
             },
@@ -5,7 +5,10 @@
 
 use arithmetic::Cycle;
 use ff::Field;
-use ragu_circuits::{polynomials::Rank, staging::StageExt};
+use ragu_circuits::{
+    polynomials::{Rank, batch_normalize},
+    staging::StageExt,
+};
 use ragu_core::{
     Result,
     drivers::Driver,
@@ -16,6 +19,8 @@ use rand::Rng;
 
 use crate::{Application, Proof, circuits::nested, proof};
 
+use super::RegistryWxPolys;
+
 impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
     pub(super) fn compute_s_prime<'dr, D, RNG: Rng>(
         &self,
@@ -23,7 +28,7 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         w: &Element<'dr, D>,
         left: &Proof<C, R>,
         right: &Proof<C, R>,
-    ) -> Result<proof::SPrime<C, R>>
+    ) -> Result<(proof::SPrime<C>, RegistryWxPolys<C, R>)>
     where
         D: Driver<'dr, F = C::CircuitField, MaybeKind = Always<()>>,
     {
@@ -33,12 +38,17 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         let native_registry_wx0_poly = self.native_registry.wx(w, x0);
         let native_registry_wx0_blind = C::CircuitField::random(&mut *rng);
-        let native_registry_wx0_commitment = native_registry_wx0_poly
-            .commit(C::host_generators(self.params), native_registry_wx0_blind);
         let native_registry_wx1_poly = self.native_registry.wx(w, x1);
         let native_registry_wx1_blind = C::CircuitField::random(&mut *rng);
-        let native_registry_wx1_commitment = native_registry_wx1_poly
-            .commit(C::host_generators(self.params), native_registry_wx1_blind);
+        let [
+            native_registry_wx0_commitment,
+            native_registry_wx1_commitment,
+        ] = batch_normalize([
+            native_registry_wx0_poly
+                .commit_projective(C::host_generators(self.params), native_registry_wx0_blind),
+            native_registry_wx1_poly
+                .commit_projective(C::host_generators(self.params), native_registry_wx1_blind),
+        ]);
 
         let nested_s_prime_witness = nested::stages::s_prime::Witness {
             registry_wx0: native_registry_wx0_commitment,
@@ -50,16 +60,19 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         let nested_s_prime_commitment =
             nested_s_prime_rx.commit(C::nested_generators(self.params), nested_s_prime_blind);
 
-        Ok(proof::SPrime {
-            registry_wx0_poly: native_registry_wx0_poly,
-            registry_wx0_blind: native_registry_wx0_blind,
-            registry_wx0_commitment: native_registry_wx0_commitment,
-            registry_wx1_poly: native_registry_wx1_poly,
-            registry_wx1_blind: native_registry_wx1_blind,
-            registry_wx1_commitment: native_registry_wx1_commitment,
-            nested_s_prime_rx,
-            nested_s_prime_blind,
-            nested_s_prime_commitment,
-        })
+        Ok((
+            proof::SPrime {
+                registry_wx0_blind: native_registry_wx0_blind,
+                registry_wx0_commitment: native_registry_wx0_commitment,
+                registry_wx1_blind: native_registry_wx1_blind,
+                registry_wx1_commitment: native_registry_wx1_commitment,
+                nested_s_prime_blind,
+                nested_s_prime_commitment,
+            },
+            RegistryWxPolys {
+                wx0: native_registry_wx0_poly,
+                wx1: native_registry_wx1_poly,
+            },
+        ))
     }
 }
@@ -9,7 +9,10 @@
 
 use arithmetic::Cycle;
 use ff::Field;
-use ragu_circuits::{polynomials::Rank, staging::StageExt};
+use ragu_circuits::{
+    polynomials::{Rank, batch_normalize, structured},
+    staging::StageExt,
+};
 use ragu_core::{
     Result,
     drivers::Driver,
@@ -41,6 +44,7 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         right: &'rx Proof<C, R>,
     ) -> Result<(
         proof::ErrorM<C, R>,
+        structured::Polynomial<C::CircuitField, R>,
         native::stages::error_m::Witness<C, NativeParameters>,
         claims::Builder<'_, 'rx, C::CircuitField, R>,
     )>
@@ -53,8 +57,6 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         let registry_wy_poly = self.native_registry.wy(w, y);
         let registry_wy_blind = C::CircuitField::random(&mut *rng);
-        let registry_wy_commitment =
-            registry_wy_poly.commit(C::host_generators(self.params), registry_wy_blind);
 
         let source = FuseProofSource { left, right };
         let mut builder = claims::Builder::new(&self.native_registry, y, z);
@@ -69,7 +71,10 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
             &error_m_witness,
         )?;
         let native_blind = C::CircuitField::random(&mut *rng);
-        let native_commitment = native_rx.commit(C::host_generators(self.params), native_blind);
+        let [registry_wy_commitment, native_commitment] = batch_normalize([
+            registry_wy_poly.commit_projective(C::host_generators(self.params), registry_wy_blind),
+            native_rx.commit_projective(C::host_generators(self.params), native_blind),
+        ]);
 
         let nested_error_m_witness = nested::stages::error_m::Witness {
             native_error_m: native_commitment,
@@ -82,16 +87,15 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         Ok((
             proof::ErrorM {
-                registry_wy_poly,
                 registry_wy_blind,
                 registry_wy_commitment,
-                native_rx,
+                native_rx: native_rx.into(),
                 native_blind,
                 native_commitment,
-                nested_rx,
                 nested_blind,
                 nested_commitment,
             },
+            registry_wy_poly,
             error_m_witness,
             builder,
         ))
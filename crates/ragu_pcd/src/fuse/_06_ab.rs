@@ -30,7 +30,7 @@
 use arithmetic::Cycle;
 use ff::Field;
 use ragu_circuits::{
-    polynomials::{Rank, structured},
+    polynomials::{Rank, batch_normalize, structured},
     staging::StageExt,
 };
 use ragu_core::{
@@ -69,11 +69,14 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         let a_poly = fold_revdot::fold_polys_n::<_, R, NativeParameters>(a, mu_prime_inv);
         let a_blind = C::CircuitField::random(&mut *rng);
-        let a_commitment = a_poly.commit(C::host_generators(self.params), a_blind);
 
         let b_poly = fold_revdot::fold_polys_n::<_, R, NativeParameters>(b, mu_prime_nu_prime);
         let b_blind = C::CircuitField::random(&mut *rng);
-        let b_commitment = b_poly.commit(C::host_generators(self.params), b_blind);
+
+        let [a_commitment, b_commitment] = batch_normalize([
+            a_poly.commit_projective(C::host_generators(self.params), a_blind),
+            b_poly.commit_projective(C::host_generators(self.params), b_blind),
+        ]);
 
         let c = a_poly.revdot(&b_poly);
 
@@ -86,14 +89,13 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         let nested_commitment = nested_rx.commit(C::nested_generators(self.params), nested_blind);
 
         Ok(proof::AB {
-            a_poly,
+            a_poly: a_poly.into(),
             a_blind,
             a_commitment,
-            b_poly,
+            b_poly: b_poly.into(),
             b_blind,
             b_commitment,
             c,
-            nested_rx,
             nested_blind,
             nested_commitment,
         })
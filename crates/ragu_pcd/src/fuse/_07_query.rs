@@ -10,7 +10,11 @@
 
 use arithmetic::Cycle;
 use ff::Field;
-use ragu_circuits::{polynomials::Rank, staging::StageExt};
+use ragu_circuits::{
+    polynomials::{Rank, batch_normalize, structured, unstructured},
+    registry::CircuitIndex,
+    staging::StageExt,
+};
 use ragu_core::{
     Result,
     drivers::Driver,
@@ -19,12 +23,14 @@ use ragu_core::{
 use ragu_primitives::Element;
 use rand::Rng;
 
+use alloc::vec::Vec;
+
 use crate::{
     Application, Proof,
     circuits::{self, native, native::stages::query, nested},
     proof,
 };
-use native::InternalCircuitIndex;
+use native::{InternalCircuitIndex, NUM_INTERNAL_CIRCUITS};
 
 impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
     pub(super) fn compute_query<'dr, D, RNG: Rng>(
@@ -34,7 +40,7 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         x: &Element<'dr, D>,
         y: &Element<'dr, D>,
         z: &Element<'dr, D>,
-        error_m: &proof::ErrorM<C, R>,
+        registry_wy_poly: &structured::Polynomial<C::CircuitField, R>,
         left: &Proof<C, R>,
         right: &Proof<C, R>,
     ) -> Result<(
@@ -53,18 +59,19 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         let registry_xy_poly = self.native_registry.xy(x, y);
         let registry_xy_blind = C::CircuitField::random(&mut *rng);
-        let registry_xy_commitment =
-            registry_xy_poly.commit(C::host_generators(self.params), registry_xy_blind);
 
-        let registry_at = |idx: InternalCircuitIndex| -> C::CircuitField {
-            let circuit_id = idx.circuit_index();
-            registry_xy_poly.eval(circuit_id.omega_j())
-        };
+        // Evaluate registry_xy at every internal circuit's omega^j, in the
+        // order of `InternalCircuitIndex`, and then at w in a single pass.
+        // TODO: the registry itself could assist with these evaluations.
+        let points: Vec<_> = (0..NUM_INTERNAL_CIRCUITS)
+            .map(|i| CircuitIndex::new(i).omega_j())
+            .chain([w])
+            .collect();
+        let evals = unstructured::Polynomial::eval_batch([&registry_xy_poly], &points);
+        let registry_at = |idx: InternalCircuitIndex| evals[idx as usize];
 
         let query_witness = query::Witness {
             fixed_registry: query::FixedRegistryWitness {
-                // TODO: these can all be evaluated at the same time; in fact,
-                // that's what registry.xy is supposed to allow.
                 preamble_stage: registry_at(PreambleStage),
                 error_m_stage: registry_at(ErrorMStage),
                 error_n_stage: registry_at(ErrorNStage),
@@ -79,14 +86,14 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
                 full_collapse_circuit: registry_at(FullCollapseCircuit),
                 compute_v_circuit: registry_at(ComputeVCircuit),
             },
-            registry_wxy: registry_xy_poly.eval(w),
+            registry_wxy: evals[NUM_INTERNAL_CIRCUITS],
             left: query::ChildEvaluationsWitness::from_proof(
                 left,
                 w,
                 x,
                 xz,
                 &registry_xy_poly,
-                &error_m.registry_wy_poly,
+                registry_wy_poly,
             ),
             right: query::ChildEvaluationsWitness::from_proof(
                 right,
@@ -94,13 +101,16 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
                 x,
                 xz,
                 &registry_xy_poly,
-                &error_m.registry_wy_poly,
+                registry_wy_poly,
             ),
         };
 
         let native_rx = query::Stage::<C, R, HEADER_SIZE>::rx(&query_witness)?;
         let native_blind = C::CircuitField::random(&mut *rng);
-        let native_commitment = native_rx.commit(C::host_generators(self.params), native_blind);
+        let [registry_xy_commitment, native_commitment] = batch_normalize([
+            registry_xy_poly.commit_projective(C::host_generators(self.params), registry_xy_blind),
+            native_rx.commit_projective(C::host_generators(self.params), native_blind),
+        ]);
 
         let nested_query_witness = nested::stages::query::Witness {
             native_query: native_commitment,
@@ -112,13 +122,12 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         Ok((
             proof::Query {
-                registry_xy_poly,
+                registry_xy_poly: registry_xy_poly.into(),
                 registry_xy_blind,
                 registry_xy_commitment,
-                native_rx,
+                native_rx: native_rx.into(),
                 native_blind,
                 native_commitment,
-                nested_rx,
                 nested_blind,
                 nested_commitment,
             },
@@ -12,7 +12,7 @@
 use arithmetic::Cycle;
 use ff::Field;
 use ragu_circuits::{
-    polynomials::{Rank, unstructured},
+    polynomials::{Rank, structured, unstructured},
     staging::StageExt,
 };
 use ragu_core::{
@@ -29,6 +29,8 @@ use crate::{
     Application, Proof, circuits::native::InternalCircuitIndex, circuits::nested::stages::f, proof,
 };
 
+use super::RegistryWxPolys;
+
 impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
     pub(super) fn compute_f<'dr, D, RNG: Rng>(
         &self,
@@ -38,13 +40,13 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         z: &Element<'dr, D>,
         x: &Element<'dr, D>,
         alpha: &Element<'dr, D>,
-        s_prime: &proof::SPrime<C, R>,
-        error_m: &proof::ErrorM<C, R>,
+        registry_wx: &RegistryWxPolys<C, R>,
+        registry_wy_poly: &structured::Polynomial<C::CircuitField, R>,
         ab: &proof::AB<C, R>,
         query: &proof::Query<C, R>,
         left: &Proof<C, R>,
         right: &Proof<C, R>,
-    ) -> Result<proof::F<C, R>>
+    ) -> Result<(proof::F<C>, unstructured::Polynomial<C::CircuitField, R>)>
     where
         D: Driver<'dr, F = C::CircuitField, MaybeKind = Always<()>>,
     {
@@ -64,95 +66,116 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         // This must exactly match the ordering of the `poly_queries` function
         // in the `compute_v` circuit.
         let mut iters = [
-            factor_iter(left.p.poly.iter_coeffs(), left.challenges.u),
-            factor_iter(right.p.poly.iter_coeffs(), right.challenges.u),
-            factor_iter(left.query.registry_xy_poly.iter_coeffs(), w),
-            factor_iter(right.query.registry_xy_poly.iter_coeffs(), w),
-            factor_iter(s_prime.registry_wx0_poly.iter_coeffs(), left.challenges.y),
-            factor_iter(s_prime.registry_wx1_poly.iter_coeffs(), right.challenges.y),
-            factor_iter(s_prime.registry_wx0_poly.iter_coeffs(), y),
-            factor_iter(s_prime.registry_wx1_poly.iter_coeffs(), y),
-            factor_iter(error_m.registry_wy_poly.iter_coeffs(), left.challenges.x),
-            factor_iter(error_m.registry_wy_poly.iter_coeffs(), right.challenges.x),
-            factor_iter(error_m.registry_wy_poly.iter_coeffs(), x),
-            factor_iter(query.registry_xy_poly.iter_coeffs(), w),
-            factor_iter(query.registry_xy_poly.iter_coeffs(), omega_j(PreambleStage)),
-            factor_iter(query.registry_xy_poly.iter_coeffs(), omega_j(ErrorNStage)),
-            factor_iter(query.registry_xy_poly.iter_coeffs(), omega_j(ErrorMStage)),
-            factor_iter(query.registry_xy_poly.iter_coeffs(), omega_j(QueryStage)),
-            factor_iter(query.registry_xy_poly.iter_coeffs(), omega_j(EvalStage)),
-            factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+            factor_iter(left.p.poly.resident().iter_coeffs(), left.challenges.u),
+            factor_iter(right.p.poly.resident().iter_coeffs(), right.challenges.u),
+            factor_iter(left.query.registry_xy_poly.resident().iter_coeffs(), w),
+            factor_iter(right.query.registry_xy_poly.resident().iter_coeffs(), w),
+            factor_iter(registry_wx.wx0.iter_coeffs(), left.challenges.y),
+            factor_iter(registry_wx.wx1.iter_coeffs(), right.challenges.y),
+            factor_iter(registry_wx.wx0.iter_coeffs(), y),
+            factor_iter(registry_wx.wx1.iter_coeffs(), y),
+            factor_iter(registry_wy_poly.iter_coeffs(), left.challenges.x),
+            factor_iter(registry_wy_poly.iter_coeffs(), right.challenges.x),
+            factor_iter(registry_wy_poly.iter_coeffs(), x),
+            factor_iter(query.registry_xy_poly.resident().iter_coeffs(), w),
+            factor_iter(
+                query.registry_xy_poly.resident().iter_coeffs(),
+                omega_j(PreambleStage),
+            ),
+            factor_iter(
+                query.registry_xy_poly.resident().iter_coeffs(),
+                omega_j(ErrorNStage),
+            ),
+            factor_iter(
+                query.registry_xy_poly.resident().iter_coeffs(),
+                omega_j(ErrorMStage),
+            ),
+            factor_iter(
+                query.registry_xy_poly.resident().iter_coeffs(),
+                omega_j(QueryStage),
+            ),
+            factor_iter(
+                query.registry_xy_poly.resident().iter_coeffs(),
+                omega_j(EvalStage),
+            ),
+            factor_iter(
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(ErrorMFinalStaged),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(ErrorNFinalStaged),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(EvalFinalStaged),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(Hashes1Circuit),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(Hashes2Circuit),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(PartialCollapseCircuit),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(FullCollapseCircuit),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 omega_j(ComputeVCircuit),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 left.application.circuit_id.omega_j(),
             ),
             factor_iter(
-                query.registry_xy_poly.iter_coeffs(),
+                query.registry_xy_poly.resident().iter_coeffs(),
                 right.application.circuit_id.omega_j(),
             ),
             // A/B polynomial queries:
             // a_poly at xz, b_poly at x for left child, right child, current
-            factor_iter(left.ab.a_poly.iter_coeffs(), xz),
-            factor_iter(left.ab.b_poly.iter_coeffs(), x),
-            factor_iter(right.ab.a_poly.iter_coeffs(), xz),
-            factor_iter(right.ab.b_poly.iter_coeffs(), x),
-            factor_iter(ab.a_poly.iter_coeffs(), xz),
-            factor_iter(ab.b_poly.iter_coeffs(), x),
+            factor_iter(left.ab.a_poly.resident().iter_coeffs(), xz),
+            factor_iter(left.ab.b_poly.resident().iter_coeffs(), x),
+            factor_iter(right.ab.a_poly.resident().iter_coeffs(), xz),
+            factor_iter(right.ab.b_poly.resident().iter_coeffs(), x),
+            factor_iter(ab.a_poly.resident().iter_coeffs(), xz),
+            factor_iter(ab.b_poly.resident().iter_coeffs(), x),
             // Per-rx evaluations at xz only. The same r_i(xz) values feed
             // into both A(xz) (undilated) and B(x) (Z-dilated).
-            factor_iter(left.preamble.native_rx.iter_coeffs(), xz),
-            factor_iter(left.error_n.native_rx.iter_coeffs(), xz),
-            factor_iter(left.error_m.native_rx.iter_coeffs(), xz),
-            factor_iter(left.query.native_rx.iter_coeffs(), xz),
-            factor_iter(left.eval.native_rx.iter_coeffs(), xz),
-            factor_iter(left.application.rx.iter_coeffs(), xz),
-            factor_iter(left.circuits.hashes_1_rx.iter_coeffs(), xz),
-            factor_iter(left.circuits.hashes_2_rx.iter_coeffs(), xz),
-            factor_iter(left.circuits.partial_collapse_rx.iter_coeffs(), xz),
-            factor_iter(left.circuits.full_collapse_rx.iter_coeffs(), xz),
-            factor_iter(left.circuits.compute_v_rx.iter_coeffs(), xz),
-            factor_iter(right.preamble.native_rx.iter_coeffs(), xz),
-            factor_iter(right.error_n.native_rx.iter_coeffs(), xz),
-            factor_iter(right.error_m.native_rx.iter_coeffs(), xz),
-            factor_iter(right.query.native_rx.iter_coeffs(), xz),
-            factor_iter(right.eval.native_rx.iter_coeffs(), xz),
-            factor_iter(right.application.rx.iter_coeffs(), xz),
-            factor_iter(right.circuits.hashes_1_rx.iter_coeffs(), xz),
-            factor_iter(right.circuits.hashes_2_rx.iter_coeffs(), xz),
-            factor_iter(right.circuits.partial_collapse_rx.iter_coeffs(), xz),
-            factor_iter(right.circuits.full_collapse_rx.iter_coeffs(), xz),
-            factor_iter(right.circuits.compute_v_rx.iter_coeffs(), xz),
+            factor_iter(left.preamble.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(left.error_n.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(left.error_m.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(left.query.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(left.eval.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(left.application.rx.resident().iter_coeffs(), xz),
+            factor_iter(left.circuits.hashes_1_rx.resident().iter_coeffs(), xz),
+            factor_iter(left.circuits.hashes_2_rx.resident().iter_coeffs(), xz),
+            factor_iter(
+                left.circuits.partial_collapse_rx.resident().iter_coeffs(),
+                xz,
+            ),
+            factor_iter(left.circuits.full_collapse_rx.resident().iter_coeffs(), xz),
+            factor_iter(left.circuits.compute_v_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.preamble.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.error_n.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.error_m.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.query.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.eval.native_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.application.rx.resident().iter_coeffs(), xz),
+            factor_iter(right.circuits.hashes_1_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.circuits.hashes_2_rx.resident().iter_coeffs(), xz),
+            factor_iter(
+                right.circuits.partial_collapse_rx.resident().iter_coeffs(),
+                xz,
+            ),
+            factor_iter(right.circuits.full_collapse_rx.resident().iter_coeffs(), xz),
+            factor_iter(right.circuits.compute_v_rx.resident().iter_coeffs(), xz),
         ];
 
         let mut coeffs = Vec::new();
@@ -175,13 +198,14 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         let nested_blind = C::ScalarField::random(&mut *rng);
         let nested_commitment = nested_rx.commit(C::nested_generators(self.params), nested_blind);
 
-        Ok(proof::F {
+        Ok((
+            proof::F {
+                blind,
+                commitment,
+                nested_blind,
+                nested_commitment,
+            },
             poly,
-            blind,
-            commitment,
-            nested_rx,
-            nested_blind,
-            nested_commitment,
-        })
+        ))
     }
 }
@@ -6,7 +6,10 @@
 
 use arithmetic::Cycle;
 use ff::Field;
-use ragu_circuits::{polynomials::Rank, staging::StageExt};
+use ragu_circuits::{
+    polynomials::{Rank, structured},
+    staging::StageExt,
+};
 use ragu_core::{
     Result,
     drivers::Driver,
@@ -21,6 +24,8 @@ use crate::{
     proof,
 };
 
+use super::RegistryWxPolys;
+
 impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
     pub(super) fn compute_eval<'dr, D, RNG: Rng>(
         &self,
@@ -28,8 +33,8 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         u: &Element<'dr, D>,
         left: &Proof<C, R>,
         right: &Proof<C, R>,
-        s_prime: &proof::SPrime<C, R>,
-        error_m: &proof::ErrorM<C, R>,
+        registry_wx: &RegistryWxPolys<C, R>,
+        registry_wy_poly: &structured::Polynomial<C::CircuitField, R>,
         ab: &proof::AB<C, R>,
         query: &proof::Query<C, R>,
     ) -> Result<(proof::Eval<C, R>, eval::Witness<C::CircuitField>)>
@@ -46,12 +51,12 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
                 // efficient if they're computed simultaneously with assistance
                 // from the registry itself, rather than individually evaluated for
                 // each of these restrictions.
-                registry_wx0: s_prime.registry_wx0_poly.eval(u),
-                registry_wx1: s_prime.registry_wx1_poly.eval(u),
-                registry_wy: error_m.registry_wy_poly.eval(u),
-                a_poly: ab.a_poly.eval(u),
-                b_poly: ab.b_poly.eval(u),
-                registry_xy: query.registry_xy_poly.eval(u),
+                registry_wx0: registry_wx.wx0.eval(u),
+                registry_wx1: registry_wx.wx1.eval(u),
+                registry_wy: registry_wy_poly.eval(u),
+                a_poly: ab.a_poly.resident().eval(u),
+                b_poly: ab.b_poly.resident().eval(u),
+                registry_xy: query.registry_xy_poly.resident().eval(u),
             },
         };
         let native_rx = eval::Stage::<C, R, HEADER_SIZE>::rx(&eval_witness)?;
@@ -67,10 +72,9 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         Ok((
             proof::Eval {
-                native_rx,
+                native_rx: native_rx.into(),
                 native_blind,
                 native_commitment,
-                nested_rx,
                 nested_blind,
                 nested_commitment,
             },
@@ -14,7 +14,7 @@ use arithmetic::Cycle;
 use core::ops::AddAssign;
 use ragu_circuits::{
     CircuitExt,
-    polynomials::{Rank, unstructured},
+    polynomials::{Rank, structured, unstructured},
     staging::{MultiStage, StageExt},
 };
 use ragu_core::{
@@ -31,6 +31,8 @@ use crate::components::endoscalar::{
 };
 use crate::{Application, Proof, proof};
 
+use super::RegistryWxPolys;
+
 /// Accumulates polynomials with their blinds and commitments.
 struct Accumulator<'a, C: Cycle, R: Rank> {
     poly: &'a mut unstructured::Polynomial<C::CircuitField, R>,
@@ -58,16 +60,19 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         u: &Element<'dr, D>,
         left: &Proof<C, R>,
         right: &Proof<C, R>,
-        s_prime: &proof::SPrime<C, R>,
+        s_prime: &proof::SPrime<C>,
         error_m: &proof::ErrorM<C, R>,
         ab: &proof::AB<C, R>,
         query: &proof::Query<C, R>,
-        f: &proof::F<C, R>,
+        f: &proof::F<C>,
+        registry_wx: &RegistryWxPolys<C, R>,
+        registry_wy_poly: &structured::Polynomial<C::CircuitField, R>,
+        f_poly: unstructured::Polynomial<C::CircuitField, R>,
     ) -> Result<proof::P<C, R>>
     where
         D: Driver<'dr, F = C::CircuitField, MaybeKind = Always<()>>,
     {
-        let mut poly = f.poly.clone();
+        let mut poly = f_poly;
         let mut blind = f.blind;
 
         // Collect commitments for PointsWitness construction.
@@ -94,89 +99,97 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
             for proof in [left, right] {
                 acc.acc(
-                    &proof.application.rx,
+                    proof.application.rx.resident(),
                     proof.application.blind,
                     proof.application.commitment,
                 );
                 acc.acc(
-                    &proof.preamble.native_rx,
+                    proof.preamble.native_rx.resident(),
                     proof.preamble.native_blind,
                     proof.preamble.native_commitment,
                 );
                 acc.acc(
-                    &proof.error_n.native_rx,
+                    proof.error_n.native_rx.resident(),
                     proof.error_n.native_blind,
                     proof.error_n.native_commitment,
                 );
                 acc.acc(
-                    &proof.error_m.native_rx,
+                    proof.error_m.native_rx.resident(),
                     proof.error_m.native_blind,
                     proof.error_m.native_commitment,
                 );
-                acc.acc(&proof.ab.a_poly, proof.ab.a_blind, proof.ab.a_commitment);
-                acc.acc(&proof.ab.b_poly, proof.ab.b_blind, proof.ab.b_commitment);
                 acc.acc(
-                    &proof.query.native_rx,
+                    proof.ab.a_poly.resident(),
+                    proof.ab.a_blind,
+                    proof.ab.a_commitment,
+                );
+                acc.acc(
+                    proof.ab.b_poly.resident(),
+                    proof.ab.b_blind,
+                    proof.ab.b_commitment,
+                );
+                acc.acc(
+                    proof.query.native_rx.resident(),
                     proof.query.native_blind,
                     proof.query.native_commitment,
                 );
                 acc.acc(
-                    &proof.query.registry_xy_poly,
+                    proof.query.registry_xy_poly.resident(),
                     proof.query.registry_xy_blind,
                     proof.query.registry_xy_commitment,
                 );
                 acc.acc(
-                    &proof.eval.native_rx,
+                    proof.eval.native_rx.resident(),
                     proof.eval.native_blind,
                     proof.eval.native_commitment,
                 );
-                acc.acc(&proof.p.poly, proof.p.blind, proof.p.commitment);
+                acc.acc(proof.p.poly.resident(), proof.p.blind, proof.p.commitment);
                 acc.acc(
-                    &proof.circuits.hashes_1_rx,
+                    proof.circuits.hashes_1_rx.resident(),
                     proof.circuits.hashes_1_blind,
                     proof.circuits.hashes_1_commitment,
                 );
                 acc.acc(
-                    &proof.circuits.hashes_2_rx,
+                    proof.circuits.hashes_2_rx.resident(),
                     proof.circuits.hashes_2_blind,
                     proof.circuits.hashes_2_commitment,
                 );
                 acc.acc(
-                    &proof.circuits.partial_collapse_rx,
+                    proof.circuits.partial_collapse_rx.resident(),
                     proof.circuits.partial_collapse_blind,
                     proof.circuits.partial_collapse_commitment,
                 );
                 acc.acc(
-                    &proof.circuits.full_collapse_rx,
+                    proof.circuits.full_collapse_rx.resident(),
                     proof.circuits.full_collapse_blind,
                     proof.circuits.full_collapse_commitment,
                 );
                 acc.acc(
-                    &proof.circuits.compute_v_rx,
+                    proof.circuits.compute_v_rx.resident(),
                     proof.circuits.compute_v_blind,
                     proof.circuits.compute_v_commitment,
                 );
             }
 
             acc.acc(
-                &s_prime.registry_wx0_poly,
+                &registry_wx.wx0,
                 s_prime.registry_wx0_blind,
                 s_prime.registry_wx0_commitment,
             );
             acc.acc(
-                &s_prime.registry_wx1_poly,
+                &registry_wx.wx1,
                 s_prime.registry_wx1_blind,
                 s_prime.registry_wx1_commitment,
             );
             acc.acc(
-                &error_m.registry_wy_poly,
+                registry_wy_poly,
                 error_m.registry_wy_blind,
                 error_m.registry_wy_commitment,
             );
-            acc.acc(&ab.a_poly, ab.a_blind, ab.a_commitment);
-            acc.acc(&ab.b_poly, ab.b_blind, ab.b_commitment);
+            acc.acc(ab.a_poly.resident(), ab.a_blind, ab.a_commitment);
+            acc.acc(ab.b_poly.resident(), ab.b_blind, ab.b_commitment);
             acc.acc(
-                &query.registry_xy_poly,
+                query.registry_xy_poly.resident(),
                 query.registry_xy_blind,
                 query.registry_xy_commitment,
             );
@@ -230,13 +243,13 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         let v = poly.eval(*u.value().take());
 
         Ok(proof::P {
-            poly,
+            poly: poly.into(),
             blind,
             commitment,
             v,
-            endoscalar_rx,
-            points_rx,
-            step_rxs,
+            endoscalar_rx: endoscalar_rx.into(),
+            points_rx: points_rx.into(),
+            step_rxs: step_rxs.into_iter().map(Into::into).collect(),
         })
     }
 }
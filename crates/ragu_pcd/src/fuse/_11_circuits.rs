@@ -1,12 +1,15 @@
 use arithmetic::Cycle;
 use ff::Field;
-use ragu_circuits::{CircuitExt, polynomials::Rank};
+use ragu_circuits::{
+    CircuitExt,
+    polynomials::{Rank, batch_normalize},
+};
 use ragu_core::Result;
 use rand::Rng;
 
 use crate::{
     Application,
-    circuits::{self, native, native::total_circuit_counts},
+    circuits::{self, native, native::total_circuit_count},
     components::fold_revdot::NativeParameters,
     proof,
 };
@@ -16,12 +19,12 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         &self,
         rng: &mut RNG,
         preamble: &proof::Preamble<C, R>,
-        s_prime: &proof::SPrime<C, R>,
+        s_prime: &proof::SPrime<C>,
         error_n: &proof::ErrorN<C, R>,
         error_m: &proof::ErrorM<C, R>,
         ab: &proof::AB<C, R>,
         query: &proof::Query<C, R>,
-        f: &proof::F<C, R>,
+        f: &proof::F<C>,
         eval: &proof::Eval<C, R>,
         p: &proof::P<C, R>,
         preamble_witness: &native::stages::preamble::Witness<'_, C, R, HEADER_SIZE>,
@@ -56,21 +59,16 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         };
 
         let (hashes_1_rx, _) =
-            native::hashes_1::Circuit::<C, R, HEADER_SIZE, NativeParameters>::new(
-                self.params,
-                total_circuit_counts(self.num_application_steps).1,
-            )
-            .rx::<R>(
-                native::hashes_1::Witness {
-                    unified_instance,
-                    preamble_witness,
-                    error_n_witness,
-                },
-                self.native_registry.key(),
-            )?;
+            native::hashes_1::Circuit::<C, R, HEADER_SIZE, NativeParameters>::new(self.params)
+                .rx::<R>(
+                    native::hashes_1::Witness {
+                        unified_instance,
+                        preamble_witness,
+                        error_n_witness,
+                    },
+                    self.native_registry.key(),
+                )?;
         let hashes_1_rx_blind = C::CircuitField::random(&mut *rng);
-        let hashes_1_rx_commitment =
-            hashes_1_rx.commit(C::host_generators(self.params), hashes_1_rx_blind);
 
         let (hashes_2_rx, _) =
             native::hashes_2::Circuit::<C, R, HEADER_SIZE, NativeParameters>::new(self.params)
@@ -82,8 +80,6 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
                     self.native_registry.key(),
                 )?;
         let hashes_2_rx_blind = C::CircuitField::random(&mut *rng);
-        let hashes_2_rx_commitment =
-            hashes_2_rx.commit(C::host_generators(self.params), hashes_2_rx_blind);
 
         let (partial_collapse_rx, _) =
             native::partial_collapse::Circuit::<C, R, HEADER_SIZE, NativeParameters>::new()
@@ -97,11 +93,12 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
                     self.native_registry.key(),
                 )?;
         let partial_collapse_rx_blind = C::CircuitField::random(&mut *rng);
-        let partial_collapse_rx_commitment =
-            partial_collapse_rx.commit(C::host_generators(self.params), partial_collapse_rx_blind);
 
         let (full_collapse_rx, _) =
-            native::full_collapse::Circuit::<C, R, HEADER_SIZE, NativeParameters>::new().rx::<R>(
+            native::full_collapse::Circuit::<C, R, HEADER_SIZE, NativeParameters>::new(
+                total_circuit_count(self.num_application_steps),
+            )
+            .rx::<R>(
                 native::full_collapse::Witness {
                     unified_instance,
                     preamble_witness,
@@ -110,10 +107,10 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
                 self.native_registry.key(),
             )?;
         let full_collapse_rx_blind = C::CircuitField::random(&mut *rng);
-        let full_collapse_rx_commitment =
-            full_collapse_rx.commit(C::host_generators(self.params), full_collapse_rx_blind);
 
-        let (compute_v_rx, _) = native::compute_v::Circuit::<C, R, HEADER_SIZE>::new().rx::<R>(
+        // The only internal circuit with predictable routines.
+        let (compute_v_rx, _) = super::rx::<_, _, R>(
+            &native::compute_v::Circuit::<C, R, HEADER_SIZE>::new(),
             native::compute_v::Witness {
                 unified_instance,
                 preamble_witness,
@@ -123,23 +120,37 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
             self.native_registry.key(),
         )?;
         let compute_v_rx_blind = C::CircuitField::random(&mut *rng);
-        let compute_v_rx_commitment =
-            compute_v_rx.commit(C::host_generators(self.params), compute_v_rx_blind);
+
+        let [
+            hashes_1_rx_commitment,
+            hashes_2_rx_commitment,
+            partial_collapse_rx_commitment,
+            full_collapse_rx_commitment,
+            compute_v_rx_commitment,
+        ] = batch_normalize([
+            hashes_1_rx.commit_projective(C::host_generators(self.params), hashes_1_rx_blind),
+            hashes_2_rx.commit_projective(C::host_generators(self.params), hashes_2_rx_blind),
+            partial_collapse_rx
+                .commit_projective(C::host_generators(self.params), partial_collapse_rx_blind),
+            full_collapse_rx
+                .commit_projective(C::host_generators(self.params), full_collapse_rx_blind),
+            compute_v_rx.commit_projective(C::host_generators(self.params), compute_v_rx_blind),
+        ]);
 
         Ok(proof::InternalCircuits {
-            hashes_1_rx,
+            hashes_1_rx: hashes_1_rx.into(),
             hashes_1_blind: hashes_1_rx_blind,
             hashes_1_commitment: hashes_1_rx_commitment,
-            hashes_2_rx,
+            hashes_2_rx: hashes_2_rx.into(),
             hashes_2_blind: hashes_2_rx_blind,
             hashes_2_commitment: hashes_2_rx_commitment,
-            partial_collapse_rx,
+            partial_collapse_rx: partial_collapse_rx.into(),
             partial_collapse_blind: partial_collapse_rx_blind,
             partial_collapse_commitment: partial_collapse_rx_commitment,
-            full_collapse_rx,
+            full_collapse_rx: full_collapse_rx.into(),
             full_collapse_blind: full_collapse_rx_blind,
             full_collapse_commitment: full_collapse_rx_commitment,
-            compute_v_rx,
+            compute_v_rx: compute_v_rx.into(),
             compute_v_blind: compute_v_rx_blind,
             compute_v_commitment: compute_v_rx_commitment,
         })
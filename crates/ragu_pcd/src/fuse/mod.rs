@@ -16,16 +16,18 @@ mod _10_p;
 mod _11_circuits;
 
 use arithmetic::Cycle;
+use ff::Field;
 use ragu_circuits::{
-    polynomials::{Rank, structured},
-    registry::CircuitIndex,
+    Circuit, CircuitExt,
+    polynomials::{Rank, structured, unstructured},
+    registry::{CircuitIndex, Key},
 };
 use ragu_core::{Result, drivers::emulator::Emulator, maybe::Maybe};
 use ragu_primitives::{GadgetExt, Point, poseidon::Sponge, vec::CollectFixed};
 use rand::Rng;
 
 use crate::{
-    Application, Pcd, Proof,
+    Application, Pcd, PolynomialStore, Proof,
     components::claims::{Source, native::RxComponent},
     proof,
     step::Step,
@@ -48,6 +50,9 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
     ///   [`Step::Left`] header.
     /// * `right`: the right [`Pcd`] to fuse in this step; must correspond to
     ///   the [`Step::Right`] header.
+    ///
+    /// The step's public instance is zero; see
+    /// [`Application::fuse_with_instance`].
     pub fn fuse<'source, RNG: Rng, S: Step<C>>(
         &self,
         rng: &mut RNG,
@@ -56,8 +61,35 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         left: Pcd<'source, C, R, S::Left>,
         right: Pcd<'source, C, R, S::Right>,
     ) -> Result<(Proof<C, R>, S::Aux<'source>)> {
+        self.fuse_with_instance(rng, step, witness, C::CircuitField::ZERO, left, right)
+    }
+
+    /// Fuse two [`Pcd`] into one using a provided [`Step`] and a public
+    /// `instance` element, which is passed to [`Step::witness_with_instance`].
+    /// See [`Application::fuse`] for the other parameters.
+    ///
+    /// The resulting proof is only valid for the same instance, which must be
+    /// provided to [`Application::verify_with_instance`] and
+    /// [`Application::rerandomize_with_instance`]. A step that later fuses
+    /// this proof receives its instance in [`Instances`](crate::step::Instances),
+    /// where it should be constrained if the step depends on it.
+    ///
+    /// Returns an error if the polynomials of either child proof are in a
+    /// [`PolynomialStore`]; see [`Application::fuse_with_store`].
+    pub fn fuse_with_instance<'source, RNG: Rng, S: Step<C>>(
+        &self,
+        rng: &mut RNG,
+        step: S,
+        witness: S::Witness<'source>,
+        instance: C::CircuitField,
+        left: Pcd<'source, C, R, S::Left>,
+        right: Pcd<'source, C, R, S::Right>,
+    ) -> Result<(Proof<C, R>, S::Aux<'source>)> {
+        left.proof.ensure_resident()?;
+        right.proof.ensure_resident()?;
+
         let (left, right, application, application_aux) =
-            self.compute_application_proof(rng, step, witness, left, right)?;
+            self.compute_application_proof(rng, step, witness, instance, left, right)?;
 
         let mut dr = Emulator::execute();
         let mut transcript = Sponge::new(&mut dr, C::circuit_poseidon(self.params));
@@ -67,13 +99,13 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         Point::constant(&mut dr, preamble.nested_commitment)?.write(&mut dr, &mut transcript)?;
         let w = transcript.squeeze(&mut dr)?;
 
-        let s_prime = self.compute_s_prime(rng, &w, &left, &right)?;
+        let (s_prime, registry_wx) = self.compute_s_prime(rng, &w, &left, &right)?;
         Point::constant(&mut dr, s_prime.nested_s_prime_commitment)?
             .write(&mut dr, &mut transcript)?;
         let y = transcript.squeeze(&mut dr)?;
         let z = transcript.squeeze(&mut dr)?;
 
-        let (error_m, error_m_witness, claims) =
+        let (error_m, registry_wy_poly, error_m_witness, claims) =
             self.compute_errors_m(rng, &w, &y, &z, &left, &right)?;
         Point::constant(&mut dr, error_m.nested_commitment)?.write(&mut dr, &mut transcript)?;
 
@@ -108,23 +140,53 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         let x = transcript.squeeze(&mut dr)?;
 
         let (query, query_witness) =
-            self.compute_query(rng, &w, &x, &y, &z, &error_m, &left, &right)?;
+            self.compute_query(rng, &w, &x, &y, &z, &registry_wy_poly, &left, &right)?;
         Point::constant(&mut dr, query.nested_commitment)?.write(&mut dr, &mut transcript)?;
         let alpha = transcript.squeeze(&mut dr)?;
 
-        let f = self.compute_f(
-            rng, &w, &y, &z, &x, &alpha, &s_prime, &error_m, &ab, &query, &left, &right,
+        let (f, f_poly) = self.compute_f(
+            rng,
+            &w,
+            &y,
+            &z,
+            &x,
+            &alpha,
+            &registry_wx,
+            &registry_wy_poly,
+            &ab,
+            &query,
+            &left,
+            &right,
         )?;
         Point::constant(&mut dr, f.nested_commitment)?.write(&mut dr, &mut transcript)?;
         let u = transcript.squeeze(&mut dr)?;
 
-        let (eval, eval_witness) =
-            self.compute_eval(rng, &u, &left, &right, &s_prime, &error_m, &ab, &query)?;
+        let (eval, eval_witness) = self.compute_eval(
+            rng,
+            &u,
+            &left,
+            &right,
+            &registry_wx,
+            &registry_wy_poly,
+            &ab,
+            &query,
+        )?;
         Point::constant(&mut dr, eval.nested_commitment)?.write(&mut dr, &mut transcript)?;
         let pre_beta = transcript.squeeze(&mut dr)?;
 
         let p = self.compute_p(
-            &pre_beta, &u, &left, &right, &s_prime, &error_m, &ab, &query, &f,
+            &pre_beta,
+            &u,
+            &left,
+            &right,
+            &s_prime,
+            &error_m,
+            &ab,
+            &query,
+            &f,
+            &registry_wx,
+            &registry_wy_poly,
+            f_poly,
         )?;
 
         let challenges = proof::Challenges::new(
@@ -168,6 +230,46 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
             application_aux,
         ))
     }
+
+    /// Fuse two [`Pcd`] into one as in [`Application::fuse_with_instance`],
+    /// keeping the polynomials of the proofs involved in `store`.
+    ///
+    /// The polynomials of `left` and `right` are read back from `store` if
+    /// they were spilled to it, and those of the resulting proof are written
+    /// to it before it is returned, so that only the proofs being fused hold
+    /// their polynomials in memory. The resulting proof must be loaded with
+    /// [`Proof::load`] before it is verified or rerandomized.
+    pub fn fuse_with_store<'source, RNG: Rng, S: Step<C>, PS>(
+        &self,
+        store: &mut PS,
+        rng: &mut RNG,
+        step: S,
+        witness: S::Witness<'source>,
+        instance: C::CircuitField,
+        mut left: Pcd<'source, C, R, S::Left>,
+        mut right: Pcd<'source, C, R, S::Right>,
+    ) -> Result<(Proof<C, R>, S::Aux<'source>)>
+    where
+        PS: PolynomialStore<C::CircuitField, R> + PolynomialStore<C::ScalarField, R> + ?Sized,
+    {
+        left.proof.load(store)?;
+        right.proof.load(store)?;
+        let (mut proof, aux) =
+            self.fuse_with_instance(rng, step, witness, instance, left, right)?;
+        proof.spill(store)?;
+        Ok((proof, aux))
+    }
+}
+
+/// The $m(w, x_i, Y)$ polynomials for the child proofs' $x$ challenges,
+/// committed to in [`proof::SPrime`].
+///
+/// These (like $m(w, X, y)$ and $f(X)$) are only needed to compute later
+/// components of the same step, so they are dropped once [`proof::P`] has
+/// accumulated them rather than retained in the [`Proof`].
+pub(crate) struct RegistryWxPolys<C: Cycle, R: Rank> {
+    pub(crate) wx0: unstructured::Polynomial<C::CircuitField, R>,
+    pub(crate) wx1: unstructured::Polynomial<C::CircuitField, R>,
 }
 
 pub(crate) struct FuseProofSource<'rx, C: Cycle, R: Rank> {
@@ -183,37 +285,58 @@ impl<'rx, C: Cycle, R: Rank> Source for FuseProofSource<'rx, C, R> {
     fn rx(&self, component: RxComponent) -> impl Iterator<Item = Self::Rx> {
         use RxComponent::*;
         let (left_poly, right_poly) = match component {
-            AbA => (&self.left.ab.a_poly, &self.right.ab.a_poly),
-            AbB => (&self.left.ab.b_poly, &self.right.ab.b_poly),
-            Application => (&self.left.application.rx, &self.right.application.rx),
+            AbA => (
+                self.left.ab.a_poly.resident(),
+                self.right.ab.a_poly.resident(),
+            ),
+            AbB => (
+                self.left.ab.b_poly.resident(),
+                self.right.ab.b_poly.resident(),
+            ),
+            Application => (
+                self.left.application.rx.resident(),
+                self.right.application.rx.resident(),
+            ),
             Hashes1 => (
-                &self.left.circuits.hashes_1_rx,
-                &self.right.circuits.hashes_1_rx,
+                self.left.circuits.hashes_1_rx.resident(),
+                self.right.circuits.hashes_1_rx.resident(),
             ),
             Hashes2 => (
-                &self.left.circuits.hashes_2_rx,
-                &self.right.circuits.hashes_2_rx,
+                self.left.circuits.hashes_2_rx.resident(),
+                self.right.circuits.hashes_2_rx.resident(),
             ),
             PartialCollapse => (
-                &self.left.circuits.partial_collapse_rx,
-                &self.right.circuits.partial_collapse_rx,
+                self.left.circuits.partial_collapse_rx.resident(),
+                self.right.circuits.partial_collapse_rx.resident(),
             ),
             FullCollapse => (
-                &self.left.circuits.full_collapse_rx,
-                &self.right.circuits.full_collapse_rx,
+                self.left.circuits.full_collapse_rx.resident(),
+                self.right.circuits.full_collapse_rx.resident(),
             ),
             ComputeV => (
-                &self.left.circuits.compute_v_rx,
-                &self.right.circuits.compute_v_rx,
+                self.left.circuits.compute_v_rx.resident(),
+                self.right.circuits.compute_v_rx.resident(),
             ),
             Preamble => (
-                &self.left.preamble.native_rx,
-                &self.right.preamble.native_rx,
+                self.left.preamble.native_rx.resident(),
+                self.right.preamble.native_rx.resident(),
+            ),
+            ErrorM => (
+                self.left.error_m.native_rx.resident(),
+                self.right.error_m.native_rx.resident(),
+            ),
+            ErrorN => (
+                self.left.error_n.native_rx.resident(),
+                self.right.error_n.native_rx.resident(),
+            ),
+            Query => (
+                self.left.query.native_rx.resident(),
+                self.right.query.native_rx.resident(),
+            ),
+            Eval => (
+                self.left.eval.native_rx.resident(),
+                self.right.eval.native_rx.resident(),
             ),
-            ErrorM => (&self.left.error_m.native_rx, &self.right.error_m.native_rx),
-            ErrorN => (&self.left.error_n.native_rx, &self.right.error_n.native_rx),
-            Query => (&self.left.query.native_rx, &self.right.query.native_rx),
-            Eval => (&self.left.eval.native_rx, &self.right.eval.native_rx),
         };
         [left_poly, right_poly].into_iter()
     }
@@ -226,3 +349,21 @@ impl<'rx, C: Cycle, R: Rank> Source for FuseProofSource<'rx, C, R> {
         .into_iter()
     }
 }
+
+/// Computes the witness polynomial $r(X)$ of `circuit`, synthesizing
+/// predictable [routines](ragu_core::routines) on other threads when the `std`
+/// feature is enabled.
+fn rx<'witness, F: Field, Ci: Circuit<F>, R: Rank>(
+    circuit: &Ci,
+    witness: Ci::Witness<'witness>,
+    key: &Key<F>,
+) -> Result<(structured::Polynomial<F, R>, Ci::Aux<'witness>)> {
+    #[cfg(feature = "std")]
+    {
+        circuit.rx_parallel(witness, key)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        circuit.rx(witness, key)
+    }
+}
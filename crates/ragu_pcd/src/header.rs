@@ -4,13 +4,18 @@
 use ff::Field;
 use ragu_core::{
     Result,
-    drivers::{Driver, DriverValue},
+    drivers::{Driver, DriverValue, emulator::Emulator},
     gadgets::GadgetKind,
+    maybe::{Empty, Maybe},
 };
-use ragu_primitives::io::Write;
+use ragu_primitives::{Element, GadgetExt, io::Write};
 
+use alloc::vec::Vec;
 use core::any::Any;
 
+mod committed;
+pub use committed::{Committed, Opening};
+
 /// The number of suffixes used internally by Ragu.
 ///
 /// * `0` is reserved for all circuits that have a fixed ID, used internally for
@@ -93,6 +98,15 @@ pub trait Header<F: Field>: Send + Sync + Any {
         dr: &mut D,
         witness: DriverValue<D, Self::Data<'source>>,
     ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>>;
+
+    /// Recovers the data from its encoding, as computed by [`encode`], so that
+    /// an [`AnyPcd`](crate::AnyPcd) received without its data can be
+    /// downcast. Headers whose encoding does not determine their data (such as
+    /// a hash) keep the default, which returns `None`.
+    fn decode(encoding: &[F]) -> Option<Self::Data<'static>> {
+        let _ = encoding;
+        None
+    }
 }
 
 /// Trivial header that encodes no data.
@@ -108,4 +122,37 @@ impl<F: Field> Header<F> for () {
     ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
         Ok(())
     }
+
+    fn decode(encoding: &[F]) -> Option<()> {
+        encoding.is_empty().then_some(())
+    }
+}
+
+/// Computes the encoding of `data` for the [`Header`] `H` as field elements.
+///
+/// This excludes the padding and [`Suffix`] that are appended to every header
+/// of an [`Application`](crate::Application), so along with `H::SUFFIX` it
+/// is the encoding expected by
+/// [`Application::verify_encoded`](crate::Application::verify_encoded).
+pub fn encode<F: Field, H: Header<F>>(data: H::Data<'_>) -> Result<Vec<F>> {
+    Emulator::emulate_wireless(data, |dr, data| {
+        let gadget = H::encode(dr, data)?;
+
+        let mut elements: Vec<Element<'_, _>> = Vec::new();
+        gadget.write(dr, &mut elements)?;
+
+        Ok(elements.iter().map(|e| *e.value().take()).collect())
+    })
+}
+
+/// Computes the number of elements in the encoding of the [`Header`] `H`,
+/// which does not depend on the data being encoded.
+pub(crate) fn encoded_len<F: Field, H: Header<F>>() -> Result<usize> {
+    let mut dr = Emulator::counter();
+    let gadget = H::encode(&mut dr, Empty)?;
+
+    let mut elements: Vec<Element<'_, _>> = Vec::new();
+    gadget.write(&mut dr, &mut elements)?;
+
+    Ok(elements.len())
 }
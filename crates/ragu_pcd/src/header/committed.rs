@@ -0,0 +1,131 @@
+//! Headers that are committed to with Poseidon.
+//!
+//! Every header is padded to `HEADER_SIZE` field elements, so a header whose
+//! [`Output`](Header::Output) gadget is larger than that cannot be used
+//! directly, and raising `HEADER_SIZE` for one large header increases the size
+//! of every header in the [`Application`](crate::Application). [`Committed`]
+//! instead encodes a header `H` as a single Poseidon digest of its
+//! [`Output`](Header::Output) gadget, and steps that need the underlying data
+//! re-open the digest using [`Committed::open`].
+//!
+//! The data carried by a committed header is an [`Opening`], which is created
+//! outside of circuits using [`Committed::commit`]. Steps that produce a
+//! committed header compute its digest in-circuit using
+//! [`Committed::from_gadget`], while steps that only pass a committed header
+//! along can encode it with [`Encoded::new`], which does not.
+
+use ff::{Field, PrimeField};
+use ragu_core::{
+    Result,
+    drivers::{Driver, DriverValue, emulator::Emulator},
+    gadgets::{GadgetKind, Kind},
+    maybe::Maybe,
+};
+use ragu_primitives::{Element, GadgetExt, poseidon::Sponge};
+
+use core::marker::PhantomData;
+
+use super::{Header, Suffix};
+use crate::step::Encoded;
+
+/// Encodes the [`Header`] `H` as a Poseidon digest of its
+/// [`Output`](Header::Output) gadget.
+///
+/// This uses the same [`Suffix`] as `H`, so `H` and `Committed<H>` cannot be
+/// used within the same [`Application`](crate::Application).
+pub struct Committed<H>(PhantomData<H>);
+
+/// The data for a [`Committed`] header: the data for the underlying header
+/// along with its digest.
+pub struct Opening<'source, F: Field, H: Header<F>> {
+    /// The data for the underlying header.
+    pub data: H::Data<'source>,
+
+    /// The Poseidon digest of the underlying header's encoding.
+    pub digest: F,
+}
+
+impl<F: Field, H: Header<F>> Clone for Opening<'_, F, H> {
+    fn clone(&self) -> Self {
+        Opening {
+            data: self.data.clone(),
+            digest: self.digest,
+        }
+    }
+}
+
+impl<F: Field, H: Header<F>> Header<F> for Committed<H> {
+    const SUFFIX: Suffix = H::SUFFIX;
+
+    type Data<'source> = Opening<'source, F, H>;
+    type Output = Kind![F; Element<'_, _>];
+
+    fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+        dr: &mut D,
+        witness: DriverValue<D, Self::Data<'source>>,
+    ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+        Element::alloc(dr, witness.map(|opening| opening.digest))
+    }
+}
+
+impl<H> Committed<H> {
+    /// Computes the [`Opening`] of `data` for use as the data of a
+    /// [`Committed`] header.
+    pub fn commit<'source, F: PrimeField, P: arithmetic::PoseidonPermutation<F>>(
+        poseidon: &P,
+        data: H::Data<'source>,
+    ) -> Result<Opening<'source, F, H>>
+    where
+        H: Header<F>,
+    {
+        let digest = Emulator::emulate_wireless(data.clone(), |dr, data| {
+            let gadget = H::encode(dr, data)?;
+            let digest = Self::from_gadget::<_, _, 0>(dr, poseidon, &gadget)?;
+
+            Ok(*digest.as_gadget().value().take())
+        })?;
+
+        Ok(Opening { data, digest })
+    }
+
+    /// Encodes a [`Committed`] header within a step by computing the digest of
+    /// the [`Output`](Header::Output) gadget of the underlying header.
+    pub fn from_gadget<'dr, D, P, const HEADER_SIZE: usize>(
+        dr: &mut D,
+        poseidon: &'dr P,
+        gadget: &<H::Output as GadgetKind<D::F>>::Rebind<'dr, D>,
+    ) -> Result<Encoded<'dr, D, Self, HEADER_SIZE>>
+    where
+        D: Driver<'dr, F: PrimeField>,
+        H: Header<D::F>,
+        P: arithmetic::PoseidonPermutation<D::F>,
+    {
+        let mut sponge = Sponge::new(dr, poseidon);
+        gadget.write(dr, &mut sponge)?;
+
+        Ok(Encoded::from_gadget(sponge.squeeze(dr)?))
+    }
+
+    /// Encodes a [`Committed`] header within a step while also returning the
+    /// [`Output`](Header::Output) gadget of the underlying header, so that the
+    /// step can use the full data. The digest is computed in-circuit, which
+    /// binds the returned gadget to the encoded header.
+    pub fn open<'dr, 'source: 'dr, D, P, const HEADER_SIZE: usize>(
+        dr: &mut D,
+        poseidon: &'dr P,
+        witness: DriverValue<D, Opening<'source, D::F, H>>,
+    ) -> Result<(
+        Encoded<'dr, D, Self, HEADER_SIZE>,
+        <H::Output as GadgetKind<D::F>>::Rebind<'dr, D>,
+    )>
+    where
+        D: Driver<'dr, F: PrimeField>,
+        H: Header<D::F>,
+        P: arithmetic::PoseidonPermutation<D::F>,
+    {
+        let gadget = H::encode(dr, witness.map(|opening| opening.data))?;
+        let encoded = Self::from_gadget(dr, poseidon, &gadget)?;
+
+        Ok((encoded, gadget))
+    }
+}
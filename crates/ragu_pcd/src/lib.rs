@@ -8,12 +8,18 @@
 #![doc(html_logo_url = "https://tachyon.z.cash/assets/ragu/v1/rustdoc-128x128.png")]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod circuits;
 mod components;
+#[cfg(feature = "prover")]
 mod fuse;
 pub mod header;
 mod proof;
+pub mod report;
+#[cfg(feature = "prover")]
+pub mod schedule;
 pub mod step;
 mod verify;
 
@@ -21,27 +27,31 @@ mod verify;
 #[doc(hidden)]
 pub mod test_fixtures;
 
-use arithmetic::Cycle;
+use arithmetic::{Cycle, FixedGenerators};
+#[cfg(feature = "prover")]
+use ff::Field;
 use ragu_circuits::{
     polynomials::Rank,
     registry::{Registry, RegistryBuilder},
 };
 use ragu_core::{Error, Result};
+#[cfg(feature = "prover")]
 use rand::Rng;
 
 use alloc::collections::BTreeMap;
-use core::{any::TypeId, cell::OnceCell, marker::PhantomData};
+use core::{any::TypeId, marker::PhantomData};
 
 use header::Header;
-pub use proof::{Pcd, Proof};
-use step::{Step, internal::adapter::Adapter};
+pub use proof::{AnyPcd, Pcd, PolynomialStore, Proof, StoreKey};
+use step::{Step, StepSet, internal::adapter::Adapter};
 
 /// Builder for an [`Application`] for proof-carrying data.
 pub struct ApplicationBuilder<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize> {
     native_registry: RegistryBuilder<'params, C::CircuitField, R>,
     nested_registry: RegistryBuilder<'params, C::ScalarField, R>,
     num_application_steps: usize,
-    header_map: BTreeMap<header::Suffix, TypeId>,
+    step_sets: step::StepSets,
+    header_map: BTreeMap<header::Suffix, RegisteredHeader>,
     _marker: PhantomData<[(); HEADER_SIZE]>,
 }
 
@@ -62,6 +72,7 @@ impl<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
             native_registry: RegistryBuilder::new(),
             nested_registry: RegistryBuilder::new(),
             num_application_steps: 0,
+            step_sets: BTreeMap::new(),
             header_map: BTreeMap::new(),
             _marker: PhantomData,
         }
@@ -69,9 +80,11 @@ impl<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
 
     /// Register a new application-defined [`Step`] in this context. The
     /// provided [`Step`]'s [`INDEX`](Step::INDEX) should be the next sequential
-    /// index that has not been inserted yet.
+    /// index that has not been inserted yet, or the next
+    /// [relative](step::Index::relative) index of the [`StepSet`] currently
+    /// being registered.
     pub fn register<S: Step<C> + 'params>(mut self, step: S) -> Result<Self> {
-        S::INDEX.assert_index(self.num_application_steps)?;
+        S::INDEX.assert_index(self.num_application_steps, &mut self.step_sets)?;
 
         self.prevent_duplicate_suffixes::<S::Output>()?;
         self.prevent_duplicate_suffixes::<S::Left>()?;
@@ -85,6 +98,12 @@ impl<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
         Ok(self)
     }
 
+    /// Register every [`Step`] of a [`StepSet`] in this context, placing the
+    /// set after the steps that have already been registered.
+    pub fn register_set<S: StepSet<C> + 'params>(self, set: S) -> Result<Self> {
+        set.register(self)
+    }
+
     /// Register `count` trivial circuits to simulate application steps
     /// registration.
     ///
@@ -102,23 +121,33 @@ impl<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
 
     /// Perform finalization and optimization steps to produce the
     /// [`Application`].
+    ///
+    /// Returns [`Error::Initialization`] if `params` do not contain enough
+    /// generators to commit to polynomials of the chosen [`Rank`].
     pub fn finalize(
         mut self,
         params: &'params C::Params,
     ) -> Result<Application<'params, C, R, HEADER_SIZE>> {
+        if C::host_generators(params).g().len() < R::num_coeffs()
+            || C::nested_generators(params).g().len() < R::num_coeffs()
+        {
+            return Err(Error::Initialization(
+                "parameters contain too few generators for the chosen rank".into(),
+            ));
+        }
+
         // Build the native registry:
         // 1. Internal masks
         // 2. Internal circuits
         // 3. Internal steps
         // 4. Application circuits (already registered)
-        let (total_circuits, log2_circuits) =
-            circuits::native::total_circuit_counts(self.num_application_steps);
+        let total_circuits = circuits::native::total_circuit_count(self.num_application_steps);
 
         // First, register internal masks and circuits
         self.native_registry = circuits::native::register_all::<C, R, HEADER_SIZE>(
             self.native_registry,
             params,
-            log2_circuits,
+            total_circuits,
         )?;
 
         // Then, register internal steps
@@ -133,11 +162,6 @@ impl<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
                 Adapter::<C, _, R, HEADER_SIZE>::new(step::internal::trivial::Trivial::new()),
             )?;
 
-        assert_eq!(
-            self.native_registry.log2_circuits(),
-            log2_circuits,
-            "log2_circuits mismatch"
-        );
         assert_eq!(
             self.native_registry.num_circuits(),
             total_circuits,
@@ -151,28 +175,57 @@ impl<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize>
             native_registry: self.native_registry.finalize(C::circuit_poseidon(params))?,
             nested_registry: self.nested_registry.finalize(C::scalar_poseidon(params))?,
             params,
+            #[cfg(feature = "prover")]
             num_application_steps: self.num_application_steps,
-            seeded_trivial: OnceCell::new(),
+            #[cfg(feature = "prover")]
+            step_sets: self.step_sets,
+            header_map: self.header_map,
+            #[cfg(feature = "prover")]
+            seeded_trivial: Default::default(),
             _marker: PhantomData,
         })
     }
 
     fn prevent_duplicate_suffixes<H: Header<C::CircuitField>>(&mut self) -> Result<()> {
-        match self.header_map.get(&H::SUFFIX) {
-            Some(ty) => {
-                if *ty != TypeId::of::<H>() {
-                    return Err(Error::Initialization(
-                        "two different Header implementations using the same suffix".into(),
-                    ));
-                }
-            }
-            None => {
-                self.header_map.insert(H::SUFFIX, TypeId::of::<H>());
+        register_suffix::<C::CircuitField, H>(&mut self.header_map)
+    }
+}
+
+/// A [`Header`] implementation that has been registered for its
+/// [`Suffix`](header::Suffix).
+#[derive(Clone, Copy)]
+pub(crate) struct RegisteredHeader {
+    type_id: TypeId,
+    /// The number of elements in the header's encoding, as computed by
+    /// [`header::encode`].
+    encoded_len: usize,
+}
+
+/// Records the [`Suffix`](header::Suffix) of the [`Header`] `H`, returning an
+/// error if a different [`Header`] implementation already uses it.
+pub(crate) fn register_suffix<F: ff::Field, H: Header<F>>(
+    header_map: &mut BTreeMap<header::Suffix, RegisteredHeader>,
+) -> Result<()> {
+    match header_map.get(&H::SUFFIX) {
+        Some(registered) => {
+            if registered.type_id != TypeId::of::<H>() {
+                return Err(Error::Initialization(
+                    "two different Header implementations using the same suffix".into(),
+                ));
             }
         }
-
-        Ok(())
+        None => {
+            header_map.insert(
+                H::SUFFIX,
+                RegisteredHeader {
+                    type_id: TypeId::of::<H>(),
+                    encoded_len: header::encoded_len::<F, H>()?,
+                },
+            );
+        }
     }
+
+    Ok(())
 }
 
 /// The recursion context that is used to create and verify proof-carrying data.
@@ -180,12 +233,36 @@ pub struct Application<'params, C: Cycle, R: Rank, const HEADER_SIZE: usize> {
     native_registry: Registry<'params, C::CircuitField, R>,
     nested_registry: Registry<'params, C::ScalarField, R>,
     params: &'params C::Params,
+    #[cfg(feature = "prover")]
     num_application_steps: usize,
+    /// The location of each registered [`StepSet`].
+    #[cfg(feature = "prover")]
+    step_sets: step::StepSets,
+    /// The [`Header`] registered for each [`Suffix`](header::Suffix), used to
+    /// verify [`AnyPcd`] and encoded headers.
+    header_map: BTreeMap<header::Suffix, RegisteredHeader>,
+    /// Cached seeded trivial proof for rerandomization. With the `std` feature
+    /// this is a [`OnceLock`](std::sync::OnceLock), so that the application is
+    /// [`Sync`] and can be shared between the threads of
+    /// [`Application::fuse_tree`].
+    #[cfg(all(feature = "prover", feature = "std"))]
+    seeded_trivial: std::sync::OnceLock<Proof<C, R>>,
     /// Cached seeded trivial proof for rerandomization.
-    seeded_trivial: OnceCell<Proof<C, R>>,
+    #[cfg(all(feature = "prover", not(feature = "std")))]
+    seeded_trivial: core::cell::OnceCell<Proof<C, R>>,
     _marker: PhantomData<[(); HEADER_SIZE]>,
 }
 
+impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
+    /// Lists every registered circuit (internal masks, internal circuits,
+    /// internal steps and application steps) along with its $\omega^j$ and
+    /// constraint counts.
+    pub fn circuit_report(&self) -> report::CircuitReport<C> {
+        report::CircuitReport::new(&self.native_registry, &self.nested_registry)
+    }
+}
+
+#[cfg(feature = "prover")]
 impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
     /// Seed a new computation by running a step with trivial inputs.
     ///
@@ -198,7 +275,26 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         step: S,
         witness: S::Witness<'source>,
     ) -> Result<(Proof<C, R>, S::Aux<'source>)> {
-        self.fuse(rng, step, witness, self.trivial_pcd(), self.trivial_pcd())
+        self.seed_with_instance(rng, step, witness, C::CircuitField::ZERO)
+    }
+
+    /// Seed a new computation by running a step with trivial inputs and a
+    /// public `instance` element. See [`Application::fuse_with_instance`].
+    pub fn seed_with_instance<'source, RNG: Rng, S: Step<C, Left = (), Right = ()>>(
+        &self,
+        rng: &mut RNG,
+        step: S,
+        witness: S::Witness<'source>,
+        instance: C::CircuitField,
+    ) -> Result<(Proof<C, R>, S::Aux<'source>)> {
+        self.fuse_with_instance(
+            rng,
+            step,
+            witness,
+            instance,
+            self.trivial_pcd(),
+            self.trivial_pcd(),
+        )
     }
 
     /// Returns a seeded trivial proof for use in rerandomization.
@@ -226,20 +322,46 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
     /// is valid for the same [`Header`] but reveals nothing else about the
     /// original proof. As a result, [`Application::verify`] should produce the
     /// same result on the provided `pcd` as it would the output of this method.
+    ///
+    /// This expects a zero public instance, and returns an error for proofs
+    /// created with another instance (see
+    /// [`Application::fuse_with_instance`]), which must be rerandomized using
+    /// [`Application::rerandomize_with_instance`].
     pub fn rerandomize<'source, RNG: Rng, H: Header<C::CircuitField>>(
         &self,
         pcd: Pcd<'source, C, R, H>,
         rng: &mut RNG,
     ) -> Result<Pcd<'source, C, R, H>> {
+        self.rerandomize_with_instance(pcd, C::CircuitField::ZERO, rng)
+    }
+
+    /// Rerandomize proof-carrying data that was created with a public
+    /// `instance`. See [`Application::rerandomize`].
+    ///
+    /// The rerandomized proof is bound to the same instance, and must be
+    /// verified using [`Application::verify_with_instance`]. Returns an error
+    /// if `pcd` was not created with `instance`.
+    pub fn rerandomize_with_instance<'source, RNG: Rng, H: Header<C::CircuitField>>(
+        &self,
+        pcd: Pcd<'source, C, R, H>,
+        instance: C::CircuitField,
+        rng: &mut RNG,
+    ) -> Result<Pcd<'source, C, R, H>> {
+        if pcd.proof.application.instance != instance {
+            return Err(Error::InvalidWitness(
+                "proof was not created for this instance".into(),
+            ));
+        }
         let data = pcd.data.clone();
 
         // Seed a trivial proof for rerandomization.
         // TODO: this is a temporary hack that allows the base case logic to be simple
         let seeded_trivial = self.seeded_trivial_pcd(rng);
-        let rerandomized_proof = self.fuse(
+        let rerandomized_proof = self.fuse_with_instance(
             rng,
             step::internal::rerandomize::Rerandomize::new(),
             (),
+            instance,
             pcd,
             seeded_trivial,
         )?;
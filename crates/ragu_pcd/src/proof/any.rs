@@ -0,0 +1,87 @@
+//! Type-erased proof-carrying data.
+//!
+//! A [`Pcd`] is statically typed by its [`Header`], but a party that receives
+//! proof-carrying data (for example, from the network) may not know which
+//! header it is for. [`AnyPcd`] instead carries the [`Suffix`] of the header
+//! along with its encoding, so that it can be verified with
+//! [`Application::verify_any`](crate::Application::verify_any), which
+//! dispatches on the suffix, and then recovered with [`AnyPcd::downcast`].
+
+use arithmetic::Cycle;
+use ragu_circuits::polynomials::Rank;
+use ragu_core::Result;
+
+use alloc::vec::Vec;
+
+use super::{Pcd, Proof};
+use crate::header::{self, Header, Suffix};
+
+/// Proof-carrying data for a [`Header`] that is only known at runtime by its
+/// [`Suffix`].
+pub struct AnyPcd<C: Cycle, R: Rank> {
+    /// The recursive proof for the accompanying data.
+    pub proof: Proof<C, R>,
+    suffix: Suffix,
+    header: Vec<C::CircuitField>,
+}
+
+impl<C: Cycle, R: Rank, H: Header<C::CircuitField>> Pcd<'_, C, R, H> {
+    /// Erases the [`Header`] type of this proof-carrying data, keeping only
+    /// the encoding of its data.
+    pub fn into_any(self) -> Result<AnyPcd<C, R>> {
+        Ok(AnyPcd {
+            proof: self.proof,
+            suffix: H::SUFFIX,
+            header: header::encode::<_, H>(self.data)?,
+        })
+    }
+}
+
+impl<C: Cycle, R: Rank> AnyPcd<C, R> {
+    /// Creates type-erased proof-carrying data from a [`Proof`] and the
+    /// [`Suffix`] and encoding of its header, as computed by
+    /// [`header::encode`], such as when they are received from the network.
+    pub fn new(proof: Proof<C, R>, suffix: Suffix, header: Vec<C::CircuitField>) -> Self {
+        AnyPcd {
+            proof,
+            suffix,
+            header,
+        }
+    }
+
+    /// The [`Suffix`] of the [`Header`] that this proof-carrying data is for.
+    pub fn suffix(&self) -> Suffix {
+        self.suffix
+    }
+
+    /// The encoding of the header, excluding padding and the suffix, as
+    /// computed by [`header::encode`].
+    pub fn header(&self) -> &[C::CircuitField] {
+        &self.header
+    }
+
+    /// Returns `true` if this proof-carrying data has the suffix of the
+    /// [`Header`] `H`.
+    pub fn is<H: Header<C::CircuitField>>(&self) -> bool {
+        self.suffix == H::SUFFIX
+    }
+
+    /// Recovers the [`Pcd`] for the [`Header`] `H` by decoding its data with
+    /// [`Header::decode`], returning `self` if the suffix of `H` does not
+    /// match or the encoding is not the encoding of any data for `H`.
+    #[allow(clippy::result_large_err)]
+    pub fn downcast<H: Header<C::CircuitField>>(
+        self,
+    ) -> core::result::Result<Pcd<'static, C, R, H>, Self> {
+        if !self.is::<H>() {
+            return Err(self);
+        }
+
+        match H::decode(&self.header) {
+            Some(data) if header::encode::<_, H>(data.clone()).is_ok_and(|e| e == self.header) => {
+                Ok(self.proof.carry(data))
+            }
+            _ => Err(self),
+        }
+    }
+}
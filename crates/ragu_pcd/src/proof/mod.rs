@@ -6,9 +6,14 @@
 
 #![allow(dead_code)]
 
+mod any;
 pub(crate) mod components;
+mod store;
 pub(crate) use components::*;
 
+pub use any::AnyPcd;
+pub use store::{PolynomialStore, StoreKey};
+
 use arithmetic::Cycle;
 use ff::Field;
 use ragu_circuits::{
@@ -43,16 +48,29 @@ impl<C: Cycle, R: Rank, H: Header<C::CircuitField>> Clone for Pcd<'_, C, R, H> {
 }
 
 /// Represents a recursive proof for the correctness of some computation.
+///
+/// Only the polynomials needed to verify the proof, or to fuse it into a later
+/// step, are retained. The rest (such as the nested stage polynomials, which
+/// are only needed to compute their commitments) are dropped during
+/// [`Application::fuse`](crate::Application::fuse) once they have been
+/// committed to and evaluated.
+///
+/// The retained polynomials each have up to `R::num_coeffs()` coefficients.
+/// They can be moved out of memory into a caller-provided
+/// [`PolynomialStore`], either by
+/// [`Application::fuse_with_store`](crate::Application::fuse_with_store) or by
+/// [`Proof::spill`], after which the proof holds only their [`StoreKey`]s until
+/// [`Proof::load`] reads them back.
 #[derive(Clone)]
 pub struct Proof<C: Cycle, R: Rank> {
     pub(crate) application: Application<C, R>,
     pub(crate) preamble: Preamble<C, R>,
-    pub(crate) s_prime: SPrime<C, R>,
+    pub(crate) s_prime: SPrime<C>,
     pub(crate) error_n: ErrorN<C, R>,
     pub(crate) error_m: ErrorM<C, R>,
     pub(crate) ab: AB<C, R>,
     pub(crate) query: Query<C, R>,
-    pub(crate) f: F<C, R>,
+    pub(crate) f: F<C>,
     pub(crate) eval: Eval<C, R>,
     pub(crate) p: P<C, R>,
     pub(crate) challenges: Challenges<C>,
@@ -67,6 +85,7 @@ impl<C: Cycle, R: Rank> Proof<C, R> {
 }
 
 impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> crate::Application<'_, C, R, HEADER_SIZE> {
+    #[cfg(feature = "prover")]
     pub(crate) fn trivial_pcd<'source>(&self) -> Pcd<'source, C, R, ()> {
         self.trivial_proof().carry(())
     }
@@ -89,114 +108,105 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> crate::Application<'_, C, R, H
                 circuit_id: CircuitIndex::new(0),
                 left_header: vec![C::CircuitField::ZERO; HEADER_SIZE],
                 right_header: vec![C::CircuitField::ZERO; HEADER_SIZE],
-                rx: zero_structured_host.clone(),
+                instance: C::CircuitField::ZERO,
+                left_instance: C::CircuitField::ZERO,
+                right_instance: C::CircuitField::ZERO,
+                rx: zero_structured_host.clone().into(),
                 blind: host_blind,
                 commitment: host_commitment,
             },
             preamble: Preamble {
-                native_rx: zero_structured_host.clone(),
+                native_rx: zero_structured_host.clone().into(),
                 native_blind: host_blind,
                 native_commitment: host_commitment,
-                nested_rx: zero_structured_nested.clone(),
                 nested_blind,
                 nested_commitment,
             },
             s_prime: SPrime {
-                registry_wx0_poly: zero_unstructured.clone(),
                 registry_wx0_blind: host_blind,
                 registry_wx0_commitment: host_commitment,
-                registry_wx1_poly: zero_unstructured.clone(),
                 registry_wx1_blind: host_blind,
                 registry_wx1_commitment: host_commitment,
-                nested_s_prime_rx: zero_structured_nested.clone(),
                 nested_s_prime_blind: nested_blind,
                 nested_s_prime_commitment: nested_commitment,
             },
             error_n: ErrorN {
-                native_rx: zero_structured_host.clone(),
+                native_rx: zero_structured_host.clone().into(),
                 native_blind: host_blind,
                 native_commitment: host_commitment,
-                nested_rx: zero_structured_nested.clone(),
                 nested_blind,
                 nested_commitment,
             },
             error_m: ErrorM {
-                registry_wy_poly: zero_structured_host.clone(),
                 registry_wy_blind: host_blind,
                 registry_wy_commitment: host_commitment,
-                native_rx: zero_structured_host.clone(),
+                native_rx: zero_structured_host.clone().into(),
                 native_blind: host_blind,
                 native_commitment: host_commitment,
-                nested_rx: zero_structured_nested.clone(),
                 nested_blind,
                 nested_commitment,
             },
             ab: AB {
-                a_poly: zero_structured_host.clone(),
+                a_poly: zero_structured_host.clone().into(),
                 a_blind: host_blind,
                 a_commitment: host_commitment,
-                b_poly: zero_structured_host.clone(),
+                b_poly: zero_structured_host.clone().into(),
                 b_blind: host_blind,
                 b_commitment: host_commitment,
                 c: C::CircuitField::ZERO,
-                nested_rx: zero_structured_nested.clone(),
                 nested_blind,
                 nested_commitment,
             },
             query: Query {
-                registry_xy_poly: zero_unstructured.clone(),
+                registry_xy_poly: zero_unstructured.clone().into(),
                 registry_xy_blind: host_blind,
                 registry_xy_commitment: host_commitment,
-                native_rx: zero_structured_host.clone(),
+                native_rx: zero_structured_host.clone().into(),
                 native_blind: host_blind,
                 native_commitment: host_commitment,
-                nested_rx: zero_structured_nested.clone(),
                 nested_blind,
                 nested_commitment,
             },
             f: F {
-                poly: zero_unstructured.clone(),
                 blind: host_blind,
                 commitment: host_commitment,
-                nested_rx: zero_structured_nested.clone(),
                 nested_blind,
                 nested_commitment,
             },
             eval: Eval {
-                native_rx: zero_structured_host.clone(),
+                native_rx: zero_structured_host.clone().into(),
                 native_blind: host_blind,
                 native_commitment: host_commitment,
-                nested_rx: zero_structured_nested.clone(),
                 nested_blind,
                 nested_commitment,
             },
             p: P {
-                poly: zero_unstructured.clone(),
+                poly: zero_unstructured.clone().into(),
                 blind: host_blind,
                 commitment: host_commitment,
                 v: C::CircuitField::ZERO,
-                endoscalar_rx: zero_structured_nested.clone(),
-                points_rx: zero_structured_nested.clone(),
+                endoscalar_rx: zero_structured_nested.clone().into(),
+                points_rx: zero_structured_nested.clone().into(),
                 step_rxs: vec![
-                    zero_structured_nested.clone();
+                    zero_structured_nested.clone().into();
                     NumStepsLen::<NUM_ENDOSCALING_POINTS>::len()
                 ],
             },
             challenges: Challenges::trivial(),
             circuits: InternalCircuits {
-                hashes_1_rx: zero_structured_host.clone(),
+                hashes_1_rx: zero_structured_host.clone().into(),
                 hashes_1_blind: host_blind,
                 hashes_1_commitment: host_commitment,
-                hashes_2_rx: zero_structured_host.clone(),
+                hashes_2_rx: zero_structured_host.clone().into(),
                 hashes_2_blind: host_blind,
                 hashes_2_commitment: host_commitment,
-                partial_collapse_rx: zero_structured_host.clone(),
+                partial_collapse_rx: zero_structured_host.clone().into(),
                 partial_collapse_blind: host_blind,
                 partial_collapse_commitment: host_commitment,
-                full_collapse_rx: zero_structured_host.clone(),
+                full_collapse_rx: zero_structured_host.clone().into(),
                 full_collapse_blind: host_blind,
                 full_collapse_commitment: host_commitment,
-                compute_v_rx: zero_structured_host,
+                compute_v_rx: zero_structured_host.into(),
                 compute_v_blind: host_blind,
                 compute_v_commitment: host_commitment,
             },
@@ -0,0 +1,249 @@
+//! Caller-provided storage for the polynomials of a [`Proof`].
+
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::polynomials::{Rank, structured, unstructured};
+use ragu_core::{Error, Result};
+
+use super::Proof;
+
+/// Identifies a polynomial that was written to a [`PolynomialStore`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StoreKey(pub u64);
+
+/// Storage for polynomials over `F`, such as a file or a database, provided by
+/// the caller to keep the polynomials of a [`Proof`] out of memory.
+///
+/// Each [`Proof`] carries a few dozen polynomials with up to
+/// `R::num_coeffs()` coefficients, which are needed to fuse it into a later
+/// step or to verify it but not in between.
+/// [`Application::fuse_with_store`](crate::Application::fuse_with_store)
+/// writes the polynomials of the proof it creates to a store once the proof is
+/// complete, so that the returned [`Proof`] holds only their [`StoreKey`]s
+/// alongside its commitments and scalars, and reads the polynomials of the
+/// child proofs back from the store. [`Proof::spill`] and [`Proof::load`] move
+/// the polynomials of an existing proof in and out of a store.
+///
+/// Proofs whose polynomials are in a store must be loaded before they are
+/// passed to [`Application::fuse`](crate::Application::fuse),
+/// [`Application::verify`](crate::Application::verify) or
+/// [`Application::rerandomize`](crate::Application::rerandomize), which
+/// return an error otherwise.
+///
+/// Polynomials are read back by the [`StoreKey`] returned when they were
+/// written, possibly more than once since proofs can be cloned. The store
+/// decides when to discard them; a proof whose polynomials were discarded can
+/// no longer be loaded.
+///
+/// A [`Proof`] has polynomials over both fields of its [`Cycle`], so the store
+/// must implement this trait for both.
+pub trait PolynomialStore<F: Field, R: Rank> {
+    /// Writes a structured polynomial and returns the key to read it with.
+    fn write_structured(&mut self, poly: &structured::Polynomial<F, R>) -> Result<StoreKey>;
+
+    /// Reads back a structured polynomial written under `key`.
+    fn read_structured(&self, key: StoreKey) -> Result<structured::Polynomial<F, R>>;
+
+    /// Writes an unstructured polynomial and returns the key to read it with.
+    fn write_unstructured(&mut self, poly: &unstructured::Polynomial<F, R>) -> Result<StoreKey>;
+
+    /// Reads back an unstructured polynomial written under `key`.
+    fn read_unstructured(&self, key: StoreKey) -> Result<unstructured::Polynomial<F, R>>;
+}
+
+/// A polynomial of a [`Proof`], which is either held in memory or has been
+/// written to a [`PolynomialStore`].
+#[derive(Clone)]
+pub(crate) enum Stored<P> {
+    Resident(P),
+    Spilled(StoreKey),
+}
+
+impl<P> From<P> for Stored<P> {
+    fn from(poly: P) -> Self {
+        Stored::Resident(poly)
+    }
+}
+
+impl<P> Stored<P> {
+    /// Returns the polynomial, which must be resident.
+    ///
+    /// Entry points that read the polynomials of a proof check
+    /// [`Proof::is_resident`] first, so this does not panic for proofs that
+    /// were loaded.
+    pub(crate) fn resident(&self) -> &P {
+        match self {
+            Stored::Resident(poly) => poly,
+            Stored::Spilled(_) => panic!("polynomial must be loaded from its store before use"),
+        }
+    }
+
+    fn is_resident(&self) -> bool {
+        matches!(self, Stored::Resident(_))
+    }
+}
+
+/// A kind of polynomial that a [`PolynomialStore`] holds.
+pub(crate) trait Storable<F: Field, R: Rank>: Sized {
+    fn write<S: PolynomialStore<F, R> + ?Sized>(&self, store: &mut S) -> Result<StoreKey>;
+    fn read<S: PolynomialStore<F, R> + ?Sized>(store: &S, key: StoreKey) -> Result<Self>;
+}
+
+impl<F: Field, R: Rank> Storable<F, R> for structured::Polynomial<F, R> {
+    fn write<S: PolynomialStore<F, R> + ?Sized>(&self, store: &mut S) -> Result<StoreKey> {
+        store.write_structured(self)
+    }
+
+    fn read<S: PolynomialStore<F, R> + ?Sized>(store: &S, key: StoreKey) -> Result<Self> {
+        store.read_structured(key)
+    }
+}
+
+impl<F: Field, R: Rank> Storable<F, R> for unstructured::Polynomial<F, R> {
+    fn write<S: PolynomialStore<F, R> + ?Sized>(&self, store: &mut S) -> Result<StoreKey> {
+        store.write_unstructured(self)
+    }
+
+    fn read<S: PolynomialStore<F, R> + ?Sized>(store: &S, key: StoreKey) -> Result<Self> {
+        store.read_unstructured(key)
+    }
+}
+
+/// Visits every polynomial of a [`Proof`], by the field it is over.
+trait Visitor<C: Cycle, R: Rank> {
+    fn native<P: Storable<C::CircuitField, R>>(&mut self, poly: &mut Stored<P>) -> Result<()>;
+    fn nested<P: Storable<C::ScalarField, R>>(&mut self, poly: &mut Stored<P>) -> Result<()>;
+}
+
+impl<C: Cycle, R: Rank> Proof<C, R> {
+    fn visit<V: Visitor<C, R>>(&mut self, v: &mut V) -> Result<()> {
+        v.native(&mut self.application.rx)?;
+        v.native(&mut self.preamble.native_rx)?;
+        v.native(&mut self.error_n.native_rx)?;
+        v.native(&mut self.error_m.native_rx)?;
+        v.native(&mut self.ab.a_poly)?;
+        v.native(&mut self.ab.b_poly)?;
+        v.native(&mut self.query.registry_xy_poly)?;
+        v.native(&mut self.query.native_rx)?;
+        v.native(&mut self.eval.native_rx)?;
+        v.native(&mut self.p.poly)?;
+        v.nested(&mut self.p.endoscalar_rx)?;
+        v.nested(&mut self.p.points_rx)?;
+        for step_rx in &mut self.p.step_rxs {
+            v.nested(step_rx)?;
+        }
+        v.native(&mut self.circuits.hashes_1_rx)?;
+        v.native(&mut self.circuits.hashes_2_rx)?;
+        v.native(&mut self.circuits.partial_collapse_rx)?;
+        v.native(&mut self.circuits.full_collapse_rx)?;
+        v.native(&mut self.circuits.compute_v_rx)?;
+        Ok(())
+    }
+
+    /// Writes every polynomial of this proof that is held in memory to
+    /// `store`, keeping only its [`StoreKey`].
+    pub fn spill<S>(&mut self, store: &mut S) -> Result<()>
+    where
+        S: PolynomialStore<C::CircuitField, R> + PolynomialStore<C::ScalarField, R> + ?Sized,
+    {
+        struct Spill<'a, S: ?Sized>(&'a mut S);
+
+        impl<C: Cycle, R: Rank, S> Visitor<C, R> for Spill<'_, S>
+        where
+            S: PolynomialStore<C::CircuitField, R> + PolynomialStore<C::ScalarField, R> + ?Sized,
+        {
+            fn native<P: Storable<C::CircuitField, R>>(
+                &mut self,
+                poly: &mut Stored<P>,
+            ) -> Result<()> {
+                if let Stored::Resident(p) = poly {
+                    *poly = Stored::Spilled(p.write(self.0)?);
+                }
+                Ok(())
+            }
+
+            fn nested<P: Storable<C::ScalarField, R>>(
+                &mut self,
+                poly: &mut Stored<P>,
+            ) -> Result<()> {
+                if let Stored::Resident(p) = poly {
+                    *poly = Stored::Spilled(p.write(self.0)?);
+                }
+                Ok(())
+            }
+        }
+
+        self.visit(&mut Spill(store))
+    }
+
+    /// Reads every polynomial of this proof that was written to `store` back
+    /// into memory.
+    pub fn load<S>(&mut self, store: &S) -> Result<()>
+    where
+        S: PolynomialStore<C::CircuitField, R> + PolynomialStore<C::ScalarField, R> + ?Sized,
+    {
+        struct Load<'a, S: ?Sized>(&'a S);
+
+        impl<C: Cycle, R: Rank, S> Visitor<C, R> for Load<'_, S>
+        where
+            S: PolynomialStore<C::CircuitField, R> + PolynomialStore<C::ScalarField, R> + ?Sized,
+        {
+            fn native<P: Storable<C::CircuitField, R>>(
+                &mut self,
+                poly: &mut Stored<P>,
+            ) -> Result<()> {
+                if let Stored::Spilled(key) = *poly {
+                    *poly = Stored::Resident(P::read(self.0, key)?);
+                }
+                Ok(())
+            }
+
+            fn nested<P: Storable<C::ScalarField, R>>(
+                &mut self,
+                poly: &mut Stored<P>,
+            ) -> Result<()> {
+                if let Stored::Spilled(key) = *poly {
+                    *poly = Stored::Resident(P::read(self.0, key)?);
+                }
+                Ok(())
+            }
+        }
+
+        self.visit(&mut Load(store))
+    }
+
+    /// Returns `true` if every polynomial of this proof is held in memory,
+    /// as needed to fuse, verify or rerandomize it.
+    pub fn is_resident(&self) -> bool {
+        // The same polynomials as in `visit`, which needs mutable access.
+        self.application.rx.is_resident()
+            && self.preamble.native_rx.is_resident()
+            && self.error_n.native_rx.is_resident()
+            && self.error_m.native_rx.is_resident()
+            && self.ab.a_poly.is_resident()
+            && self.ab.b_poly.is_resident()
+            && self.query.registry_xy_poly.is_resident()
+            && self.query.native_rx.is_resident()
+            && self.eval.native_rx.is_resident()
+            && self.p.poly.is_resident()
+            && self.p.endoscalar_rx.is_resident()
+            && self.p.points_rx.is_resident()
+            && self.p.step_rxs.iter().all(Stored::is_resident)
+            && self.circuits.hashes_1_rx.is_resident()
+            && self.circuits.hashes_2_rx.is_resident()
+            && self.circuits.partial_collapse_rx.is_resident()
+            && self.circuits.full_collapse_rx.is_resident()
+            && self.circuits.compute_v_rx.is_resident()
+    }
+
+    /// Returns an error unless [`Proof::is_resident`].
+    pub(crate) fn ensure_resident(&self) -> Result<()> {
+        if self.is_resident() {
+            Ok(())
+        } else {
+            Err(Error::InvalidWitness(
+                "proof polynomials are in a PolynomialStore and must be loaded first".into(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Summaries of the circuits registered in an [`Application`].
+//!
+//! The internal recursion circuits only fit into a [`Rank`] through careful
+//! budgeting of their constraints, so [`Application::circuit_report`] exposes
+//! the constraint counts of every registered circuit to make changes to them
+//! visible.
+//!
+//! [`Application`]: crate::Application
+//! [`Application::circuit_report`]: crate::Application::circuit_report
+
+use arithmetic::Cycle;
+use ff::PrimeField;
+use ragu_circuits::{
+    polynomials::Rank,
+    registry::{CircuitIndex, Registry},
+};
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The role of a circuit within a registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitKind {
+    /// A stage mask or final stage mask used by internal circuits.
+    InternalMask,
+    /// A circuit used internally for recursion.
+    InternalCircuit,
+    /// A [`Step`](crate::step::Step) used internally, such as for
+    /// rerandomization.
+    InternalStep,
+    /// A [`Step`](crate::step::Step) registered by the application.
+    ApplicationStep,
+}
+
+impl fmt::Display for CircuitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            CircuitKind::InternalMask => "internal mask",
+            CircuitKind::InternalCircuit => "internal circuit",
+            CircuitKind::InternalStep => "internal step",
+            CircuitKind::ApplicationStep => "application step",
+        })
+    }
+}
+
+/// Describes a single circuit in a registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitEntry<F> {
+    /// The index of the circuit in the registry.
+    pub index: usize,
+    /// The role of the circuit.
+    pub kind: CircuitKind,
+    /// The domain element $\omega^j$ assigned to the circuit. See
+    /// [`CircuitIndex::omega_j`].
+    pub omega_j: F,
+    /// The number of multiplication constraints.
+    pub num_multiplication_constraints: usize,
+    /// The number of linear constraints.
+    pub num_linear_constraints: usize,
+}
+
+/// Lists every circuit registered in an [`Application`](crate::Application),
+/// in registry order.
+pub struct CircuitReport<C: Cycle> {
+    /// Circuits over the [`CircuitField`](Cycle::CircuitField).
+    pub native: Vec<CircuitEntry<C::CircuitField>>,
+    /// Circuits over the [`ScalarField`](Cycle::ScalarField).
+    pub nested: Vec<CircuitEntry<C::ScalarField>>,
+}
+
+impl<C: Cycle> CircuitReport<C> {
+    pub(crate) fn new<R: Rank>(
+        native: &Registry<'_, C::CircuitField, R>,
+        nested: &Registry<'_, C::ScalarField, R>,
+    ) -> Self {
+        CircuitReport {
+            native: entries(native, crate::step::NUM_INTERNAL_STEPS),
+            nested: entries(nested, 0),
+        }
+    }
+}
+
+/// Describes the circuits of a registry whose last `num_internal_steps`
+/// internal circuits are internal steps.
+fn entries<F: PrimeField, R: Rank>(
+    registry: &Registry<'_, F, R>,
+    num_internal_steps: usize,
+) -> Vec<CircuitEntry<F>> {
+    let num_masks = registry.num_internal_masks();
+    let num_internal = registry.num_internal_circuits();
+
+    registry
+        .circuits()
+        .iter()
+        .enumerate()
+        .map(|(index, circuit)| {
+            let kind = if index < num_masks {
+                CircuitKind::InternalMask
+            } else if index < num_internal - num_internal_steps {
+                CircuitKind::InternalCircuit
+            } else if index < num_internal {
+                CircuitKind::InternalStep
+            } else {
+                CircuitKind::ApplicationStep
+            };
+            let (num_multiplication_constraints, num_linear_constraints) =
+                circuit.constraint_counts();
+
+            CircuitEntry {
+                index,
+                kind,
+                omega_j: CircuitIndex::new(index).omega_j(),
+                num_multiplication_constraints,
+                num_linear_constraints,
+            }
+        })
+        .collect()
+}
+
+impl<F: fmt::Debug> fmt::Display for CircuitEntry<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>5}  {:<16}  mul = {:>5}  lin = {:>5}  omega_j = {:?}",
+            self.index,
+            self.kind,
+            self.num_multiplication_constraints,
+            self.num_linear_constraints,
+            self.omega_j
+        )
+    }
+}
+
+impl<C: Cycle> fmt::Display for CircuitReport<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "native:")?;
+        for entry in &self.native {
+            writeln!(f, "{entry}")?;
+        }
+        writeln!(f, "nested:")?;
+        for entry in &self.nested {
+            writeln!(f, "{entry}")?;
+        }
+
+        Ok(())
+    }
+}
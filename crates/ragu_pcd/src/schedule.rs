@@ -0,0 +1,340 @@
+//! Scheduling the fusion of an entire tree of proof-carrying data.
+//!
+//! Aggregating many leaves into a single [`Pcd`] by hand means recursing over
+//! [`Application::seed`] and [`Application::fuse`] and deciding which fusions
+//! are independent. Instead, a [`Tree`] describes the whole computation and
+//! [`Application::fuse_tree`] executes it bottom-up, producing the [`Pcd`] for
+//! the root.
+//!
+//! With the `std` feature enabled, independent subtrees are fused on separate
+//! threads. The randomness used for each node is derived from the provided
+//! random number generator before any subtree is fused, so that the result
+//! does not depend on the order in which nodes are scheduled.
+//!
+//! Only trees are supported, not general DAGs: every node's [`Pcd`] is
+//! consumed by its single parent. A result needed by several parents must be
+//! fused separately and then supplied to each of them as a [`Tree::Done`]
+//! subtree, since [`Pcd`] is [`Clone`].
+//!
+//! # Checkpointing
+//!
+//! Every node is identified by its [`NodeId`], the path to it from the root.
+//! [`Application::fuse_tree`] reports the [`Pcd`] of each node to a checkpoint
+//! callback once it has been created, and an interrupted computation can be
+//! resumed by replacing the subtrees that were already fused with
+//! [`Tree::Done`] using [`Tree::resume`].
+
+use arithmetic::Cycle;
+use ragu_circuits::polynomials::Rank;
+use ragu_core::Result;
+use rand::{Rng, SeedableRng};
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{Application, Pcd, header::Header, step::Step};
+
+/// Identifies a node of a [`Tree`] by its path from the root.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId {
+    path: Vec<bool>,
+}
+
+impl NodeId {
+    /// The root of a tree.
+    pub fn root() -> Self {
+        NodeId::default()
+    }
+
+    /// The left child of this node.
+    pub fn left(&self) -> Self {
+        self.child(false)
+    }
+
+    /// The right child of this node.
+    pub fn right(&self) -> Self {
+        self.child(true)
+    }
+
+    /// The distance of this node from the root.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// The path from the root to this node, where `true` denotes the right
+    /// child.
+    pub fn path(&self) -> &[bool] {
+        &self.path
+    }
+
+    fn child(&self, right: bool) -> Self {
+        let mut path = self.path.clone();
+        path.push(right);
+        NodeId { path }
+    }
+}
+
+/// A binary tree of proof-carrying data to be fused by
+/// [`Application::fuse_tree`].
+///
+/// Leaves are seeded using a step `L` and internal nodes are fused using a
+/// step `S`, which must both produce the header `H`. The auxiliary data of
+/// each step is converted into the data carried by its [`Pcd`].
+pub enum Tree<'source, C: Cycle, R: Rank, H, L, S>
+where
+    H: Header<C::CircuitField>,
+    L: Step<C, Left = (), Right = (), Output = H>,
+    S: Step<C, Left = H, Right = H, Output = H>,
+{
+    /// Proof-carrying data that has already been created, such as a
+    /// checkpoint or the output of another computation.
+    Done(Box<Pcd<'source, C, R, H>>),
+
+    /// A leaf, created using [`Application::seed`].
+    Leaf {
+        /// The step that creates the leaf.
+        step: L,
+        /// The witness for `step`.
+        witness: L::Witness<'source>,
+    },
+
+    /// An internal node, created using [`Application::fuse`] on the results of
+    /// its children.
+    Node {
+        /// The step that fuses the children.
+        step: S,
+        /// The witness for `step`.
+        witness: S::Witness<'source>,
+        /// The left child.
+        left: Box<Self>,
+        /// The right child.
+        right: Box<Self>,
+    },
+}
+
+impl<'source, C: Cycle, R: Rank, H, L, S> Tree<'source, C, R, H, L, S>
+where
+    H: Header<C::CircuitField>,
+    L: Step<C, Left = (), Right = (), Output = H>,
+    S: Step<C, Left = H, Right = H, Output = H>,
+{
+    /// Creates a leaf.
+    pub fn leaf(step: L, witness: L::Witness<'source>) -> Self {
+        Tree::Leaf { step, witness }
+    }
+
+    /// Creates an internal node with the provided children.
+    pub fn node(step: S, witness: S::Witness<'source>, left: Self, right: Self) -> Self {
+        Tree::Node {
+            step,
+            witness,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Builds a balanced tree over `leaves`, preserving their order, by
+    /// repeatedly pairing adjacent subtrees. When a level has an odd number of
+    /// subtrees the last one is carried to the next level. The step and
+    /// witness of each internal node are obtained from `node`.
+    ///
+    /// Returns `None` if there are no leaves.
+    pub fn balanced(
+        leaves: impl IntoIterator<Item = Self>,
+        mut node: impl FnMut() -> (S, S::Witness<'source>),
+    ) -> Option<Self> {
+        let mut level: Vec<Self> = leaves.into_iter().collect();
+        while level.len() > 1 {
+            let mut next = Vec::with_capacity(level.len().div_ceil(2));
+            let mut subtrees = level.into_iter();
+            while let Some(left) = subtrees.next() {
+                next.push(match subtrees.next() {
+                    Some(right) => {
+                        let (step, witness) = node();
+                        Tree::node(step, witness, left, right)
+                    }
+                    None => left,
+                });
+            }
+            level = next;
+        }
+
+        level.pop()
+    }
+
+    /// Replaces every subtree for which `checkpoint` returns a [`Pcd`] with
+    /// [`Tree::Done`], so that a computation can resume without fusing those
+    /// subtrees again.
+    pub fn resume(
+        self,
+        mut checkpoint: impl FnMut(&NodeId) -> Option<Pcd<'source, C, R, H>>,
+    ) -> Self {
+        self.resume_at(&NodeId::root(), &mut checkpoint)
+    }
+
+    fn resume_at(
+        self,
+        id: &NodeId,
+        checkpoint: &mut impl FnMut(&NodeId) -> Option<Pcd<'source, C, R, H>>,
+    ) -> Self {
+        if let Tree::Done(_) = self {
+            return self;
+        }
+        if let Some(pcd) = checkpoint(id) {
+            return Tree::Done(Box::new(pcd));
+        }
+
+        match self {
+            Tree::Node {
+                step,
+                witness,
+                left,
+                right,
+            } => Tree::Node {
+                step,
+                witness,
+                left: Box::new(left.resume_at(&id.left(), checkpoint)),
+                right: Box::new(right.resume_at(&id.right(), checkpoint)),
+            },
+            tree => tree,
+        }
+    }
+}
+
+/// Derives an independent random number generator from `rng`.
+fn fork<RNG: Rng + SeedableRng>(rng: &mut RNG) -> RNG {
+    let mut seed = RNG::Seed::default();
+    rng.fill_bytes(seed.as_mut());
+    RNG::from_seed(seed)
+}
+
+/// Runs `a` and `b`, on separate threads if `threads > 1`.
+#[cfg(feature = "std")]
+fn join<A, B, RA, RB>(threads: usize, a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    if threads <= 1 {
+        return (a(), b());
+    }
+
+    std::thread::scope(|scope| {
+        let a = scope.spawn(a);
+        let b = b();
+        let a = a
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+
+        (a, b)
+    })
+}
+
+/// Runs `a` and `b`.
+#[cfg(not(feature = "std"))]
+fn join<A, B, RA, RB>(_: usize, a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA,
+    B: FnOnce() -> RB,
+{
+    (a(), b())
+}
+
+/// The number of threads available to [`Application::fuse_tree`].
+fn available_threads() -> usize {
+    #[cfg(feature = "std")]
+    {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        1
+    }
+}
+
+impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
+    /// Fuses an entire [`Tree`] bottom-up, returning the [`Pcd`] for its root.
+    ///
+    /// After the [`Pcd`] of a leaf or internal node is created it is passed to
+    /// `checkpoint` along with its [`NodeId`]; see the [module
+    /// documentation](crate::schedule) for resuming from checkpoints.
+    /// With the `std` feature, independent subtrees are fused in parallel and
+    /// `checkpoint` may be called from multiple threads.
+    pub fn fuse_tree<'source, RNG, H, L, S>(
+        &self,
+        rng: &mut RNG,
+        tree: Tree<'source, C, R, H, L, S>,
+        checkpoint: impl Fn(&NodeId, &Pcd<'source, C, R, H>) + Sync,
+    ) -> Result<Pcd<'source, C, R, H>>
+    where
+        RNG: Rng + SeedableRng + Send,
+        H: Header<C::CircuitField>,
+        L: Step<C, Left = (), Right = (), Output = H>,
+        S: Step<C, Left = H, Right = H, Output = H>,
+        L::Aux<'source>: Into<H::Data<'source>>,
+        S::Aux<'source>: Into<H::Data<'source>>,
+    {
+        self.fuse_subtree(
+            fork(rng),
+            tree,
+            NodeId::root(),
+            &checkpoint,
+            available_threads(),
+        )
+    }
+
+    fn fuse_subtree<'source, RNG, H, L, S, K>(
+        &self,
+        mut rng: RNG,
+        tree: Tree<'source, C, R, H, L, S>,
+        id: NodeId,
+        checkpoint: &K,
+        threads: usize,
+    ) -> Result<Pcd<'source, C, R, H>>
+    where
+        RNG: Rng + SeedableRng + Send,
+        H: Header<C::CircuitField>,
+        L: Step<C, Left = (), Right = (), Output = H>,
+        S: Step<C, Left = H, Right = H, Output = H>,
+        L::Aux<'source>: Into<H::Data<'source>>,
+        S::Aux<'source>: Into<H::Data<'source>>,
+        K: Fn(&NodeId, &Pcd<'source, C, R, H>) + Sync,
+    {
+        let pcd = match tree {
+            Tree::Done(pcd) => return Ok(*pcd),
+            Tree::Leaf { step, witness } => {
+                let (proof, aux) = self.seed(&mut rng, step, witness)?;
+                proof.carry(aux.into())
+            }
+            Tree::Node {
+                step,
+                witness,
+                left,
+                right,
+            } => {
+                let (left_rng, right_rng) = (fork(&mut rng), fork(&mut rng));
+                let (left_id, right_id) = (id.left(), id.right());
+                let (left, right) = join(
+                    threads,
+                    || self.fuse_subtree(left_rng, *left, left_id, checkpoint, threads / 2),
+                    || {
+                        self.fuse_subtree(
+                            right_rng,
+                            *right,
+                            right_id,
+                            checkpoint,
+                            threads - threads / 2,
+                        )
+                    },
+                );
+
+                let (proof, aux) = self.fuse(&mut rng, step, witness, left?, right?)?;
+                proof.carry(aux.into())
+            }
+        };
+
+        checkpoint(&id, &pcd);
+        Ok(pcd)
+    }
+}
@@ -14,15 +14,16 @@ use ragu_primitives::{
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use super::super::Step;
+use super::super::{Instances, Step};
 use crate::Header;
 
-/// Represents triple a length determined at compile time.
-pub struct TripleConstLen<const N: usize>;
+/// Represents triple a length determined at compile time, plus three for the
+/// public instances of the step and of its left and right child proofs.
+pub struct TripleConstLenPlusThree<const N: usize>;
 
-impl<const N: usize> Len for TripleConstLen<N> {
+impl<const N: usize> Len for TripleConstLenPlusThree<N> {
     fn len() -> usize {
-        N * 3
+        N * 3 + 3
     }
 }
 
@@ -47,13 +48,16 @@ impl<C: Cycle, S: Step<C>, R: Rank, const HEADER_SIZE: usize> Circuit<C::Circuit
         FixedVec<C::CircuitField, ConstLen<HEADER_SIZE>>,
         FixedVec<C::CircuitField, ConstLen<HEADER_SIZE>>,
         <S::Output as Header<C::CircuitField>>::Data<'source>,
+        (C::CircuitField, C::CircuitField, C::CircuitField),
     );
     type Witness<'source> = (
         <S::Left as Header<C::CircuitField>>::Data<'source>,
         <S::Right as Header<C::CircuitField>>::Data<'source>,
         S::Witness<'source>,
+        (C::CircuitField, C::CircuitField, C::CircuitField),
     );
-    type Output = Kind![C::CircuitField; FixedVec<Element<'_, _>, TripleConstLen<HEADER_SIZE>>];
+    type Output =
+        Kind![C::CircuitField; FixedVec<Element<'_, _>, TripleConstLenPlusThree<HEADER_SIZE>>];
     type Aux<'source> = (
         (
             FixedVec<C::CircuitField, ConstLen<HEADER_SIZE>>,
@@ -81,16 +85,25 @@ impl<C: Cycle, S: Step<C>, R: Rank, const HEADER_SIZE: usize> Circuit<C::Circuit
     where
         Self: 'dr,
     {
-        let (left, right, witness) = witness.cast();
+        let (left, right, witness, instances) = witness.cast();
 
+        let (instance, left_instance, right_instance) = instances.cast();
+        let instances = Instances {
+            instance: Element::alloc(dr, instance)?,
+            left: Element::alloc(dr, left_instance)?,
+            right: Element::alloc(dr, right_instance)?,
+        };
         let ((left, right, output), aux) = self
             .step
-            .witness::<_, HEADER_SIZE>(dr, witness, left, right)?;
+            .witness_with_instance::<_, HEADER_SIZE>(dr, witness, &instances, left, right)?;
 
-        let mut elements = Vec::with_capacity(HEADER_SIZE * 3);
+        let mut elements = Vec::with_capacity(HEADER_SIZE * 3 + 3);
         left.write(dr, &mut elements)?;
         right.write(dr, &mut elements)?;
         output.write(dr, &mut elements)?;
+        elements.push(instances.instance);
+        elements.push(instances.left);
+        elements.push(instances.right);
 
         let aux = D::with(|| {
             let left_header = elements[0..HEADER_SIZE]
@@ -7,12 +7,14 @@ use ragu_core::{
 use ragu_primitives::{
     Element, GadgetExt,
     io::{Buffer, Write},
+    vec::{CollectFixed, ConstLen, FixedVec},
 };
 
-use core::marker::PhantomData;
+use core::{iter::repeat_n, marker::PhantomData};
 
 use crate::Header;
 use crate::components::suffix::WithSuffix;
+use crate::header::Suffix;
 
 /// A header gadget padded to a fixed size with a suffix element appended.
 ///
@@ -49,6 +51,33 @@ pub(crate) fn for_header<
     })
 }
 
+/// Computes the padded encoding of a header outside of circuits, given the
+/// [`Suffix`] and the elements that its gadget writes.
+///
+/// Like [`for_header`], this fails if there are more than `HEADER_SIZE - 1`
+/// elements.
+pub(crate) fn native<F: PrimeField, const HEADER_SIZE: usize>(
+    suffix: Suffix,
+    elements: &[F],
+) -> Result<FixedVec<F, ConstLen<HEADER_SIZE>>> {
+    if elements.len() >= HEADER_SIZE {
+        return Err(ragu_core::Error::MalformedEncoding(
+            alloc::format!(
+                "Header encoding size exceeded HEADER_SIZE - 1 ({})",
+                HEADER_SIZE - 1,
+            )
+            .into(),
+        ));
+    }
+
+    elements
+        .iter()
+        .copied()
+        .chain(repeat_n(F::ZERO, HEADER_SIZE - 1 - elements.len()))
+        .chain(Some(F::from(suffix.get())))
+        .collect_fixed()
+}
+
 /// Inner gadget that writes the header gadget followed by zero padding up to
 /// `HEADER_SIZE - 1` elements (reserving space for the suffix).
 #[derive(Gadget)]
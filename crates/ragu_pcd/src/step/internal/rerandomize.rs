@@ -4,16 +4,20 @@
 //! trivial header `()` to produce the same left header. In order to ensure that
 //! this rerandomization step synthesizes the same circuit no matter what the
 //! left header is, we use a _uniform_ encoding of the left header.
+//!
+//! The instance of the step is constrained to equal the instance of the left
+//! proof, so that the rerandomized proof is valid for the same instance.
 
 use arithmetic::Cycle;
 use ragu_core::{
     Result,
     drivers::{Driver, DriverValue},
+    gadgets::Gadget,
 };
 
 use core::marker::PhantomData;
 
-use super::super::{Encoded, Index, Step};
+use super::super::{Encoded, Index, Instances, Step};
 use crate::Header;
 
 pub(crate) use crate::step::InternalStepIndex::Rerandomize as INTERNAL_ID;
@@ -70,6 +74,33 @@ impl<C: Cycle, H: Header<C::CircuitField>> Step<C> for Rerandomize<H> {
 
         Ok(((left.clone(), right, left), D::just(|| ())))
     }
+
+    fn witness_with_instance<
+        'dr,
+        'source: 'dr,
+        D: Driver<'dr, F = C::CircuitField>,
+        const HEADER_SIZE: usize,
+    >(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, Self::Witness<'source>>,
+        instances: &Instances<'dr, D>,
+        left: DriverValue<D, H::Data<'source>>,
+        right: DriverValue<D, ()>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        instances.instance.enforce_equal(dr, &instances.left)?;
+        Step::<C>::witness::<_, HEADER_SIZE>(self, dr, witness, left, right)
+    }
 }
 
 #[test]
@@ -4,13 +4,21 @@ mod encoder;
 pub(crate) mod internal;
 
 use arithmetic::Cycle;
+use ragu_circuits::polynomials::Rank;
+#[cfg(feature = "prover")]
 use ragu_circuits::registry::CircuitIndex;
 use ragu_core::{
     Result,
     drivers::{Driver, DriverValue},
 };
+use ragu_primitives::Element;
+
+use alloc::collections::BTreeMap;
+use core::{any::TypeId, ops::Range};
 
 use super::header::Header;
+use crate::ApplicationBuilder;
+#[cfg(feature = "prover")]
 use crate::circuits::native::NUM_INTERNAL_CIRCUITS;
 
 pub use encoder::Encoded;
@@ -27,10 +35,19 @@ pub(crate) enum InternalStepIndex {
 /// Internal representation of a [`Step`] index distinguishing internal vs.
 /// application steps.
 enum StepIndex {
+    // Internal step indices are only resolved when proving.
+    #[cfg_attr(not(feature = "prover"), allow(dead_code))]
     Internal(InternalStepIndex),
     Application(usize),
+    /// An application step whose index is relative to the first step of the
+    /// [`StepSet`] identified by the [`TypeId`] of its marker type.
+    Relative(fn() -> TypeId, usize),
 }
 
+/// The range of application step indices occupied by each registered
+/// [`StepSet`], keyed by the [`TypeId`] of its marker type.
+pub(crate) type StepSets = BTreeMap<TypeId, Range<usize>>;
+
 /// The number of internal steps used by Ragu for things like rerandomization or
 /// proof decompression.
 pub(crate) const NUM_INTERNAL_STEPS: usize = 2;
@@ -39,7 +56,9 @@ pub(crate) const NUM_INTERNAL_STEPS: usize = 2;
 ///
 /// All steps added to an application have a unique index and must be inserted
 /// sequentially so that their location (and other metadata) can be identified
-/// during proof generation and at other times.
+/// during proof generation and at other times. Steps that belong to a
+/// [`StepSet`] instead use an index [relative](Index::relative) to the set,
+/// which is resolved when the set is registered.
 pub struct Index {
     index: StepIndex,
 }
@@ -52,34 +71,65 @@ impl Index {
         }
     }
 
+    /// Creates a new [`Step`] index relative to the first step of the
+    /// [`StepSet`] identified by the marker type `M`.
+    ///
+    /// The steps of a set must be registered consecutively and in order of
+    /// their relative indices, starting from zero.
+    pub const fn relative<M: 'static>(value: usize) -> Self {
+        Index {
+            index: StepIndex::Relative(TypeId::of::<M>, value),
+        }
+    }
+
     /// Returns the circuit index for this step.
     ///
     /// Circuits are registered in the following order: internal masks,
     /// internal circuits, internal steps, then application steps.
     ///
-    /// Pass the known number of application steps to validate and compute the
-    /// final index of this step. Returns an error if an application step index
-    /// exceeds the number of registered steps.
-    pub(crate) fn circuit_index(&self, num_application_steps: usize) -> Result<CircuitIndex> {
-        match self.index {
+    /// Pass the known number of application steps and the registered step sets
+    /// to validate and compute the final index of this step. Returns an error
+    /// if an application step index exceeds the number of registered steps.
+    #[cfg(feature = "prover")]
+    pub(crate) fn circuit_index(
+        &self,
+        num_application_steps: usize,
+        step_sets: &StepSets,
+    ) -> Result<CircuitIndex> {
+        let i = match self.index {
             StepIndex::Internal(i) => {
                 // Internal steps come after internal circuits
-                Ok(CircuitIndex::from_u32(
+                return Ok(CircuitIndex::from_u32(
                     NUM_INTERNAL_CIRCUITS as u32 + i as u32,
-                ))
+                ));
             }
-            StepIndex::Application(i) => {
-                if i >= num_application_steps {
+            StepIndex::Application(i) => i,
+            StepIndex::Relative(set, i) => {
+                let range = step_sets.get(&set()).ok_or_else(|| {
+                    ragu_core::Error::Initialization(
+                        "attempted to use Step from a step set that was not registered".into(),
+                    )
+                })?;
+                if i >= range.len() {
                     return Err(ragu_core::Error::Initialization(
-                            "attempted to use application Step index that exceeds Application registered steps".into(),
-                        ));
+                        "attempted to use relative Step index that exceeds the registered steps of its set".into(),
+                    ));
                 }
 
-                Ok(CircuitIndex::new(
-                    NUM_INTERNAL_STEPS + NUM_INTERNAL_CIRCUITS + i,
-                ))
+                range.start + i
             }
+        };
+
+        if i >= num_application_steps {
+            return Err(ragu_core::Error::Initialization(
+                "attempted to use application Step index that exceeds Application registered steps"
+                    .into(),
+            ));
         }
+
+        Ok(CircuitIndex::new(
+            NUM_INTERNAL_STEPS + NUM_INTERNAL_CIRCUITS + i,
+        ))
     }
 
     /// Creates a new internal-defined [`Step`] index. Only called internally by
@@ -91,12 +141,13 @@ impl Index {
     }
 
     /// Called during application step registration to assert the appropriate
-    /// next sequential index.
+    /// next sequential index, recording the position of relative indices in
+    /// `step_sets`.
     ///
     /// ## Panics
     ///
     /// Panics if called on an internal step.
-    pub(crate) fn assert_index(&self, expect_id: usize) -> Result<()> {
+    pub(crate) fn assert_index(&self, expect_id: usize, step_sets: &mut StepSets) -> Result<()> {
         match self.index {
             StepIndex::Application(i) => {
                 if i != expect_id {
@@ -107,43 +158,101 @@ impl Index {
 
                 Ok(())
             }
+            StepIndex::Relative(set, i) => {
+                let range = step_sets.entry(set()).or_insert(expect_id..expect_id);
+                if range.end != expect_id || range.start + i != expect_id {
+                    return Err(ragu_core::Error::Initialization(
+                        "steps of a step set must be registered consecutively in sequential order"
+                            .into(),
+                    ));
+                }
+                range.end += 1;
+
+                Ok(())
+            }
             StepIndex::Internal(_) => panic!("step should be application-defined"),
         }
     }
 }
 
 #[test]
+#[cfg(feature = "prover")]
 fn test_index_map() -> Result<()> {
     use crate::circuits::native::NUM_INTERNAL_CIRCUITS;
 
     let num_application_steps = 10;
     let app_offset = NUM_INTERNAL_STEPS + NUM_INTERNAL_CIRCUITS;
+    let mut step_sets = StepSets::new();
 
     // Internal steps come after internal circuits
     assert_eq!(
-        Index::internal(InternalStepIndex::Rerandomize).circuit_index(num_application_steps)?,
+        Index::internal(InternalStepIndex::Rerandomize)
+            .circuit_index(num_application_steps, &step_sets)?,
         CircuitIndex::new(NUM_INTERNAL_CIRCUITS)
     );
     assert_eq!(
-        Index::internal(InternalStepIndex::Trivial).circuit_index(num_application_steps)?,
+        Index::internal(InternalStepIndex::Trivial)
+            .circuit_index(num_application_steps, &step_sets)?,
         CircuitIndex::new(NUM_INTERNAL_CIRCUITS + 1)
     );
 
     // Application steps occupy indices (NUM_INTERNAL_CIRCUITS + NUM_INTERNAL_STEPS)..
     assert_eq!(
-        Index::new(0).circuit_index(num_application_steps)?,
+        Index::new(0).circuit_index(num_application_steps, &step_sets)?,
         CircuitIndex::new(app_offset)
     );
     assert_eq!(
-        Index::new(1).circuit_index(num_application_steps)?,
+        Index::new(1).circuit_index(num_application_steps, &step_sets)?,
         CircuitIndex::new(app_offset + 1)
     );
-    Index::new(999).assert_index(999)?;
-    assert!(Index::new(10).circuit_index(num_application_steps).is_err());
+    Index::new(999).assert_index(999, &mut step_sets)?;
+    assert!(
+        Index::new(10)
+            .circuit_index(num_application_steps, &step_sets)
+            .is_err()
+    );
+
+    // Relative indices are resolved once their set is registered.
+    struct Set;
+    assert!(
+        Index::relative::<Set>(0)
+            .circuit_index(num_application_steps, &step_sets)
+            .is_err()
+    );
+    Index::relative::<Set>(0).assert_index(4, &mut step_sets)?;
+    Index::relative::<Set>(1).assert_index(5, &mut step_sets)?;
+    assert!(
+        Index::relative::<Set>(3)
+            .assert_index(6, &mut step_sets)
+            .is_err()
+    );
+    assert_eq!(
+        Index::relative::<Set>(1).circuit_index(num_application_steps, &step_sets)?,
+        CircuitIndex::new(app_offset + 5)
+    );
+    assert!(
+        Index::relative::<Set>(2)
+            .circuit_index(num_application_steps, &step_sets)
+            .is_err()
+    );
 
     Ok(())
 }
 
+/// The public instances available to [`Step::witness_with_instance`].
+///
+/// The instances of the child proofs are bound to the instances that those
+/// proofs were created with, so a step can constrain them, for example to
+/// require that its children were created for the same block height.
+pub struct Instances<'dr, D: Driver<'dr>> {
+    /// The instance of this step, supplied by the verifier.
+    pub instance: Element<'dr, D>,
+    /// The instance of the step that created the left child proof.
+    pub left: Element<'dr, D>,
+    /// The instance of the step that created the right child proof.
+    pub right: Element<'dr, D>,
+}
+
 /// Represents a node in the computational graph (or the proof-carrying data
 /// tree) that represents the merging of two pieces of proof-carrying data.
 pub trait Step<C: Cycle>: Sized + Send + Sync {
@@ -184,4 +293,73 @@ pub trait Step<C: Cycle>: Sized + Send + Sync {
     )>
     where
         Self: 'dr;
+
+    /// Synthesis method for steps that read a public `instance` supplied by
+    /// the verifier rather than carried by proofs, such as a block height.
+    ///
+    /// The instance is a single field element that is bound into the public
+    /// inputs of the step's circuit; richer context can be hashed into it. It
+    /// is provided to [`Application::fuse_with_instance`] and
+    /// [`Application::verify_with_instance`], and is zero when using
+    /// [`Application::fuse`] and [`Application::verify`]. Proofs keep their
+    /// instance through [`Application::rerandomize_with_instance`].
+    ///
+    /// The [`Instances`] also hold the instances that the left and right child
+    /// proofs were created with. Nothing else constrains them, so steps that
+    /// depend on them must check them here.
+    ///
+    /// By default the instances are ignored and [`Step::witness`] is used.
+    /// Steps that override this method can implement [`Step::witness`] by
+    /// calling it with zero instances.
+    ///
+    /// [`Application::fuse`]: crate::Application::fuse
+    /// [`Application::fuse_with_instance`]: crate::Application::fuse_with_instance
+    /// [`Application::rerandomize_with_instance`]: crate::Application::rerandomize_with_instance
+    /// [`Application::verify`]: crate::Application::verify
+    /// [`Application::verify_with_instance`]: crate::Application::verify_with_instance
+    fn witness_with_instance<
+        'dr,
+        'source: 'dr,
+        D: Driver<'dr, F = C::CircuitField>,
+        const HEADER_SIZE: usize,
+    >(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, Self::Witness<'source>>,
+        instances: &Instances<'dr, D>,
+        left: DriverValue<D, <Self::Left as Header<C::CircuitField>>::Data<'source>>,
+        right: DriverValue<D, <Self::Right as Header<C::CircuitField>>::Data<'source>>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let _ = instances;
+        self.witness(dr, witness, left, right)
+    }
+}
+
+/// A collection of [`Step`]s that can be registered as a unit, so that
+/// independently developed sets of steps can be combined into one
+/// [`Application`](crate::Application).
+///
+/// Steps in a set use an [`Index::relative`] to a marker type that identifies
+/// the set, and are registered consecutively by [`StepSet::register`]. The
+/// position of the set within the application is resolved by
+/// [`ApplicationBuilder::register_set`].
+pub trait StepSet<C: Cycle> {
+    /// Registers each [`Step`] of this set with `builder`, in order of their
+    /// relative indices.
+    fn register<'params, R: Rank, const HEADER_SIZE: usize>(
+        self,
+        builder: ApplicationBuilder<'params, C, R, HEADER_SIZE>,
+    ) -> Result<ApplicationBuilder<'params, C, R, HEADER_SIZE>>
+    where
+        Self: 'params;
 }
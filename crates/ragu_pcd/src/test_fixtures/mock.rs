@@ -0,0 +1,377 @@
+//! A mock [`Application`](crate::Application) for fast testing of [`Step`]
+//! logic.
+//!
+//! [`MockApplication`] mirrors the `seed`/`fuse`/`verify`/`rerandomize` API of
+//! the real [`Application`](crate::Application) but skips all cryptography:
+//! each [`Step`] is synthesized under the [`Simulator`], which checks that its
+//! constraints are satisfied, and the resulting [`MockProof`] only records the
+//! encoding of its output header. Fusing checks that the headers encoded by a
+//! step for its inputs match the headers of the provided [`MockPcd`]s, which
+//! catches mismatched header data and suffixes the same way that verification
+//! of a real proof would.
+
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::registry::CircuitIndex;
+use ragu_core::{Error, Result, maybe::Maybe};
+use ragu_primitives::{Element, Simulator};
+use rand::Rng;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::marker::PhantomData;
+
+use crate::{
+    RegisteredHeader,
+    header::{Header, Suffix},
+    register_suffix,
+    step::{Encoded, Instances, Step, StepSets},
+};
+
+/// A mock of [`Application`](crate::Application) that synthesizes steps under
+/// the [`Simulator`] instead of creating proofs.
+pub struct MockApplication<C: Cycle, const HEADER_SIZE: usize> {
+    num_application_steps: usize,
+    step_sets: StepSets,
+    header_map: BTreeMap<Suffix, RegisteredHeader>,
+    _marker: PhantomData<C>,
+}
+
+/// A mock of [`Proof`](crate::Proof) that records the encoding of the header
+/// and public instance it was created for.
+pub struct MockProof<C: Cycle> {
+    circuit_id: CircuitIndex,
+    header: Vec<C::CircuitField>,
+    instance: C::CircuitField,
+}
+
+impl<C: Cycle> Clone for MockProof<C> {
+    fn clone(&self) -> Self {
+        MockProof {
+            circuit_id: self.circuit_id,
+            header: self.header.clone(),
+            instance: self.instance,
+        }
+    }
+}
+
+impl<C: Cycle> MockProof<C> {
+    /// Augment a mock proof with some data, described by a [`Header`].
+    pub fn carry<H: Header<C::CircuitField>>(self, data: H::Data<'_>) -> MockPcd<'_, C, H> {
+        MockPcd { proof: self, data }
+    }
+}
+
+/// A mock of [`Pcd`](crate::Pcd).
+pub struct MockPcd<'source, C: Cycle, H: Header<C::CircuitField>> {
+    /// The mock proof.
+    pub proof: MockProof<C>,
+
+    /// The data carried by the proof.
+    pub data: H::Data<'source>,
+}
+
+impl<C: Cycle, H: Header<C::CircuitField>> Clone for MockPcd<'_, C, H> {
+    fn clone(&self) -> Self {
+        MockPcd {
+            proof: self.proof.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// Computes the encoding of the header `H` for `data`, including its padding
+/// and suffix.
+fn encode_header<C: Cycle, H: Header<C::CircuitField>, const HEADER_SIZE: usize>(
+    data: H::Data<'_>,
+) -> Result<Vec<C::CircuitField>> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    Simulator::simulate(data, |dr, data| {
+        let encoded = Encoded::<_, H, HEADER_SIZE>::new(dr, data)?;
+        header = values(dr, encoded)?;
+        Ok(())
+    })?;
+
+    Ok(header)
+}
+
+/// Writes an encoded header, returning the values of its elements.
+fn values<'dr, F: ff::PrimeField, H: Header<F>, const HEADER_SIZE: usize>(
+    dr: &mut Simulator<F>,
+    encoded: Encoded<'dr, Simulator<F>, H, HEADER_SIZE>,
+) -> Result<Vec<F>> {
+    let mut elements: Vec<Element<'dr, Simulator<F>>> = Vec::with_capacity(HEADER_SIZE);
+    encoded.write(dr, &mut elements)?;
+
+    Ok(elements.iter().map(|e| *e.value().take()).collect())
+}
+
+impl<C: Cycle, const HEADER_SIZE: usize> Default for MockApplication<C, HEADER_SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Cycle, const HEADER_SIZE: usize> MockApplication<C, HEADER_SIZE> {
+    /// Create an empty [`MockApplication`].
+    pub fn new() -> Self {
+        MockApplication {
+            num_application_steps: 0,
+            step_sets: StepSets::new(),
+            header_map: BTreeMap::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Register a new application-defined [`Step`], subject to the same
+    /// checks as [`ApplicationBuilder::register`](crate::ApplicationBuilder::register).
+    pub fn register<S: Step<C>>(mut self, _step: S) -> Result<Self> {
+        S::INDEX.assert_index(self.num_application_steps, &mut self.step_sets)?;
+
+        register_suffix::<_, S::Output>(&mut self.header_map)?;
+        register_suffix::<_, S::Left>(&mut self.header_map)?;
+        register_suffix::<_, S::Right>(&mut self.header_map)?;
+
+        self.num_application_steps += 1;
+
+        Ok(self)
+    }
+
+    fn trivial_pcd<'source>(&self) -> Result<MockPcd<'source, C, ()>> {
+        Ok(MockProof {
+            circuit_id: CircuitIndex::new(0),
+            header: encode_header::<C, (), HEADER_SIZE>(())?,
+            instance: C::CircuitField::ZERO,
+        }
+        .carry(()))
+    }
+
+    /// Seed a new computation by running a step with trivial inputs. See
+    /// [`Application::seed`](crate::Application::seed).
+    pub fn seed<'source, RNG: Rng, S: Step<C, Left = (), Right = ()>>(
+        &self,
+        rng: &mut RNG,
+        step: S,
+        witness: S::Witness<'source>,
+    ) -> Result<(MockProof<C>, S::Aux<'source>)> {
+        self.seed_with_instance(rng, step, witness, C::CircuitField::ZERO)
+    }
+
+    /// Seed a new computation with a public instance. See
+    /// [`Application::seed_with_instance`](crate::Application::seed_with_instance).
+    pub fn seed_with_instance<'source, RNG: Rng, S: Step<C, Left = (), Right = ()>>(
+        &self,
+        rng: &mut RNG,
+        step: S,
+        witness: S::Witness<'source>,
+        instance: C::CircuitField,
+    ) -> Result<(MockProof<C>, S::Aux<'source>)> {
+        self.fuse_with_instance(
+            rng,
+            step,
+            witness,
+            instance,
+            self.trivial_pcd()?,
+            self.trivial_pcd()?,
+        )
+    }
+
+    /// Fuse two [`MockPcd`]s using the provided step. See
+    /// [`Application::fuse`](crate::Application::fuse).
+    ///
+    /// Returns [`Error::InvalidWitness`] if the step's constraints are not
+    /// satisfied, or if the headers it encodes for its inputs differ from the
+    /// headers of `left` and `right`.
+    pub fn fuse<'source, RNG: Rng, S: Step<C>>(
+        &self,
+        rng: &mut RNG,
+        step: S,
+        witness: S::Witness<'source>,
+        left: MockPcd<'source, C, S::Left>,
+        right: MockPcd<'source, C, S::Right>,
+    ) -> Result<(MockProof<C>, S::Aux<'source>)> {
+        self.fuse_with_instance(rng, step, witness, C::CircuitField::ZERO, left, right)
+    }
+
+    /// Fuse two [`MockPcd`]s using the provided step and public instance. See
+    /// [`Application::fuse_with_instance`](crate::Application::fuse_with_instance).
+    pub fn fuse_with_instance<'source, RNG: Rng, S: Step<C>>(
+        &self,
+        _: &mut RNG,
+        step: S,
+        witness: S::Witness<'source>,
+        instance: C::CircuitField,
+        left: MockPcd<'source, C, S::Left>,
+        right: MockPcd<'source, C, S::Right>,
+    ) -> Result<(MockProof<C>, S::Aux<'source>)> {
+        let circuit_id = S::INDEX.circuit_index(self.num_application_steps, &self.step_sets)?;
+
+        let instances = (instance, left.proof.instance, right.proof.instance);
+
+        let mut result = None;
+        Simulator::simulate(
+            (left.data, right.data, witness, instances),
+            |dr, witness| {
+                let (left, right, witness, instances) = witness.cast();
+                let (instance, left_instance, right_instance) = instances.cast();
+                let instances = Instances {
+                    instance: Element::alloc(dr, instance)?,
+                    left: Element::alloc(dr, left_instance)?,
+                    right: Element::alloc(dr, right_instance)?,
+                };
+                let ((left, right, output), aux) = step.witness_with_instance::<_, HEADER_SIZE>(
+                    dr, witness, &instances, left, right,
+                )?;
+
+                result = Some((
+                    values(dr, left)?,
+                    values(dr, right)?,
+                    values(dr, output)?,
+                    *instances.instance.value().take(),
+                    aux.take(),
+                ));
+                Ok(())
+            },
+        )?;
+        let (left_header, right_header, header, instance, aux) =
+            result.expect("simulation succeeded");
+
+        if left_header != left.proof.header {
+            return Err(Error::InvalidWitness(
+                "left header does not match the left proof".into(),
+            ));
+        }
+        if right_header != right.proof.header {
+            return Err(Error::InvalidWitness(
+                "right header does not match the right proof".into(),
+            ));
+        }
+
+        Ok((
+            MockProof {
+                circuit_id,
+                header,
+                instance,
+            },
+            aux,
+        ))
+    }
+
+    /// Verifies some [`MockPcd`] for the provided [`Header`]. See
+    /// [`Application::verify`](crate::Application::verify).
+    pub fn verify<RNG: Rng, H: Header<C::CircuitField>>(
+        &self,
+        pcd: &MockPcd<'_, C, H>,
+        rng: RNG,
+    ) -> Result<bool> {
+        self.verify_with_instance(pcd, C::CircuitField::ZERO, rng)
+    }
+
+    /// Verifies some [`MockPcd`] along with its public instance. See
+    /// [`Application::verify_with_instance`](crate::Application::verify_with_instance).
+    pub fn verify_with_instance<RNG: Rng, H: Header<C::CircuitField>>(
+        &self,
+        pcd: &MockPcd<'_, C, H>,
+        instance: C::CircuitField,
+        _: RNG,
+    ) -> Result<bool> {
+        Ok(
+            encode_header::<C, H, HEADER_SIZE>(pcd.data.clone())? == pcd.proof.header
+                && instance == pcd.proof.instance,
+        )
+    }
+
+    /// Rerandomize a [`MockPcd`] with a zero public instance. See
+    /// [`Application::rerandomize`](crate::Application::rerandomize).
+    pub fn rerandomize<'source, RNG: Rng, H: Header<C::CircuitField>>(
+        &self,
+        pcd: MockPcd<'source, C, H>,
+        rng: &mut RNG,
+    ) -> Result<MockPcd<'source, C, H>> {
+        self.rerandomize_with_instance(pcd, C::CircuitField::ZERO, rng)
+    }
+
+    /// Rerandomize a [`MockPcd`], which leaves it unchanged but checks its
+    /// public instance. See
+    /// [`Application::rerandomize_with_instance`](crate::Application::rerandomize_with_instance).
+    pub fn rerandomize_with_instance<'source, RNG: Rng, H: Header<C::CircuitField>>(
+        &self,
+        pcd: MockPcd<'source, C, H>,
+        instance: C::CircuitField,
+        _: &mut RNG,
+    ) -> Result<MockPcd<'source, C, H>> {
+        if pcd.proof.instance != instance {
+            return Err(Error::InvalidWitness(
+                "proof was not created for this instance".into(),
+            ));
+        }
+        Ok(pcd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::nontrivial::{Hash2, InternalNode, LeafNode, WitnessLeaf};
+    use ragu_pasta::{Fp, Pasta};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    type App = MockApplication<Pasta, 4>;
+
+    #[test]
+    fn test_mock_merging() -> Result<()> {
+        let pasta = Pasta::baked();
+        let poseidon_params = Pasta::circuit_poseidon(pasta);
+        let app = App::new()
+            .register(WitnessLeaf { poseidon_params })?
+            .register(Hash2 { poseidon_params })?;
+        let mut rng = StdRng::seed_from_u64(1234);
+
+        let leaf1 = app.seed(&mut rng, WitnessLeaf { poseidon_params }, Fp::from(42u64))?;
+        let leaf1 = leaf1.0.carry::<LeafNode>(leaf1.1);
+        assert!(app.verify(&leaf1, &mut rng)?);
+
+        let leaf2 = app.seed(&mut rng, WitnessLeaf { poseidon_params }, Fp::from(7u64))?;
+        let leaf2 = leaf2.0.carry::<LeafNode>(leaf2.1);
+
+        let node = app.fuse(
+            &mut rng,
+            Hash2 { poseidon_params },
+            (),
+            leaf1.clone(),
+            leaf2.clone(),
+        )?;
+        let node = node.0.carry::<InternalNode>(node.1);
+        assert!(app.verify(&node, &mut rng)?);
+
+        // Carrying the wrong data is detected by verification.
+        let wrong = node.proof.clone().carry::<InternalNode>(Fp::from(1u64));
+        assert!(!app.verify(&wrong, &mut rng)?);
+
+        // Fusing inputs that carry the wrong data is rejected.
+        let wrong = leaf1.proof.clone().carry::<LeafNode>(Fp::from(1u64));
+        assert!(
+            app.fuse(&mut rng, Hash2 { poseidon_params }, (), wrong, leaf2)
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mock_unregistered_step() -> Result<()> {
+        let pasta = Pasta::baked();
+        let poseidon_params = Pasta::circuit_poseidon(pasta);
+        let app = App::new().register(WitnessLeaf { poseidon_params })?;
+        let mut rng = StdRng::seed_from_u64(1234);
+
+        let leaf = app.seed(&mut rng, WitnessLeaf { poseidon_params }, Fp::from(42u64))?;
+        let leaf = leaf.0.carry::<LeafNode>(leaf.1);
+
+        assert!(
+            app.fuse(&mut rng, Hash2 { poseidon_params }, (), leaf.clone(), leaf)
+                .is_err()
+        );
+
+        Ok(())
+    }
+}
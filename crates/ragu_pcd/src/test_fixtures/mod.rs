@@ -1,5 +1,9 @@
 //! Test fixtures for ragu_pcd benchmarks.
 //!
-//! This module provides reusable Header and Step implementations for benchmarks.
+//! This module provides reusable Header and Step implementations for benchmarks,
+//! as well as a [`MockApplication`](mock::MockApplication) for testing steps
+//! without creating proofs.
 
+#[cfg(feature = "prover")]
+pub mod mock;
 pub mod nontrivial;
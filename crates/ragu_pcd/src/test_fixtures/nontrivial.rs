@@ -28,6 +28,13 @@ impl<F: Field> Header<F> for LeafNode {
     ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
         Element::alloc(dr, witness)
     }
+
+    fn decode(encoding: &[F]) -> Option<F> {
+        match encoding {
+            [value] => Some(*value),
+            _ => None,
+        }
+    }
 }
 
 pub struct InternalNode;
@@ -43,6 +50,13 @@ impl<F: Field> Header<F> for InternalNode {
     ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
         Element::alloc(dr, witness)
     }
+
+    fn decode(encoding: &[F]) -> Option<F> {
+        match encoding {
+            [value] => Some(*value),
+            _ => None,
+        }
+    }
 }
 
 pub struct Hash2<'params, C: Cycle> {
@@ -7,23 +7,105 @@ use ragu_circuits::{
     registry::CircuitIndex,
 };
 use ragu_core::{Result, drivers::emulator::Emulator, maybe::Maybe};
-use ragu_primitives::Element;
+use ragu_primitives::{
+    Element,
+    vec::{ConstLen, FixedVec},
+};
 use rand::Rng;
 
 use core::iter::once;
 
 use crate::{
-    Application, Pcd, Proof, circuits::native::stages::preamble::ProofInputs, components::claims,
-    header::Header,
+    AnyPcd, Application, Pcd, Proof,
+    circuits::native::stages::preamble::ProofInputs,
+    components::claims,
+    header::{self, Header, Suffix},
+    step::internal::padded,
 };
 
 impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_SIZE> {
     /// Verifies some [`Pcd`] for the provided [`Header`].
+    ///
+    /// This expects a zero public instance; proofs created with
+    /// [`Application::fuse_with_instance`] must be verified using
+    /// [`Application::verify_with_instance`].
     pub fn verify<RNG: Rng, H: Header<C::CircuitField>>(
         &self,
         pcd: &Pcd<'_, C, R, H>,
+        rng: RNG,
+    ) -> Result<bool> {
+        self.verify_with_instance(pcd, C::CircuitField::ZERO, rng)
+    }
+
+    /// Verifies some [`Pcd`] for the provided [`Header`] along with the public
+    /// `instance` of the [`Step`](crate::step::Step) that created it.
+    pub fn verify_with_instance<RNG: Rng, H: Header<C::CircuitField>>(
+        &self,
+        pcd: &Pcd<'_, C, R, H>,
+        instance: C::CircuitField,
+        rng: RNG,
+    ) -> Result<bool> {
+        let header = padded::native::<_, HEADER_SIZE>(
+            H::SUFFIX,
+            &header::encode::<_, H>(pcd.data.clone())?,
+        )?;
+
+        self.verify_padded(&pcd.proof, &header, instance, rng)
+    }
+
+    /// Verifies a [`Proof`] against an already-encoded header, without the
+    /// [`Header::Data`] that it was created from.
+    ///
+    /// The `header` is given by its [`Suffix`] and the field elements of its
+    /// encoding, excluding padding and the suffix, as computed by
+    /// [`header::encode`]. The proof does not verify unless a [`Header`] with
+    /// this suffix was registered with this [`Application`] and the encoding
+    /// has the same length as that header's, so trailing zero elements are
+    /// not ignored. As with [`Application::verify`], this expects a zero
+    /// public instance.
+    pub fn verify_encoded<RNG: Rng>(
+        &self,
+        proof: &Proof<C, R>,
+        suffix: Suffix,
+        header: &[C::CircuitField],
+        rng: RNG,
+    ) -> Result<bool> {
+        // The padding is not distinguishable from trailing zero elements, so
+        // the length must match the encoding of the header registered for
+        // the suffix.
+        match self.header_map.get(&suffix) {
+            Some(registered) if registered.encoded_len == header.len() => {}
+            _ => return Ok(false),
+        }
+        let Ok(header) = padded::native::<_, HEADER_SIZE>(suffix, header) else {
+            return Ok(false);
+        };
+
+        self.verify_padded(proof, &header, C::CircuitField::ZERO, rng)
+    }
+
+    /// Verifies some [`AnyPcd`] by dispatching on its [`Suffix`].
+    ///
+    /// The suffix is looked up among the headers registered with this
+    /// [`Application`], and proof-carrying data for an unregistered suffix or
+    /// with an encoding of the wrong length does not verify; see
+    /// [`Application::verify_encoded`]. As with [`Application::verify`], this
+    /// expects a zero public instance.
+    pub fn verify_any<RNG: Rng>(&self, pcd: &AnyPcd<C, R>, rng: RNG) -> Result<bool> {
+        self.verify_encoded(&pcd.proof, pcd.suffix(), pcd.header(), rng)
+    }
+
+    /// Verifies a [`Proof`] against the padded encoding of its header and its
+    /// public instance.
+    fn verify_padded<RNG: Rng>(
+        &self,
+        proof: &Proof<C, R>,
+        header: &FixedVec<C::CircuitField, ConstLen<HEADER_SIZE>>,
+        instance: C::CircuitField,
         mut rng: RNG,
     ) -> Result<bool> {
+        proof.ensure_resident()?;
+
         // Sample verification challenges w, y, and z.
         let w = C::CircuitField::random(&mut rng);
         let y = C::CircuitField::random(&mut rng);
@@ -33,7 +115,7 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         // (Internal circuit IDs are constants and don't need this check.)
         if !self
             .native_registry
-            .circuit_in_domain(pcd.proof.application.circuit_id)
+            .circuit_in_domain(proof.application.circuit_id)
         {
             return Ok(false);
         }
@@ -41,19 +123,19 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         // Validate that the `left_header` and `right_header` lengths match
         // `HEADER_SIZE`. Alternatively, the `Proof` structure could be
         // parameterized on the `HEADER_SIZE`, but this appeared to be simpler.
-        if pcd.proof.application.left_header.len() != HEADER_SIZE
-            || pcd.proof.application.right_header.len() != HEADER_SIZE
+        if proof.application.left_header.len() != HEADER_SIZE
+            || proof.application.right_header.len() != HEADER_SIZE
         {
             return Ok(false);
         }
 
         // Compute unified k(y), unified_bridge k(y), and application k(y).
         let (unified_ky, unified_bridge_ky, application_ky) =
-            Emulator::emulate_wireless((&pcd.proof, pcd.data.clone(), y), |dr, witness| {
-                let (proof, data, y) = witness.cast();
+            Emulator::emulate_wireless((proof, header, instance, y), |dr, witness| {
+                let (proof, header, instance, y) = witness.cast();
                 let y = Element::alloc(dr, y)?;
                 let proof_inputs =
-                    ProofInputs::<_, C, HEADER_SIZE>::alloc_for_verify::<R, H>(dr, proof, data)?;
+                    ProofInputs::<_, C, HEADER_SIZE>::alloc(dr, proof, header, instance)?;
 
                 let (unified_ky, unified_bridge_ky) = proof_inputs.unified_ky_values(dr, &y)?;
                 let unified_ky = *unified_ky.value().take();
@@ -64,14 +146,14 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
             })?;
 
         // Build a and b polynomials for each revdot claim.
-        let source = native::SingleProofSource { proof: &pcd.proof };
+        let source = native::SingleProofSource { proof };
         let mut builder = claims::Builder::new(&self.native_registry, y, z);
         claims::native::build(&source, &mut builder)?;
 
         // Check all native revdot claims.
         let native_revdot_claims = {
             let ky_source = native::SingleProofKySource {
-                raw_c: pcd.proof.ab.c,
+                raw_c: proof.ab.c,
                 application_ky,
                 unified_bridge_ky,
                 unified_ky,
@@ -84,7 +166,7 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
 
         // Check all nested revdot claims.
         let nested_revdot_claims = {
-            let nested_source = nested::SingleProofSource { proof: &pcd.proof };
+            let nested_source = nested::SingleProofSource { proof };
             let y_nested = C::ScalarField::random(&mut rng);
             let z_nested = C::ScalarField::random(&mut rng);
             let mut nested_builder =
@@ -98,27 +180,28 @@ impl<C: Cycle, R: Rank, const HEADER_SIZE: usize> Application<'_, C, R, HEADER_S
         };
 
         // Check polynomial evaluation claim.
-        let p_eval_claim = pcd.proof.p.poly.eval(pcd.proof.challenges.u) == pcd.proof.p.v;
+        let p_eval_claim = proof.p.poly.resident().eval(proof.challenges.u) == proof.p.v;
 
         // Check P commitment corresponds to polynomial and blind.
-        let p_commitment_claim = pcd
-            .proof
+        let p_commitment_claim = proof
             .p
             .poly
-            .commit(C::host_generators(self.params), pcd.proof.p.blind)
-            == pcd.proof.p.commitment;
+            .resident()
+            .commit(C::host_generators(self.params), proof.p.blind)
+            == proof.p.commitment;
 
         // Check registry_xy polynomial evaluation at the sampled w.
         // registry_xy_poly is m(W, x, y) - the registry evaluated at current x, y, free in W.
         let registry_xy_claim = {
-            let x = pcd.proof.challenges.x;
-            let y = pcd.proof.challenges.y;
-            let poly_eval = pcd.proof.query.registry_xy_poly.eval(w);
+            let x = proof.challenges.x;
+            let y = proof.challenges.y;
+            let poly_eval = proof.query.registry_xy_poly.resident().eval(w);
             let expected = self.native_registry.wxy(w, x, y);
             poly_eval == expected
         };
 
-        // TODO: Add checks for registry_wx0_poly, registry_wx1_poly, and registry_wy_poly.
+        // TODO: Add checks for the registry_wx0, registry_wx1, and registry_wy commitments.
+        // - their polynomials are not retained in the proof, only accumulated into p
         // - registry_wx0/wx1: need child proof x challenges (x₀, x₁) which "disappear" in preamble
         // - registry_wy: interstitial value that will be elided later
 
@@ -151,19 +234,19 @@ mod native {
         fn rx(&self, component: RxComponent) -> impl Iterator<Item = Self::Rx> {
             use RxComponent::*;
             let poly = match component {
-                AbA => &self.proof.ab.a_poly,
-                AbB => &self.proof.ab.b_poly,
-                Application => &self.proof.application.rx,
-                Hashes1 => &self.proof.circuits.hashes_1_rx,
-                Hashes2 => &self.proof.circuits.hashes_2_rx,
-                PartialCollapse => &self.proof.circuits.partial_collapse_rx,
-                FullCollapse => &self.proof.circuits.full_collapse_rx,
-                ComputeV => &self.proof.circuits.compute_v_rx,
-                Preamble => &self.proof.preamble.native_rx,
-                ErrorM => &self.proof.error_m.native_rx,
-                ErrorN => &self.proof.error_n.native_rx,
-                Query => &self.proof.query.native_rx,
-                Eval => &self.proof.eval.native_rx,
+                AbA => self.proof.ab.a_poly.resident(),
+                AbB => self.proof.ab.b_poly.resident(),
+                Application => self.proof.application.rx.resident(),
+                Hashes1 => self.proof.circuits.hashes_1_rx.resident(),
+                Hashes2 => self.proof.circuits.hashes_2_rx.resident(),
+                PartialCollapse => self.proof.circuits.partial_collapse_rx.resident(),
+                FullCollapse => self.proof.circuits.full_collapse_rx.resident(),
+                ComputeV => self.proof.circuits.compute_v_rx.resident(),
+                Preamble => self.proof.preamble.native_rx.resident(),
+                ErrorM => self.proof.error_m.native_rx.resident(),
+                ErrorN => self.proof.error_n.native_rx.resident(),
+                Query => self.proof.query.native_rx.resident(),
+                Eval => self.proof.eval.native_rx.resident(),
             };
             core::iter::once(poly)
         }
@@ -228,9 +311,9 @@ mod nested {
         fn rx(&self, component: RxComponent) -> impl Iterator<Item = Self::Rx> {
             use RxComponent::*;
             let poly = match component {
-                EndoscalarStage => &self.proof.p.endoscalar_rx,
-                PointsStage => &self.proof.p.points_rx,
-                EndoscalingStep(step) => &self.proof.p.step_rxs[step as usize], // TODO: bounds
+                EndoscalarStage => self.proof.p.endoscalar_rx.resident(),
+                PointsStage => self.proof.p.points_rx.resident(),
+                EndoscalingStep(step) => self.proof.p.step_rxs[step as usize].resident(), // TODO: bounds
             };
             core::iter::once(poly)
         }
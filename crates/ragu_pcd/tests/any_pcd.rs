@@ -0,0 +1,98 @@
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::polynomials::R;
+use ragu_core::{
+    Result,
+    drivers::{Driver, DriverValue},
+    gadgets::{GadgetKind, Kind},
+};
+use ragu_pasta::{Fp, Pasta};
+use ragu_pcd::{
+    AnyPcd, ApplicationBuilder,
+    header::{Header, Suffix},
+    test_fixtures::nontrivial::{Hash2, InternalNode, LeafNode, WitnessLeaf},
+};
+use ragu_primitives::Element;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// A header that is not registered with the application.
+struct Unregistered;
+
+impl<F: Field> Header<F> for Unregistered {
+    const SUFFIX: Suffix = Suffix::new(7);
+    type Data<'source> = F;
+    type Output = Kind![F; Element<'_, _>];
+
+    fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+        dr: &mut D,
+        witness: DriverValue<D, Self::Data<'source>>,
+    ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+        Element::alloc(dr, witness)
+    }
+}
+
+#[test]
+fn any_pcd_dispatch() -> Result<()> {
+    let pasta = Pasta::baked();
+    let poseidon_params = Pasta::circuit_poseidon(pasta);
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register(WitnessLeaf { poseidon_params })?
+        .register(Hash2 { poseidon_params })?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    let leaf1 = app.seed(&mut rng, WitnessLeaf { poseidon_params }, Fp::from(1u64))?;
+    let leaf1 = leaf1.0.carry::<LeafNode>(leaf1.1);
+    let leaf2 = app.seed(&mut rng, WitnessLeaf { poseidon_params }, Fp::from(2u64))?;
+    let leaf2 = leaf2.0.carry::<LeafNode>(leaf2.1);
+    let node = app.fuse(
+        &mut rng,
+        Hash2 { poseidon_params },
+        (),
+        leaf1.clone(),
+        leaf2,
+    )?;
+    let node = node.0.carry::<InternalNode>(node.1);
+
+    // A receiver only gets the proof, the suffix and the encoded header.
+    let received = [leaf1.clone().into_any()?, node.clone().into_any()?]
+        .map(|pcd| AnyPcd::new(pcd.proof.clone(), pcd.suffix(), pcd.header().to_vec()));
+    for pcd in &received {
+        assert!(app.verify_any(pcd, &mut rng)?);
+    }
+
+    let [leaf, node_any] = received;
+    assert_eq!(leaf.suffix(), <LeafNode as Header<Fp>>::SUFFIX);
+    assert!(leaf.is::<LeafNode>() && !leaf.is::<InternalNode>());
+
+    // Downcasting to the wrong header returns the proof-carrying data.
+    let leaf = leaf.downcast::<InternalNode>().err().unwrap();
+    let leaf = leaf.downcast::<LeafNode>().ok().unwrap();
+    assert_eq!(leaf.data, leaf1.data);
+    assert!(app.verify(&leaf, &mut rng)?);
+
+    let node_pcd = node_any.downcast::<InternalNode>().ok().unwrap();
+    assert_eq!(node_pcd.data, node.data);
+
+    // Headers that are not registered with the application do not verify, and
+    // headers without `Header::decode` cannot be downcast.
+    let unregistered = node
+        .proof
+        .clone()
+        .carry::<Unregistered>(node.data)
+        .into_any()?;
+    assert!(!app.verify_any(&unregistered, &mut rng)?);
+    assert!(unregistered.downcast::<Unregistered>().is_err());
+
+    // Neither do encodings of the wrong length for a registered suffix.
+    let padded = AnyPcd::new(
+        node.proof,
+        <InternalNode as Header<Fp>>::SUFFIX,
+        vec![node.data, Fp::ZERO],
+    );
+    assert!(!app.verify_any(&padded, &mut rng)?);
+    assert!(padded.downcast::<InternalNode>().is_err());
+
+    Ok(())
+}
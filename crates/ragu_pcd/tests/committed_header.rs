@@ -0,0 +1,186 @@
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::polynomials::R;
+use ragu_core::{
+    Result,
+    drivers::{Driver, DriverValue},
+    gadgets::{GadgetKind, Kind},
+    maybe::Maybe,
+};
+use ragu_pasta::{Fp, Pasta};
+use ragu_pcd::{
+    ApplicationBuilder,
+    header::{Committed, Header, Opening, Suffix},
+    step::{Encoded, Index, Step},
+};
+use ragu_primitives::{
+    Element,
+    vec::{ConstLen, FixedVec},
+};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+const BIG: usize = 8;
+
+/// A header much larger than the `HEADER_SIZE` of the application.
+struct Big;
+
+impl<F: Field> Header<F> for Big {
+    const SUFFIX: Suffix = Suffix::new(0);
+    type Data<'source> = [F; BIG];
+    type Output = Kind![F; FixedVec<Element<'_, _>, ConstLen<BIG>>];
+
+    fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+        dr: &mut D,
+        witness: DriverValue<D, Self::Data<'source>>,
+    ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+        let mut elements = Vec::with_capacity(BIG);
+        for i in 0..BIG {
+            elements.push(Element::alloc(dr, witness.view().map(|data| data[i]))?);
+        }
+
+        FixedVec::try_from(elements)
+    }
+}
+
+struct Total;
+
+impl<F: Field> Header<F> for Total {
+    const SUFFIX: Suffix = Suffix::new(1);
+    type Data<'source> = F;
+    type Output = Kind![F; Element<'_, _>];
+
+    fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+        dr: &mut D,
+        witness: DriverValue<D, Self::Data<'source>>,
+    ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+        Element::alloc(dr, witness)
+    }
+}
+
+struct Publish<'params, C: Cycle> {
+    poseidon_params: &'params C::CircuitPoseidon,
+}
+
+impl<C: Cycle> Step<C> for Publish<'_, C> {
+    const INDEX: Index = Index::new(0);
+    type Witness<'source> = [C::CircuitField; BIG];
+    type Aux<'source> = Opening<'source, C::CircuitField, Big>;
+    type Left = ();
+    type Right = ();
+    type Output = Committed<Big>;
+
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, Self::Witness<'source>>,
+        _: DriverValue<D, ()>,
+        _: DriverValue<D, ()>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let gadget = Big::encode(dr, witness.clone())?;
+        let output = Committed::<Big>::from_gadget(dr, self.poseidon_params, &gadget)?;
+        let aux = D::just(|| Opening {
+            data: witness.take(),
+            digest: *output.as_gadget().value().take(),
+        });
+
+        Ok((
+            (Encoded::from_gadget(()), Encoded::from_gadget(()), output),
+            aux,
+        ))
+    }
+}
+
+struct SumAll<'params, C: Cycle> {
+    poseidon_params: &'params C::CircuitPoseidon,
+}
+
+impl<C: Cycle> Step<C> for SumAll<'_, C> {
+    const INDEX: Index = Index::new(1);
+    type Witness<'source> = ();
+    type Aux<'source> = C::CircuitField;
+    type Left = Committed<Big>;
+    type Right = Committed<Big>;
+    type Output = Total;
+
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        _: DriverValue<D, ()>,
+        left: DriverValue<D, Opening<'source, C::CircuitField, Big>>,
+        right: DriverValue<D, Opening<'source, C::CircuitField, Big>>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let (left, left_data) = Committed::<Big>::open(dr, self.poseidon_params, left)?;
+        let (right, right_data) = Committed::<Big>::open(dr, self.poseidon_params, right)?;
+
+        let mut total = Element::zero(dr);
+        for element in left_data.iter().chain(right_data.iter()) {
+            total = total.add(dr, element);
+        }
+        let total_value = total.value().map(|v| *v);
+
+        Ok(((left, right, Encoded::from_gadget(total)), total_value))
+    }
+}
+
+#[test]
+fn committed_headers_exceed_header_size() -> Result<()> {
+    let pasta = Pasta::baked();
+    let poseidon_params = Pasta::circuit_poseidon(pasta);
+
+    // Each `Big` header has eight elements, but is committed to a single
+    // digest that fits (along with the suffix) in two elements.
+    let app = ApplicationBuilder::<Pasta, R<13>, 2>::new()
+        .register(Publish { poseidon_params })?
+        .register(SumAll { poseidon_params })?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    let data = |offset: u64| core::array::from_fn(|i| Fp::from(offset + i as u64));
+
+    let left = app.seed(&mut rng, Publish { poseidon_params }, data(0))?;
+    let left = left.0.carry::<Committed<Big>>(left.1);
+    assert_eq!(
+        left.data.digest,
+        Committed::<Big>::commit(poseidon_params, data(0))?.digest
+    );
+    assert!(app.verify(&left, &mut rng)?);
+
+    let right = app.seed(&mut rng, Publish { poseidon_params }, data(100))?;
+    let right = right.0.carry::<Committed<Big>>(right.1);
+
+    // A digest that does not match the proof is rejected.
+    let mut tampered = right.clone();
+    tampered.data = Committed::<Big>::commit(poseidon_params, data(1))?;
+    assert!(!app.verify(&tampered, &mut rng)?);
+
+    let total = app.fuse(&mut rng, SumAll { poseidon_params }, (), left, right)?;
+    let total = total.0.carry::<Total>(total.1);
+    assert_eq!(
+        total.data,
+        Fp::from((0..8).sum::<u64>() + (100..108).sum::<u64>())
+    );
+    assert!(app.verify(&total, &mut rng)?);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use arithmetic::Cycle;
+use ragu_circuits::polynomials::R;
+use ragu_core::Result;
+use ragu_pasta::{Fp, Pasta};
+use ragu_pcd::ApplicationBuilder;
+use ragu_pcd::header::{self, Header};
+use ragu_pcd::test_fixtures::nontrivial::{Hash2, InternalNode, LeafNode, WitnessLeaf};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+#[test]
+fn verify_against_encoded_header() -> Result<()> {
+    let pasta = Pasta::baked();
+    let poseidon_params = Pasta::circuit_poseidon(pasta);
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register(WitnessLeaf { poseidon_params })?
+        .register(Hash2 { poseidon_params })?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    let leaf1 = app.seed(&mut rng, WitnessLeaf { poseidon_params }, Fp::from(1u64))?;
+    let leaf1 = leaf1.0.carry::<LeafNode>(leaf1.1);
+    let leaf2 = app.seed(&mut rng, WitnessLeaf { poseidon_params }, Fp::from(2u64))?;
+    let leaf2 = leaf2.0.carry::<LeafNode>(leaf2.1);
+
+    let encoded = header::encode::<_, LeafNode>(leaf1.data)?;
+    assert_eq!(encoded, vec![leaf1.data]);
+    assert!(app.verify_encoded(
+        &leaf1.proof,
+        <LeafNode as Header<Fp>>::SUFFIX,
+        &encoded,
+        &mut rng
+    )?);
+
+    // Wrong elements, a wrong suffix, or extra trailing zeros do not verify,
+    // even though trailing zeros are indistinguishable from the padding.
+    assert!(!app.verify_encoded(
+        &leaf1.proof,
+        <LeafNode as Header<Fp>>::SUFFIX,
+        &[leaf2.data],
+        &mut rng
+    )?);
+    assert!(!app.verify_encoded(
+        &leaf1.proof,
+        <InternalNode as Header<Fp>>::SUFFIX,
+        &encoded,
+        &mut rng
+    )?);
+    assert!(!app.verify_encoded(
+        &leaf1.proof,
+        <LeafNode as Header<Fp>>::SUFFIX,
+        &[leaf1.data, Fp::from(0u64), Fp::from(0u64), Fp::from(0u64)],
+        &mut rng
+    )?);
+    assert!(!app.verify_encoded(
+        &leaf1.proof,
+        <LeafNode as Header<Fp>>::SUFFIX,
+        &[leaf1.data, Fp::from(0u64)],
+        &mut rng
+    )?);
+
+    let node = app.fuse(&mut rng, Hash2 { poseidon_params }, (), leaf1, leaf2)?;
+    let node = node.0.carry::<InternalNode>(node.1);
+    assert!(app.verify_encoded(
+        &node.proof,
+        <InternalNode as Header<Fp>>::SUFFIX,
+        &header::encode::<_, InternalNode>(node.data)?,
+        &mut rng
+    )?);
+
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::polynomials::R;
+use ragu_core::{
+    Result,
+    drivers::{Driver, DriverValue},
+    gadgets::{Gadget, GadgetKind, Kind},
+    maybe::Maybe,
+};
+use ragu_pasta::{Fp, Pasta};
+use ragu_pcd::{
+    ApplicationBuilder,
+    header::{Header, Suffix},
+    step::{Encoded, Index, Instances, Step},
+};
+use ragu_primitives::Element;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+struct Total;
+
+impl<F: Field> Header<F> for Total {
+    const SUFFIX: Suffix = Suffix::new(0);
+    type Data<'source> = F;
+    type Output = Kind![F; Element<'_, _>];
+
+    fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+        dr: &mut D,
+        witness: DriverValue<D, Self::Data<'source>>,
+    ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+        Element::alloc(dr, witness)
+    }
+}
+
+/// Outputs a private witness plus a public offset, supplied as the instance.
+struct Shift;
+
+impl<C: Cycle> Step<C> for Shift {
+    const INDEX: Index = Index::new(0);
+    type Witness<'source> = C::CircuitField;
+    type Aux<'source> = C::CircuitField;
+    type Left = ();
+    type Right = ();
+    type Output = Total;
+
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, Self::Witness<'source>>,
+        left: DriverValue<D, ()>,
+        right: DriverValue<D, ()>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let instances = Instances {
+            instance: Element::zero(dr),
+            left: Element::zero(dr),
+            right: Element::zero(dr),
+        };
+        Step::<C>::witness_with_instance::<_, HEADER_SIZE>(
+            self, dr, witness, &instances, left, right,
+        )
+    }
+
+    fn witness_with_instance<
+        'dr,
+        'source: 'dr,
+        D: Driver<'dr, F = C::CircuitField>,
+        const HEADER_SIZE: usize,
+    >(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, Self::Witness<'source>>,
+        instances: &Instances<'dr, D>,
+        _: DriverValue<D, ()>,
+        _: DriverValue<D, ()>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let output = Element::alloc(dr, witness)?.add(dr, &instances.instance);
+        let output_value = output.value().map(|v| *v);
+
+        Ok((
+            (
+                Encoded::from_gadget(()),
+                Encoded::from_gadget(()),
+                Encoded::from_gadget(output),
+            ),
+            output_value,
+        ))
+    }
+}
+
+struct Sum;
+
+impl<C: Cycle> Step<C> for Sum {
+    const INDEX: Index = Index::new(1);
+    type Witness<'source> = ();
+    type Aux<'source> = C::CircuitField;
+    type Left = Total;
+    type Right = Total;
+    type Output = Total;
+
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        _: DriverValue<D, ()>,
+        left: DriverValue<D, C::CircuitField>,
+        right: DriverValue<D, C::CircuitField>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let left = Encoded::new(dr, left)?;
+        let right = Encoded::new(dr, right)?;
+        let lhs: &Element<'dr, D> = left.as_gadget();
+        let output = lhs.add(dr, right.as_gadget());
+        let output_value = output.value().map(|v| *v);
+
+        Ok(((left, right, Encoded::from_gadget(output)), output_value))
+    }
+}
+
+/// Sums two totals that must have been created for the same instance as this
+/// step.
+struct SameInstanceSum;
+
+impl<C: Cycle> Step<C> for SameInstanceSum {
+    const INDEX: Index = Index::new(2);
+    type Witness<'source> = ();
+    type Aux<'source> = C::CircuitField;
+    type Left = Total;
+    type Right = Total;
+    type Output = Total;
+
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, ()>,
+        left: DriverValue<D, C::CircuitField>,
+        right: DriverValue<D, C::CircuitField>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        Step::<C>::witness::<_, HEADER_SIZE>(&Sum, dr, witness, left, right)
+    }
+
+    fn witness_with_instance<
+        'dr,
+        'source: 'dr,
+        D: Driver<'dr, F = C::CircuitField>,
+        const HEADER_SIZE: usize,
+    >(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, ()>,
+        instances: &Instances<'dr, D>,
+        left: DriverValue<D, C::CircuitField>,
+        right: DriverValue<D, C::CircuitField>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        instances.left.enforce_equal(dr, &instances.instance)?;
+        instances.right.enforce_equal(dr, &instances.instance)?;
+        Step::<C>::witness::<_, HEADER_SIZE>(&Sum, dr, witness, left, right)
+    }
+}
+
+#[test]
+fn instance_is_bound_to_proof() -> Result<()> {
+    let pasta = Pasta::baked();
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register(Shift)?
+        .register(Sum)?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    let left = app.seed_with_instance(&mut rng, Shift, Fp::from(3), Fp::from(10))?;
+    let left = left.0.carry::<Total>(left.1);
+    assert_eq!(left.data, Fp::from(13));
+    assert!(app.verify_with_instance(&left, Fp::from(10), &mut rng)?);
+
+    // The proof does not verify against any other instance.
+    assert!(!app.verify_with_instance(&left, Fp::from(11), &mut rng)?);
+    assert!(!app.verify(&left, &mut rng)?);
+
+    let right = app.seed_with_instance(&mut rng, Shift, Fp::from(5), Fp::from(20))?;
+    let right = right.0.carry::<Total>(right.1);
+
+    // A step that does not look at the instances of its children accepts
+    // any, and the parent has a zero instance.
+    let total = app.fuse(&mut rng, Sum, (), left.clone(), right.clone())?;
+    let total = total.0.carry::<Total>(total.1);
+    assert_eq!(total.data, Fp::from(38));
+    assert!(app.verify(&total, &mut rng)?);
+
+    // Rerandomization keeps the instance, and refuses any other.
+    assert!(app.rerandomize(right.clone(), &mut rng).is_err());
+    assert!(
+        app.rerandomize_with_instance(right.clone(), Fp::from(10), &mut rng)
+            .is_err()
+    );
+    let right = app.rerandomize_with_instance(right, Fp::from(20), &mut rng)?;
+    assert!(app.verify_with_instance(&right, Fp::from(20), &mut rng)?);
+    assert!(!app.verify(&right, &mut rng)?);
+
+    Ok(())
+}
+
+#[test]
+fn step_can_constrain_child_instances() -> Result<()> {
+    let pasta = Pasta::baked();
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register(Shift)?
+        .register(Sum)?
+        .register(SameInstanceSum)?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    let seed = |rng: &mut StdRng, witness: u64, instance: u64| -> Result<_> {
+        let (proof, aux) =
+            app.seed_with_instance(rng, Shift, Fp::from(witness), Fp::from(instance))?;
+        Ok(proof.carry::<Total>(aux))
+    };
+
+    let left = seed(&mut rng, 3, 10)?;
+    let right = seed(&mut rng, 5, 10)?;
+    let total = app.fuse_with_instance(&mut rng, SameInstanceSum, (), Fp::from(10), left, right)?;
+    let total = total.0.carry::<Total>(total.1);
+    assert_eq!(total.data, Fp::from(28));
+    assert!(app.verify_with_instance(&total, Fp::from(10), &mut rng)?);
+
+    // A child created for a different instance yields a proof that does not
+    // verify.
+    let left = seed(&mut rng, 3, 10)?;
+    let right = seed(&mut rng, 5, 20)?;
+    let total = app.fuse_with_instance(&mut rng, SameInstanceSum, (), Fp::from(10), left, right)?;
+    let total = total.0.carry::<Total>(total.1);
+    assert!(!app.verify_with_instance(&total, Fp::from(10), &mut rng)?);
+
+    Ok(())
+}
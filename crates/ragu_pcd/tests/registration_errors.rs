@@ -1,11 +1,11 @@
 use ff::Field;
-use ragu_circuits::polynomials::R;
+use ragu_circuits::polynomials::{R, Rank};
 use ragu_core::{
     Result,
     drivers::{Driver, DriverValue},
     gadgets::GadgetKind,
 };
-use ragu_pasta::Pasta;
+use ragu_pasta::{Pasta, PastaParams};
 use ragu_pcd::step::{Encoded, Index, Step};
 use ragu_pcd::{
     ApplicationBuilder,
@@ -176,3 +176,16 @@ fn register_steps_duplicate_suffix_should_fail() {
         .register(Step1Dup)
         .unwrap();
 }
+
+#[test]
+fn finalize_with_too_few_generators_should_fail() {
+    // One fewer power of two of generators than the rank needs, regardless of
+    // how many generators were baked in at build time.
+    let params = PastaParams::generate_with_k(R::<13>::RANK as usize - 1);
+    let result = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register(Step0)
+        .unwrap()
+        .finalize(&params);
+
+    assert!(matches!(result, Err(ragu_core::Error::Initialization(_))));
+}
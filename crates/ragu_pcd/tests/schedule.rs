@@ -0,0 +1,176 @@
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::polynomials::R;
+use ragu_core::{
+    Result,
+    drivers::{Driver, DriverValue},
+    gadgets::{GadgetKind, Kind},
+    maybe::Maybe,
+};
+use ragu_pasta::{Fp, Pasta};
+use ragu_pcd::{
+    ApplicationBuilder, Pcd,
+    header::{Header, Suffix},
+    schedule::{NodeId, Tree},
+    step::{Encoded, Index, Step},
+};
+use ragu_primitives::Element;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+struct Total;
+
+impl<F: Field> Header<F> for Total {
+    const SUFFIX: Suffix = Suffix::new(0);
+    type Data<'source> = F;
+    type Output = Kind![F; Element<'_, _>];
+
+    fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+        dr: &mut D,
+        witness: DriverValue<D, Self::Data<'source>>,
+    ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+        Element::alloc(dr, witness)
+    }
+}
+
+struct Value;
+
+impl<C: Cycle> Step<C> for Value {
+    const INDEX: Index = Index::new(0);
+    type Witness<'source> = C::CircuitField;
+    type Aux<'source> = C::CircuitField;
+    type Left = ();
+    type Right = ();
+    type Output = Total;
+
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        witness: DriverValue<D, Self::Witness<'source>>,
+        _: DriverValue<D, ()>,
+        _: DriverValue<D, ()>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let output = Encoded::new(dr, witness.clone())?;
+
+        Ok((
+            (Encoded::from_gadget(()), Encoded::from_gadget(()), output),
+            witness,
+        ))
+    }
+}
+
+struct Sum;
+
+impl<C: Cycle> Step<C> for Sum {
+    const INDEX: Index = Index::new(1);
+    type Witness<'source> = ();
+    type Aux<'source> = C::CircuitField;
+    type Left = Total;
+    type Right = Total;
+    type Output = Total;
+
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        _: DriverValue<D, ()>,
+        left: DriverValue<D, C::CircuitField>,
+        right: DriverValue<D, C::CircuitField>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )>
+    where
+        Self: 'dr,
+    {
+        let left = Encoded::new(dr, left)?;
+        let right = Encoded::new(dr, right)?;
+        let lhs: &Element<'dr, D> = left.as_gadget();
+        let output = lhs.add(dr, right.as_gadget());
+        let output_value = output.value().map(|v| *v);
+
+        Ok(((left, right, Encoded::from_gadget(output)), output_value))
+    }
+}
+
+type SumTree<'source> = Tree<'source, Pasta, R<13>, Total, Value, Sum>;
+
+fn leaves<'source>(values: &[u64]) -> Vec<SumTree<'source>> {
+    values
+        .iter()
+        .map(|&v| Tree::leaf(Value, Fp::from(v)))
+        .collect()
+}
+
+#[test]
+fn balanced_tree_shape() {
+    assert!(SumTree::balanced(leaves(&[]), || (Sum, ())).is_none());
+
+    // Five leaves pair up as ((0, 1), (2, 3)), 4.
+    let tree = SumTree::balanced(leaves(&[0, 1, 2, 3, 4]), || (Sum, ())).unwrap();
+    let Tree::Node { left, right, .. } = tree else {
+        panic!("expected a node");
+    };
+    assert!(matches!(*right, Tree::Leaf { .. }));
+    let Tree::Node { left, right, .. } = *left else {
+        panic!("expected a node");
+    };
+    assert!(matches!(*left, Tree::Node { .. }));
+    assert!(matches!(*right, Tree::Node { .. }));
+}
+
+#[test]
+fn fuse_tree_with_checkpoints() -> Result<()> {
+    let pasta = Pasta::baked();
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register(Value)?
+        .register(Sum)?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    let checkpoints = Mutex::new(BTreeMap::new());
+    let tree = SumTree::balanced(leaves(&[1, 2, 3]), || (Sum, ())).unwrap();
+    let root = app.fuse_tree(&mut rng, tree, |id: &NodeId, pcd: &Pcd<'_, _, _, Total>| {
+        checkpoints.lock().unwrap().insert(id.clone(), pcd.clone());
+    })?;
+    assert_eq!(root.data, Fp::from(6));
+    assert!(app.verify(&root, &mut rng)?);
+
+    // Every leaf and internal node was checkpointed.
+    let checkpoints = checkpoints.into_inner().unwrap();
+    assert_eq!(checkpoints.len(), 5);
+    assert_eq!(checkpoints[&NodeId::root().left()].data, Fp::from(3));
+
+    // Resuming from the left subtree only seeds the remaining leaf.
+    let fused = Mutex::new(vec![]);
+    let tree = SumTree::balanced(leaves(&[1, 2, 3]), || (Sum, ()))
+        .unwrap()
+        .resume(|id| (*id == NodeId::root().left()).then(|| checkpoints[id].clone()));
+    let root = app.fuse_tree(&mut rng, tree, |id: &NodeId, _: &Pcd<'_, _, _, Total>| {
+        fused.lock().unwrap().push(id.clone());
+    })?;
+    assert_eq!(root.data, Fp::from(6));
+    assert!(app.verify(&root, &mut rng)?);
+
+    let mut fused = fused.into_inner().unwrap();
+    fused.sort();
+    assert_eq!(fused, vec![NodeId::root(), NodeId::root().right()]);
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::polynomials::{R, Rank};
+use ragu_core::{
+    Result,
+    drivers::{Driver, DriverValue},
+    gadgets::{GadgetKind, Kind},
+    maybe::Maybe,
+};
+use ragu_pasta::{Fp, Pasta};
+use ragu_pcd::{
+    ApplicationBuilder,
+    header::{Header, Suffix},
+    step::{Encoded, Index, Step, StepSet},
+};
+use ragu_primitives::Element;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// A library of steps that sum values.
+mod sums {
+    use super::*;
+
+    pub struct Total;
+
+    impl<F: Field> Header<F> for Total {
+        const SUFFIX: Suffix = Suffix::new(0);
+        type Data<'source> = F;
+        type Output = Kind![F; Element<'_, _>];
+
+        fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+            dr: &mut D,
+            witness: DriverValue<D, Self::Data<'source>>,
+        ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+            Element::alloc(dr, witness)
+        }
+    }
+
+    pub struct Sums;
+
+    impl<C: Cycle> StepSet<C> for Sums {
+        fn register<'params, R: Rank, const HEADER_SIZE: usize>(
+            self,
+            builder: ApplicationBuilder<'params, C, R, HEADER_SIZE>,
+        ) -> Result<ApplicationBuilder<'params, C, R, HEADER_SIZE>>
+        where
+            Self: 'params,
+        {
+            builder.register(Value)?.register(Sum)
+        }
+    }
+
+    pub struct Value;
+
+    impl<C: Cycle> Step<C> for Value {
+        const INDEX: Index = Index::relative::<Sums>(0);
+        type Witness<'source> = C::CircuitField;
+        type Aux<'source> = C::CircuitField;
+        type Left = ();
+        type Right = ();
+        type Output = Total;
+
+        fn witness<
+            'dr,
+            'source: 'dr,
+            D: Driver<'dr, F = C::CircuitField>,
+            const HEADER_SIZE: usize,
+        >(
+            &self,
+            dr: &mut D,
+            witness: DriverValue<D, Self::Witness<'source>>,
+            _: DriverValue<D, ()>,
+            _: DriverValue<D, ()>,
+        ) -> Result<(
+            (
+                Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+                Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+                Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+            ),
+            DriverValue<D, Self::Aux<'source>>,
+        )>
+        where
+            Self: 'dr,
+        {
+            let output = Encoded::new(dr, witness.clone())?;
+
+            Ok((
+                (Encoded::from_gadget(()), Encoded::from_gadget(()), output),
+                witness,
+            ))
+        }
+    }
+
+    pub struct Sum;
+
+    impl<C: Cycle> Step<C> for Sum {
+        const INDEX: Index = Index::relative::<Sums>(1);
+        type Witness<'source> = ();
+        type Aux<'source> = C::CircuitField;
+        type Left = Total;
+        type Right = Total;
+        type Output = Total;
+
+        fn witness<
+            'dr,
+            'source: 'dr,
+            D: Driver<'dr, F = C::CircuitField>,
+            const HEADER_SIZE: usize,
+        >(
+            &self,
+            dr: &mut D,
+            _: DriverValue<D, ()>,
+            left: DriverValue<D, C::CircuitField>,
+            right: DriverValue<D, C::CircuitField>,
+        ) -> Result<(
+            (
+                Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+                Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+                Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+            ),
+            DriverValue<D, Self::Aux<'source>>,
+        )>
+        where
+            Self: 'dr,
+        {
+            let left = Encoded::new(dr, left)?;
+            let right = Encoded::new(dr, right)?;
+            let lhs: &Element<'dr, D> = left.as_gadget();
+            let output = lhs.add(dr, right.as_gadget());
+            let output_value = output.value().map(|v| *v);
+
+            Ok(((left, right, Encoded::from_gadget(output)), output_value))
+        }
+    }
+}
+
+/// An independently developed library of steps that squares values, using a
+/// header with the given suffix.
+mod squares {
+    use super::*;
+
+    pub struct Squared<const SUFFIX: usize>;
+
+    impl<F: Field, const SUFFIX: usize> Header<F> for Squared<SUFFIX> {
+        const SUFFIX: Suffix = Suffix::new(SUFFIX);
+        type Data<'source> = F;
+        type Output = Kind![F; Element<'_, _>];
+
+        fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+            dr: &mut D,
+            witness: DriverValue<D, Self::Data<'source>>,
+        ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+            Element::alloc(dr, witness)
+        }
+    }
+
+    pub struct Squares<const SUFFIX: usize>;
+
+    impl<C: Cycle, const SUFFIX: usize> StepSet<C> for Squares<SUFFIX> {
+        fn register<'params, R: Rank, const HEADER_SIZE: usize>(
+            self,
+            builder: ApplicationBuilder<'params, C, R, HEADER_SIZE>,
+        ) -> Result<ApplicationBuilder<'params, C, R, HEADER_SIZE>>
+        where
+            Self: 'params,
+        {
+            builder.register(Square::<SUFFIX>)
+        }
+    }
+
+    pub struct Square<const SUFFIX: usize>;
+
+    impl<C: Cycle, const SUFFIX: usize> Step<C> for Square<SUFFIX> {
+        const INDEX: Index = Index::relative::<Squares<SUFFIX>>(0);
+        type Witness<'source> = C::CircuitField;
+        type Aux<'source> = C::CircuitField;
+        type Left = ();
+        type Right = ();
+        type Output = Squared<SUFFIX>;
+
+        fn witness<
+            'dr,
+            'source: 'dr,
+            D: Driver<'dr, F = C::CircuitField>,
+            const HEADER_SIZE: usize,
+        >(
+            &self,
+            dr: &mut D,
+            witness: DriverValue<D, Self::Witness<'source>>,
+            _: DriverValue<D, ()>,
+            _: DriverValue<D, ()>,
+        ) -> Result<(
+            (
+                Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+                Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+                Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+            ),
+            DriverValue<D, Self::Aux<'source>>,
+        )>
+        where
+            Self: 'dr,
+        {
+            let value = Element::alloc(dr, witness)?;
+            let output = value.square(dr)?;
+            let output_value = output.value().map(|v| *v);
+
+            Ok((
+                (
+                    Encoded::from_gadget(()),
+                    Encoded::from_gadget(()),
+                    Encoded::from_gadget(output),
+                ),
+                output_value,
+            ))
+        }
+    }
+}
+
+#[test]
+fn composed_step_sets() -> Result<()> {
+    use squares::{Square, Squared, Squares};
+    use sums::{Sum, Sums, Total, Value};
+
+    let pasta = Pasta::baked();
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register_set(Squares::<1>)?
+        .register_set(Sums)?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+
+    let squared = app.seed(&mut rng, Square::<1>, Fp::from(3))?;
+    let squared = squared.0.carry::<Squared<1>>(squared.1);
+    assert_eq!(squared.data, Fp::from(9));
+    assert!(app.verify(&squared, &mut rng)?);
+
+    let left = app.seed(&mut rng, Value, Fp::from(2))?;
+    let left = left.0.carry::<Total>(left.1);
+    let right = app.seed(&mut rng, Value, Fp::from(5))?;
+    let right = right.0.carry::<Total>(right.1);
+    let total = app.fuse(&mut rng, Sum, (), left, right)?;
+    let total = total.0.carry::<Total>(total.1);
+    assert_eq!(total.data, Fp::from(7));
+    assert!(app.verify(&total, &mut rng)?);
+
+    Ok(())
+}
+
+#[test]
+fn step_set_registration_errors() -> Result<()> {
+    use squares::{Square, Squares};
+    use sums::{Sum, Sums};
+
+    // Sets that use the same suffix for different headers are rejected.
+    assert!(
+        ApplicationBuilder::<Pasta, R<13>, 4>::new()
+            .register_set(Sums)?
+            .register_set(Squares::<0>)
+            .is_err()
+    );
+
+    // Steps of a set must be registered in order.
+    assert!(
+        ApplicationBuilder::<Pasta, R<13>, 4>::new()
+            .register(Sum)
+            .is_err()
+    );
+
+    // Steps of a set must be registered consecutively.
+    assert!(
+        ApplicationBuilder::<Pasta, R<13>, 4>::new()
+            .register(sums::Value)?
+            .register(Square::<1>)?
+            .register(Sum)
+            .is_err()
+    );
+
+    // Steps from sets that were not registered cannot be used.
+    let pasta = Pasta::baked();
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register_set(Sums)?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+    assert!(app.seed(&mut rng, Square::<1>, Fp::from(3)).is_err());
+
+    Ok(())
+}
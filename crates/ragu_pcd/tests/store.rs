@@ -0,0 +1,235 @@
+use arithmetic::Cycle;
+use ff::Field;
+use ragu_circuits::polynomials::{R, Rank, structured, unstructured};
+use ragu_core::{
+    Error, Result,
+    drivers::{Driver, DriverValue},
+    gadgets::GadgetKind,
+};
+use ragu_pasta::{Fp, Fq, Pasta};
+use ragu_pcd::{
+    ApplicationBuilder, PolynomialStore, StoreKey,
+    header::{Header, Suffix},
+    step::{Encoded, Index, Step},
+};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+struct HeaderA;
+
+impl<F: Field> Header<F> for HeaderA {
+    const SUFFIX: Suffix = Suffix::new(0);
+    type Data<'source> = ();
+    type Output = ();
+    fn encode<'dr, 'source: 'dr, D: Driver<'dr, F = F>>(
+        _: &mut D,
+        _: DriverValue<D, Self::Data<'source>>,
+    ) -> Result<<Self::Output as GadgetKind<F>>::Rebind<'dr, D>> {
+        Ok(())
+    }
+}
+
+struct Step0;
+impl<C: Cycle> Step<C> for Step0 {
+    const INDEX: Index = Index::new(0);
+    type Witness<'source> = ();
+    type Aux<'source> = ();
+    type Left = ();
+    type Right = ();
+    type Output = HeaderA;
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        _: DriverValue<D, Self::Witness<'source>>,
+        left: DriverValue<D, ()>,
+        right: DriverValue<D, ()>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )> {
+        let left = Encoded::new(dr, left)?;
+        let right = Encoded::new(dr, right)?;
+        let output = Encoded::from_gadget(());
+        Ok(((left, right, output), D::just(|| ())))
+    }
+}
+
+struct Step1;
+impl<C: Cycle> Step<C> for Step1 {
+    const INDEX: Index = Index::new(1);
+    type Witness<'source> = ();
+    type Aux<'source> = ();
+    type Left = HeaderA;
+    type Right = HeaderA;
+    type Output = HeaderA;
+    fn witness<'dr, 'source: 'dr, D: Driver<'dr, F = C::CircuitField>, const HEADER_SIZE: usize>(
+        &self,
+        dr: &mut D,
+        _: DriverValue<D, Self::Witness<'source>>,
+        left: DriverValue<D, ()>,
+        right: DriverValue<D, ()>,
+    ) -> Result<(
+        (
+            Encoded<'dr, D, Self::Left, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Right, HEADER_SIZE>,
+            Encoded<'dr, D, Self::Output, HEADER_SIZE>,
+        ),
+        DriverValue<D, Self::Aux<'source>>,
+    )> {
+        let left = Encoded::new(dr, left)?;
+        let right = Encoded::new(dr, right)?;
+        let output = Encoded::from_gadget(());
+        Ok(((left, right, output), D::just(|| ())))
+    }
+}
+
+/// Keeps every written polynomial, standing in for a store on disk.
+struct VecStore<F: Field, R: Rank> {
+    structured: Vec<structured::Polynomial<F, R>>,
+    unstructured: Vec<unstructured::Polynomial<F, R>>,
+}
+
+impl<F: Field, R: Rank> VecStore<F, R> {
+    fn new() -> Self {
+        VecStore {
+            structured: Vec::new(),
+            unstructured: Vec::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.structured.len() + self.unstructured.len()
+    }
+}
+
+fn missing() -> Error {
+    Error::InvalidWitness("no polynomial under this key".into())
+}
+
+impl<F: Field, R: Rank> PolynomialStore<F, R> for VecStore<F, R> {
+    fn write_structured(&mut self, poly: &structured::Polynomial<F, R>) -> Result<StoreKey> {
+        self.structured.push(poly.clone());
+        Ok(StoreKey(self.structured.len() as u64 - 1))
+    }
+
+    fn read_structured(&self, key: StoreKey) -> Result<structured::Polynomial<F, R>> {
+        self.structured
+            .get(key.0 as usize)
+            .cloned()
+            .ok_or_else(missing)
+    }
+
+    fn write_unstructured(&mut self, poly: &unstructured::Polynomial<F, R>) -> Result<StoreKey> {
+        self.unstructured.push(poly.clone());
+        Ok(StoreKey(self.unstructured.len() as u64 - 1))
+    }
+
+    fn read_unstructured(&self, key: StoreKey) -> Result<unstructured::Polynomial<F, R>> {
+        self.unstructured
+            .get(key.0 as usize)
+            .cloned()
+            .ok_or_else(missing)
+    }
+}
+
+/// Stores the polynomials over both fields of the Pasta cycle.
+struct PastaStore<R: Rank> {
+    fp: VecStore<Fp, R>,
+    fq: VecStore<Fq, R>,
+}
+
+impl<R: Rank> PolynomialStore<Fp, R> for PastaStore<R> {
+    fn write_structured(&mut self, poly: &structured::Polynomial<Fp, R>) -> Result<StoreKey> {
+        self.fp.write_structured(poly)
+    }
+
+    fn read_structured(&self, key: StoreKey) -> Result<structured::Polynomial<Fp, R>> {
+        self.fp.read_structured(key)
+    }
+
+    fn write_unstructured(&mut self, poly: &unstructured::Polynomial<Fp, R>) -> Result<StoreKey> {
+        self.fp.write_unstructured(poly)
+    }
+
+    fn read_unstructured(&self, key: StoreKey) -> Result<unstructured::Polynomial<Fp, R>> {
+        self.fp.read_unstructured(key)
+    }
+}
+
+impl<R: Rank> PolynomialStore<Fq, R> for PastaStore<R> {
+    fn write_structured(&mut self, poly: &structured::Polynomial<Fq, R>) -> Result<StoreKey> {
+        self.fq.write_structured(poly)
+    }
+
+    fn read_structured(&self, key: StoreKey) -> Result<structured::Polynomial<Fq, R>> {
+        self.fq.read_structured(key)
+    }
+
+    fn write_unstructured(&mut self, poly: &unstructured::Polynomial<Fq, R>) -> Result<StoreKey> {
+        self.fq.write_unstructured(poly)
+    }
+
+    fn read_unstructured(&self, key: StoreKey) -> Result<unstructured::Polynomial<Fq, R>> {
+        self.fq.read_unstructured(key)
+    }
+}
+
+#[test]
+fn fuse_with_store() -> Result<()> {
+    let pasta = Pasta::baked();
+    let app = ApplicationBuilder::<Pasta, R<13>, 4>::new()
+        .register(Step0)?
+        .register(Step1)?
+        .finalize(pasta)?;
+    let mut rng = StdRng::seed_from_u64(1234);
+    let mut store = PastaStore {
+        fp: VecStore::new(),
+        fq: VecStore::new(),
+    };
+
+    // Spill a seeded proof, so that fusing it has to read it back.
+    let mut seeded = app.seed(&mut rng, Step0, ())?.0;
+    assert!(seeded.is_resident());
+    seeded.spill(&mut store)?;
+    assert!(!seeded.is_resident());
+    let spilled = store.fp.len() + store.fq.len();
+    assert!(spilled > 0);
+
+    // A spilled proof cannot be fused or verified without the store.
+    let seeded = seeded.carry::<HeaderA>(());
+    assert!(
+        app.fuse(&mut rng, Step1, (), seeded.clone(), seeded.clone())
+            .is_err()
+    );
+    assert!(app.verify(&seeded, &mut rng).is_err());
+
+    let (fused, ()) = app.fuse_with_store(
+        &mut store,
+        &mut rng,
+        Step1,
+        (),
+        Fp::ZERO,
+        seeded.clone(),
+        seeded,
+    )?;
+    assert!(!fused.is_resident());
+    assert_eq!(store.fp.len() + store.fq.len(), 2 * spilled);
+
+    let mut fused = fused.carry::<HeaderA>(());
+    assert!(app.verify(&fused, &mut rng).is_err());
+    fused.proof.load(&store)?;
+    assert!(fused.proof.is_resident());
+    assert!(app.verify(&fused, &mut rng)?);
+
+    // Loading the same keys again gives an identical proof.
+    let mut again = fused.proof.clone();
+    again.spill(&mut store)?;
+    again.load(&store)?;
+    assert!(app.verify(&again.carry::<HeaderA>(()), &mut rng)?);
+
+    Ok(())
+}
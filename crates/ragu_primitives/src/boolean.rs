@@ -55,6 +55,13 @@ impl<'dr, D: Driver<'dr>> Boolean<'dr, D> {
         Ok(Boolean { value, wire: c })
     }
 
+    /// Constructs a new boolean from a wire and a witness value. **It is the
+    /// caller's responsibility to ensure that the wire is constrained to be
+    /// zero or one, and that the witness value is consistent with it.**
+    pub(crate) fn promote(wire: D::Wire, value: DriverValue<D, bool>) -> Self {
+        Boolean { wire, value }
+    }
+
     /// Computes the NOT of this boolean. This is "free" in the circuit model.
     pub fn not(&self, dr: &mut D) -> Self {
         // The wire w is transformed into 1 - w, its logical NOT.
@@ -53,10 +53,12 @@ pub trait Buffer<'dr, D: Driver<'dr>> {
 /// Automatically derives the [`Write`] trait for gadgets that merely
 /// contain other gadgets.
 ///
-/// This only works for structs with named fields. Similar to the
+/// This works for structs with named fields. Similar to the
 /// [`Gadget`](derive@ragu_core::gadgets::Gadget) derive macro, the driver type
 /// can be annotated with `#[ragu(driver)]`. Fields with `#[ragu(skip)]` or
-/// `#[ragu(phantom)]` annotations are ignored.
+/// `#[ragu(phantom)]` annotations are ignored. Deriving this on an enum of
+/// [`Element`]s writes its [`Tagged`](crate::Tagged) unions as
+/// the tag followed by the padded payload.
 ///
 /// ## Example
 ///
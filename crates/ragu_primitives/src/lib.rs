@@ -19,10 +19,12 @@ mod element;
 mod endoscalar;
 mod foreign;
 pub mod io;
+mod optional;
 mod point;
 pub mod poseidon;
 pub mod promotion;
 mod simulator;
+pub mod tagged;
 mod util;
 pub mod vec;
 
@@ -34,8 +36,10 @@ use promotion::Demoted;
 pub use boolean::{Boolean, multipack};
 pub use element::{Element, multiadd};
 pub use endoscalar::{Endoscalar, compute_endoscalar, extract_endoscalar};
+pub use optional::Optional;
 pub use point::Point;
 pub use simulator::Simulator;
+pub use tagged::Tagged;
 
 /// Primitive extension trait for all gadgets.
 pub trait GadgetExt<'dr, D: Driver<'dr>>: Gadget<'dr, D> {
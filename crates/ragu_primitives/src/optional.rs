@@ -0,0 +1,173 @@
+//! Provides [`Optional`], a gadget that pairs a payload gadget with a
+//! [`Boolean`] flag indicating whether the payload is present.
+//!
+//! Gadgets cannot be `enum`s (including [`Option`]) because the active variant
+//! is instance state: drivers without witness data could not determine which
+//! variant to synthesize, and the number of wires would depend on it. Instead,
+//! an [`Optional`] always contains its payload's wires and stores the variant
+//! as a constrained [`Boolean`]. When the flag is false, the payload is still
+//! present but its assignment is meaningless, and is typically zero.
+//!
+//! Enums of [`Element`]s with several variants can instead be
+//! represented by [`Tagged`](crate::Tagged) unions.
+
+use ff::Field;
+use ragu_core::{
+    Result,
+    drivers::{Driver, DriverValue},
+    gadgets::{Consistent, Gadget, GadgetKind, Kind},
+    maybe::Maybe,
+};
+
+use crate::{
+    Boolean, Element, GadgetExt,
+    io::{Buffer, Write},
+};
+
+/// A gadget of kind `K` that may or may not be present, as determined by a
+/// [`Boolean`] flag.
+///
+/// The flag and the payload are both written (in that order) when the gadget
+/// is serialized, so every instance has the same encoding length regardless
+/// of whether the payload is present.
+#[derive(Gadget)]
+pub struct Optional<'dr, D: Driver<'dr>, K: GadgetKind<D::F>> {
+    #[ragu(gadget)]
+    is_some: Boolean<'dr, D>,
+    #[ragu(gadget)]
+    value: K::Rebind<'dr, D>,
+}
+
+impl<'dr, D: Driver<'dr>, K: GadgetKind<D::F>> Optional<'dr, D, K> {
+    /// Pairs a payload with a flag indicating whether it is present.
+    pub fn new(is_some: Boolean<'dr, D>, value: K::Rebind<'dr, D>) -> Self {
+        Optional { is_some, value }
+    }
+
+    /// Creates an [`Optional`] whose payload is (constantly) present. This is
+    /// "free" in the circuit model.
+    pub fn some(value: K::Rebind<'dr, D>) -> Self {
+        let is_some = Boolean::promote(D::ONE, D::just(|| true));
+        Optional { is_some, value }
+    }
+
+    /// Returns the flag indicating whether the payload is present.
+    pub fn is_some(&self) -> &Boolean<'dr, D> {
+        &self.is_some
+    }
+
+    /// Returns the payload, whose assignment is only meaningful if
+    /// [`is_some`](Self::is_some) is true.
+    pub fn value(&self) -> &K::Rebind<'dr, D> {
+        &self.value
+    }
+
+    /// Enforces that the payload is present, returning it.
+    ///
+    /// This costs one linear constraint.
+    pub fn unwrap(&self, dr: &mut D) -> Result<K::Rebind<'dr, D>> {
+        dr.enforce_equal(self.is_some.wire(), &D::ONE)?;
+        Ok(self.value.clone())
+    }
+}
+
+impl<'dr, D: Driver<'dr>> Optional<'dr, D, Kind![D::F; Element<'_, _>]> {
+    /// Returns the payload if it is present and `default` otherwise.
+    ///
+    /// This costs one multiplication constraint and two linear constraints.
+    pub fn unwrap_or(&self, dr: &mut D, default: &Element<'dr, D>) -> Result<Element<'dr, D>> {
+        self.is_some.conditional_select(dr, default, &self.value)
+    }
+
+    /// Allocates an [`Optional`] element from an optional witness value. The
+    /// payload is assigned zero when it is not present.
+    ///
+    /// This costs two multiplication constraints and five linear constraints.
+    pub fn alloc(dr: &mut D, value: DriverValue<D, Option<D::F>>) -> Result<Self> {
+        let is_some = Boolean::alloc(dr, value.view().map(|v| v.is_some()))?;
+        let value = Element::alloc(dr, value.map(|v| v.unwrap_or(D::F::ZERO)))?;
+
+        // Enforce that the payload is zero when it is not present:
+        // (1 - is_some) * value = 0
+        let is_none = is_some.not(dr);
+        is_none.element().mul(dr, &value)?.enforce_zero(dr)?;
+
+        Ok(Optional { is_some, value })
+    }
+}
+
+impl<F: Field, K: Write<F>> Write<F> for Kind![F; @Optional<'_, _, K>] {
+    fn write_gadget<'dr, D: Driver<'dr, F = F>, B: Buffer<'dr, D>>(
+        this: &Optional<'dr, D, K>,
+        dr: &mut D,
+        buf: &mut B,
+    ) -> Result<()> {
+        this.is_some.write(dr, buf)?;
+        K::write_gadget(&this.value, dr, buf)
+    }
+}
+
+impl<'dr, D: Driver<'dr>, K: GadgetKind<D::F>> Consistent<'dr, D> for Optional<'dr, D, K>
+where
+    K::Rebind<'dr, D>: Consistent<'dr, D>,
+{
+    fn enforce_consistent(&self, dr: &mut D) -> Result<()> {
+        self.is_some.enforce_consistent(dr)?;
+        self.value.enforce_consistent(dr)
+    }
+}
+
+#[test]
+fn test_optional_alloc() -> Result<()> {
+    type F = ragu_pasta::Fp;
+    type Simulator = crate::Simulator<F>;
+
+    for (witness, expected) in [(Some(F::from(7u64)), F::from(7u64)), (None, F::from(3u64))] {
+        let sim = Simulator::simulate(witness, |dr, witness| {
+            let optional = Optional::<'_, _, Kind![F; Element<'_, _>]>::alloc(dr, witness)?;
+            assert_eq!(optional.is_some().value().take(), expected == F::from(7u64));
+            assert_eq!(dr.num_multiplications(), 2);
+            assert_eq!(dr.num_linear_constraints(), 5);
+            dr.reset();
+
+            let default = Element::constant(dr, F::from(3u64));
+            let result = optional.unwrap_or(dr, &default)?;
+            assert_eq!(*result.value().take(), expected);
+
+            Ok(())
+        })?;
+
+        assert_eq!(sim.num_multiplications(), 1);
+        assert_eq!(sim.num_linear_constraints(), 2);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_optional_unwrap() -> Result<()> {
+    type F = ragu_pasta::Fp;
+    type Simulator = crate::Simulator<F>;
+
+    Simulator::simulate(F::from(5u64), |dr, witness| {
+        let value = Element::alloc(dr, witness)?;
+        let optional = Optional::<'_, _, Kind![F; Element<'_, _>]>::some(value);
+        let num_wires = optional.num_wires();
+        assert_eq!(*optional.unwrap(dr)?.value().take(), F::from(5u64));
+        assert_eq!(num_wires, 2);
+
+        Ok(())
+    })?;
+
+    assert!(
+        Simulator::simulate(None, |dr, witness| {
+            let optional = Optional::<'_, _, Kind![F; Element<'_, _>]>::alloc(dr, witness)?;
+            optional.unwrap(dr)?;
+
+            Ok(())
+        })
+        .is_err()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,487 @@
+//! Provides [`Tagged`], the gadget representation of enums whose variants
+//! consist of [`Element`]s.
+//!
+//! Gadgets must be fungible, so a gadget cannot be an `enum` whose active
+//! variant determines which wires it has. Instead, deriving
+//! [`Gadget`](derive@ragu_core::gadgets::Gadget) on such an enum makes the
+//! enum's _kind_ a [`GadgetKind`] whose gadgets are [`Tagged`] unions: a tag
+//! [`Element`] holding the index of the active variant, followed by the
+//! variant's elements padded with zeros to the size of the largest variant.
+//! Every union of the same kind has the same wires, and so
+//! [`Tagged::conditional_select`] can select between different variants
+//! based on a [`Boolean`] whose value is only known to the prover.
+//!
+//! The enum itself is a typed view of a single variant. It is used to
+//! construct a union with [`Tagged::new`] and to recover a variant with
+//! [`Tagged::enforce_variant`]. Since the enum is not a gadget, its kind is
+//! named directly as `Shape<'static, PhantomData<F>>` rather than with
+//! [`Kind!`](ragu_core::gadgets::Kind).
+//!
+//! ## Example
+//!
+//! ```rust
+//! # use core::marker::PhantomData;
+//! # use ragu_core::{Result, drivers::Driver, gadgets::{Consistent, Gadget}};
+//! # use ragu_primitives::{Boolean, Element, Tagged, io::Write};
+//! #[derive(Gadget, Write, Consistent)]
+//! enum Shape<'dr, D: Driver<'dr>> {
+//!     Circle { radius: Element<'dr, D> },
+//!     Rect { width: Element<'dr, D>, height: Element<'dr, D> },
+//!     Empty,
+//! }
+//!
+//! fn circle_or_square<'dr, D: Driver<'dr>>(
+//!     dr: &mut D,
+//!     is_square: &Boolean<'dr, D>,
+//!     size: &Element<'dr, D>,
+//! ) -> Result<Tagged<'dr, D, Shape<'static, PhantomData<D::F>>>> {
+//!     let circle = Tagged::new(dr, Shape::Circle { radius: size.clone() });
+//!     let square = Tagged::new(dr, Shape::Rect { width: size.clone(), height: size.clone() });
+//!
+//!     Tagged::conditional_select(dr, is_square, &circle, &square)
+//! }
+//! ```
+
+use ff::Field;
+use ragu_core::{
+    Result,
+    drivers::{Driver, FromDriver},
+    gadgets::{Consistent, Gadget, GadgetKind},
+};
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::{
+    Boolean, Element, GadgetExt,
+    io::Buffer,
+    vec::{CollectFixed, FixedVec, Len},
+};
+
+/// The kind of an enum whose variants consist of [`Element`]s, which is
+/// represented in circuits by a [`Tagged`] union.
+///
+/// This is implemented by deriving
+/// [`Gadget`](derive@ragu_core::gadgets::Gadget) on the enum. Variants are
+/// indexed in order of declaration.
+pub trait Variants<F: Field>: Send + Sync + 'static {
+    /// The number of elements in each variant, in order of declaration.
+    const LENGTHS: &'static [usize];
+
+    /// The number of elements in the largest variant, which is the size of the
+    /// payload of a [`Tagged`] union.
+    const PAYLOAD_LEN: usize = max(Self::LENGTHS);
+
+    /// The enum that this kind describes, bound to the driver `D`.
+    type View<'dr, D: Driver<'dr, F = F>>;
+
+    /// Passes the elements of `view` to `elements` in order, returning the
+    /// index of its variant.
+    fn into_parts<'dr, D: Driver<'dr, F = F>>(
+        view: Self::View<'dr, D>,
+        elements: &mut impl FnMut(Element<'dr, D>),
+    ) -> usize;
+
+    /// Constructs the variant with the provided `index` from the elements
+    /// yielded by `elements`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not the index of a variant, or if `elements`
+    /// yields fewer elements than the variant requires.
+    fn from_parts<'dr, D: Driver<'dr, F = F>>(
+        index: usize,
+        elements: &mut impl Iterator<Item = Element<'dr, D>>,
+    ) -> Self::View<'dr, D>;
+}
+
+/// Marker for [`Variants`] whose [`Tagged`] unions implement [`Consistent`].
+///
+/// This is implemented by deriving
+/// [`Consistent`](derive@ragu_core::gadgets::Consistent) on the enum.
+pub trait ConsistentVariants<F: Field>: Variants<F> {}
+
+const fn max(lengths: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < lengths.len() {
+        if lengths[i] > max {
+            max = lengths[i];
+        }
+        i += 1;
+    }
+    max
+}
+
+/// The length of the payload of a [`Tagged`] union of kind `K`.
+struct PayloadLen<F, K>(PhantomData<fn() -> (F, K)>);
+
+impl<F: Field, K: Variants<F>> Len for PayloadLen<F, K> {
+    fn len() -> usize {
+        K::PAYLOAD_LEN
+    }
+}
+
+/// A tagged union of the variants described by `K`, padded to the size of the
+/// largest variant. See the [module documentation](self) for details.
+///
+/// The union is written as its tag followed by its payload, so that every
+/// union of the same kind has an encoding of the same length.
+pub struct Tagged<'dr, D: Driver<'dr>, K: Variants<D::F>> {
+    tag: Element<'dr, D>,
+    payload: FixedVec<Element<'dr, D>, PayloadLen<D::F, K>>,
+}
+
+impl<'dr, D: Driver<'dr>, K: Variants<D::F>> Clone for Tagged<'dr, D, K> {
+    fn clone(&self) -> Self {
+        Tagged {
+            tag: self.tag.clone(),
+            payload: self.payload.clone(),
+        }
+    }
+}
+
+impl<'dr, D: Driver<'dr>, K: Variants<D::F>> Tagged<'dr, D, K> {
+    /// Creates a union holding the provided (constant) variant. This is
+    /// "free" in the circuit model.
+    pub fn new(dr: &mut D, variant: K::View<'dr, D>) -> Self {
+        let mut elements = Vec::with_capacity(K::PAYLOAD_LEN);
+        let index = K::into_parts(variant, &mut |element| elements.push(element));
+        elements.resize_with(K::PAYLOAD_LEN, || Element::zero(dr));
+
+        Tagged {
+            tag: Element::constant(dr, small(index)),
+            payload: FixedVec::new(elements).expect("no variant is larger than the payload"),
+        }
+    }
+
+    /// Returns the tag holding the index of the active variant.
+    pub fn tag(&self) -> &Element<'dr, D> {
+        &self.tag
+    }
+
+    /// Returns the elements of the active variant, followed by padding.
+    pub fn payload(&self) -> &[Element<'dr, D>] {
+        &self.payload
+    }
+
+    /// Selects between two unions (of possibly different variants) based on
+    /// `condition`, returning `a` when false and `b` when true.
+    ///
+    /// This costs one multiplication constraint per element of the union.
+    pub fn conditional_select(
+        dr: &mut D,
+        condition: &Boolean<'dr, D>,
+        a: &Self,
+        b: &Self,
+    ) -> Result<Self> {
+        Ok(Tagged {
+            tag: condition.conditional_select(dr, &a.tag, &b.tag)?,
+            payload: a
+                .payload
+                .iter()
+                .zip(b.payload.iter())
+                .map(|(a, b)| condition.conditional_select(dr, a, b))
+                .try_collect_fixed()?,
+        })
+    }
+
+    /// Enforces that this union holds the variant with the provided `index`
+    /// and that its padding is zero, returning the variant.
+    ///
+    /// This costs one linear constraint per element of the union that is not
+    /// part of the variant, plus one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not the index of a variant.
+    pub fn enforce_variant(&self, dr: &mut D, index: usize) -> Result<K::View<'dr, D>> {
+        let len = K::LENGTHS[index];
+
+        let expected = Element::constant(dr, small(index));
+        self.tag.enforce_equal(dr, &expected)?;
+        for padding in &self.payload[len..] {
+            padding.enforce_zero(dr)?;
+        }
+
+        Ok(K::from_parts(
+            index,
+            &mut self.payload[..len].iter().cloned(),
+        ))
+    }
+
+    /// Implementation of [`GadgetKind::map_gadget`] for derived [`Variants`].
+    #[doc(hidden)]
+    pub fn map_gadget<'new_dr, ND: FromDriver<'dr, 'new_dr, D>>(
+        &self,
+        ndr: &mut ND,
+    ) -> Result<Tagged<'new_dr, ND::NewDriver, K>> {
+        Ok(Tagged {
+            tag: self.tag.map(ndr)?,
+            payload: self
+                .payload
+                .iter()
+                .map(|e| e.map(ndr))
+                .try_collect_fixed()?,
+        })
+    }
+
+    /// Implementation of [`GadgetKind::enforce_equal_gadget`] for derived
+    /// [`Variants`].
+    #[doc(hidden)]
+    pub fn enforce_equal_gadget<D1: Driver<'dr, F = D::F, Wire = D::Wire>>(
+        dr: &mut D1,
+        a: &Self,
+        b: &Self,
+    ) -> Result<()> {
+        a.tag.enforce_equal(dr, &b.tag)?;
+        for (a, b) in a.payload.iter().zip(b.payload.iter()) {
+            a.enforce_equal(dr, b)?;
+        }
+        Ok(())
+    }
+
+    /// Implementation of [`Write::write_gadget`](crate::io::Write::write_gadget)
+    /// for derived [`Variants`].
+    #[doc(hidden)]
+    pub fn write_gadget<B: Buffer<'dr, D>>(&self, dr: &mut D, buf: &mut B) -> Result<()> {
+        self.tag.write(dr, buf)?;
+        for element in self.payload.iter() {
+            element.write(dr, buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'dr, D: Driver<'dr>, K> Gadget<'dr, D> for Tagged<'dr, D, K>
+where
+    K: Variants<D::F> + GadgetKind<D::F, Rebind<'dr, D> = Self>,
+{
+    type Kind = K;
+}
+
+/// Enforces that the tag is the index of a variant, and that the padding of
+/// the payload beyond the variant's elements is zero.
+///
+/// With $n$ variants this costs $n - 1$ multiplication constraints, plus one
+/// for each element of the payload that is padding in some variants but not
+/// in others.
+impl<'dr, D: Driver<'dr>, K> Consistent<'dr, D> for Tagged<'dr, D, K>
+where
+    K: ConsistentVariants<D::F> + GadgetKind<D::F, Rebind<'dr, D> = Self>,
+{
+    fn enforce_consistent(&self, dr: &mut D) -> Result<()> {
+        // Compute the Newton basis N_k(tag) = tag (tag - 1) ... (tag - k + 1)
+        // for k = 0, ..., n. Any polynomial of degree less than n in the tag is
+        // a linear combination of N_0, ..., N_{n - 1}, and N_n vanishes exactly
+        // on the valid tags 0, ..., n - 1.
+        let n = K::LENGTHS.len();
+        let mut basis = Vec::with_capacity(n + 1);
+        basis.push(Element::one());
+        basis.push(self.tag.clone());
+        for k in 1..n {
+            let shift = Element::constant(dr, small(k));
+            let shifted = self.tag.sub(dr, &shift);
+            let next = basis[k].mul(dr, &shifted)?;
+            basis.push(next);
+        }
+        basis[n].enforce_zero(dr)?;
+
+        // Each element of the payload must be zero when it is padding; this is
+        // enforced by multiplying it by the polynomial that is one on the tags
+        // of the variants it pads and zero on the others.
+        for (j, element) in self.payload.iter().enumerate() {
+            let is_padding = K::LENGTHS
+                .iter()
+                .map(|&len| if len <= j { D::F::ONE } else { D::F::ZERO })
+                .collect::<Vec<_>>();
+            if is_padding.iter().all(|v| v.is_zero_vartime()) {
+                continue;
+            }
+
+            let selector = newton_coefficients(&is_padding)
+                .into_iter()
+                .zip(basis.iter())
+                .fold(Element::zero(dr), |acc, (coeff, term)| {
+                    acc.add_coeff(dr, term, arithmetic::Coeff::Arbitrary(coeff))
+                });
+            selector.mul(dr, element)?.enforce_zero(dr)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the field element $n$.
+fn small<F: Field>(n: usize) -> F {
+    (0..usize::BITS).rev().fold(F::ZERO, |acc, i| {
+        acc.double() + if (n >> i) & 1 == 1 { F::ONE } else { F::ZERO }
+    })
+}
+
+/// Returns the coefficients in the Newton basis $N_k(X) = \prod_{i < k} (X -
+/// i)$ of the polynomial of degree less than $n$ taking the provided values at
+/// $0, \ldots, n - 1$.
+fn newton_coefficients<F: Field>(values: &[F]) -> Vec<F> {
+    let mut coeffs = Vec::with_capacity(values.len());
+    let mut differences = values.to_vec();
+    let mut factorial = F::ONE;
+    for k in 0..values.len() {
+        if k > 0 {
+            factorial *= small::<F>(k);
+        }
+        coeffs.push(differences[0] * factorial.invert().expect("k! is nonzero"));
+        differences = differences.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    coeffs
+}
+
+#[cfg(test)]
+mod tests {
+    use ff::Field;
+    use ragu_core::{
+        drivers::Driver,
+        gadgets::{Consistent, Gadget},
+        maybe::Maybe,
+    };
+    use ragu_pasta::Fp;
+
+    use alloc::vec::Vec;
+    use core::marker::PhantomData;
+
+    use super::{Tagged, newton_coefficients};
+    use crate::{Boolean, Element, GadgetExt, Simulator, io::Write};
+
+    #[derive(Gadget, Write, Consistent)]
+    enum Shape<'dr, D: Driver<'dr>> {
+        Circle { radius: Element<'dr, D> },
+        Rect(Element<'dr, D>, Element<'dr, D>),
+        Empty,
+    }
+
+    type ShapeKind = Shape<'static, PhantomData<Fp>>;
+
+    #[test]
+    fn test_newton_coefficients() {
+        let values = [3, 1, 4, 1, 5].map(Fp::from);
+        let coeffs = newton_coefficients(&values);
+        for (x, value) in values.iter().enumerate() {
+            let x = Fp::from(x as u64);
+            let (mut basis, mut eval) = (Fp::ONE, Fp::ZERO);
+            for (k, coeff) in coeffs.iter().enumerate() {
+                eval += *coeff * basis;
+                basis *= x - Fp::from(k as u64);
+            }
+            assert_eq!(eval, *value);
+        }
+    }
+
+    #[test]
+    fn test_tagged_encoding() -> ragu_core::Result<()> {
+        Simulator::simulate((), |dr, _| {
+            let two = Element::constant(dr, Fp::from(2));
+            let three = Element::constant(dr, Fp::from(3));
+
+            for (shape, expected) in [
+                (
+                    Shape::Circle {
+                        radius: two.clone(),
+                    },
+                    [0, 2, 0],
+                ),
+                (Shape::Rect(two.clone(), three.clone()), [1, 2, 3]),
+                (Shape::Empty, [2, 0, 0]),
+            ] {
+                let tagged = Tagged::<_, ShapeKind>::new(dr, shape);
+                assert_eq!(tagged.num_wires(), 3);
+
+                let mut buf = Vec::new();
+                tagged.write(dr, &mut buf)?;
+                let values = buf.iter().map(|e| *e.value().take()).collect::<Vec<_>>();
+                assert_eq!(values, expected.map(Fp::from));
+
+                tagged.enforce_consistent(dr)?;
+            }
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tagged_conditional_select() -> ragu_core::Result<()> {
+        for condition in [false, true] {
+            Simulator::simulate(condition, |dr, condition| {
+                let condition = Boolean::alloc(dr, condition)?;
+                let radius = Element::constant(dr, Fp::from(7));
+                let (width, height) = (Element::one(), Element::constant(dr, Fp::from(5)));
+
+                let circle = Tagged::<_, ShapeKind>::new(dr, Shape::Circle { radius });
+                let rect = Tagged::new(dr, Shape::Rect(width, height));
+                let selected = Tagged::conditional_select(dr, &condition, &circle, &rect)?;
+                selected.enforce_consistent(dr)?;
+
+                let expected = if condition.value().take() {
+                    &rect
+                } else {
+                    &circle
+                };
+                for (a, b) in selected.payload().iter().zip(expected.payload()) {
+                    assert_eq!(a.value().take(), b.value().take());
+                }
+
+                match selected.enforce_variant(dr, condition.value().take() as usize)? {
+                    Shape::Circle { radius } => assert_eq!(*radius.value().take(), Fp::from(7)),
+                    Shape::Rect(_, height) => assert_eq!(*height.value().take(), Fp::from(5)),
+                    Shape::Empty => unreachable!(),
+                }
+
+                Ok(())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tagged_inconsistent() {
+        // A circle whose padding is nonzero.
+        assert!(
+            Simulator::simulate((), |dr, _| {
+                let circle = Tagged::<_, ShapeKind>::new(
+                    dr,
+                    Shape::Circle {
+                        radius: Element::one(),
+                    },
+                );
+                let rect =
+                    Tagged::<_, ShapeKind>::new(dr, Shape::Rect(Element::one(), Element::one()));
+                let tampered = Tagged {
+                    tag: circle.tag,
+                    payload: rect.payload,
+                };
+                tampered.enforce_consistent(dr)
+            })
+            .is_err()
+        );
+
+        // A tag that is not the index of a variant.
+        assert!(
+            Simulator::simulate((), |dr, _| {
+                let circle = Tagged::<_, ShapeKind>::new(
+                    dr,
+                    Shape::Circle {
+                        radius: Element::one(),
+                    },
+                );
+                let tampered = Tagged {
+                    tag: Element::constant(dr, Fp::from(3)),
+                    payload: circle.payload.clone(),
+                };
+                tampered.enforce_consistent(dr)
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,871 @@
+//! The secp256k1 and secq256k1 curves, which both have the equation
+//! $y^2 = x^3 + 7$ and form a cycle: the base field of each is the scalar
+//! field of the other.
+
+use core::{
+    fmt,
+    iter::Sum,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use alloc::boxed::Box;
+
+use arithmetic::{Coordinates, CurveAffine, CurveExt};
+use ff::{Field, FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use group::{
+    Curve as _, Group as _, GroupEncoding,
+    prime::{PrimeCurve, PrimeCurveAffine, PrimeGroup},
+};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+use crate::{Fp, Fq};
+
+/// The compressed encoding of a point: a tag byte followed by the big-endian
+/// $x$-coordinate.
+///
+/// The tag is `0x02` or `0x03` for points with an even or odd $y$-coordinate,
+/// as in SEC 1. The identity is encoded as all zeroes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CompressedPoint(pub [u8; 33]);
+
+impl Default for CompressedPoint {
+    fn default() -> Self {
+        CompressedPoint([0; 33])
+    }
+}
+
+impl AsRef<[u8]> for CompressedPoint {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for CompressedPoint {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+macro_rules! new_curve_impl {
+    (
+        $(#[$attr:meta])*
+        $name:ident,
+        $name_affine:ident,
+        $base:ident,
+        $scalar:ident,
+        $curve_id:literal,
+        $generator_x:expr,
+        $generator_y:expr,
+    ) => {
+        $(#[$attr])*
+        ///
+        /// Points are represented in Jacobian coordinates.
+        #[derive(Copy, Clone, Debug)]
+        pub struct $name {
+            x: $base,
+            y: $base,
+            z: $base,
+        }
+
+        #[doc = concat!("A point of [`", stringify!($name), "`] in affine coordinates (or the point at infinity).")]
+        #[derive(Copy, Clone)]
+        pub struct $name_affine {
+            x: $base,
+            y: $base,
+        }
+
+        impl $name {
+            const fn curve_constant_b() -> $base {
+                $base::from_raw([7, 0, 0, 0])
+            }
+        }
+
+        impl fmt::Debug for $name_affine {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+                if self.is_identity().into() {
+                    write!(f, "Infinity")
+                } else {
+                    write!(f, "({:?}, {:?})", self.x, self.y)
+                }
+            }
+        }
+
+        impl group::Group for $name {
+            type Scalar = $scalar;
+
+            fn random(mut rng: impl RngCore) -> Self {
+                $name::generator() * $scalar::random(&mut rng)
+            }
+
+            fn identity() -> Self {
+                Self {
+                    x: $base::ZERO,
+                    y: $base::ZERO,
+                    z: $base::ZERO,
+                }
+            }
+
+            fn generator() -> Self {
+                $name_affine::generator().to_curve()
+            }
+
+            fn is_identity(&self) -> Choice {
+                self.z.is_zero()
+            }
+
+            fn double(&self) -> Self {
+                // http://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian-0.html#doubling-dbl-2009-l
+                //
+                // There are no points of order 2.
+
+                let a = self.x.square();
+                let b = self.y.square();
+                let c = b.square();
+                let d = self.x + b;
+                let d = d.square();
+                let d = d - a - c;
+                let d = d + d;
+                let e = a + a + a;
+                let f = e.square();
+                let z3 = self.z * self.y;
+                let z3 = z3 + z3;
+                let x3 = f - (d + d);
+                let c = c + c;
+                let c = c + c;
+                let c = c + c;
+                let y3 = e * (d - x3) - c;
+
+                let tmp = $name {
+                    x: x3,
+                    y: y3,
+                    z: z3,
+                };
+
+                $name::conditional_select(&tmp, &$name::identity(), self.is_identity())
+            }
+        }
+
+        impl CurveExt for $name {
+            type ScalarExt = $scalar;
+            type Base = $base;
+            type AffineExt = $name_affine;
+
+            const CURVE_ID: &'static str = $curve_id;
+
+            /// Apply the curve endomorphism by multiplying the x-coordinate
+            /// by an element of multiplicative order 3.
+            fn endo(&self) -> Self {
+                $name {
+                    x: self.x * $base::ZETA,
+                    y: self.y,
+                    z: self.z,
+                }
+            }
+
+            fn jacobian_coordinates(&self) -> ($base, $base, $base) {
+                (self.x, self.y, self.z)
+            }
+
+            /// Hashes to the curve by try-and-increment: the message is
+            /// hashed with a counter to a candidate $x$-coordinate until
+            /// $x^3 + 7$ is a square.
+            ///
+            /// This is not constant time, and it is only meant for deriving
+            /// public generators.
+            fn hash_to_curve<'a>(domain_prefix: &'a str) -> Box<dyn Fn(&[u8]) -> Self + 'a> {
+                Box::new(move |message| {
+                    for counter in 0u32.. {
+                        let digest = blake2b_simd::Params::new()
+                            .hash_length(64)
+                            .personal(b"ragu_secp_h2c__")
+                            .to_state()
+                            .update(&(domain_prefix.len() as u64).to_le_bytes())
+                            .update(domain_prefix.as_bytes())
+                            .update(&[0])
+                            .update($name::CURVE_ID.as_bytes())
+                            .update(&[0])
+                            .update(message)
+                            .update(&counter.to_le_bytes())
+                            .finalize();
+                        let x = $base::from_uniform_bytes(digest.as_array());
+                        let Some(y) = Option::<$base>::from((x.square() * x + $name::curve_constant_b()).sqrt()) else {
+                            continue;
+                        };
+                        // The parity of the y-coordinate is drawn from the
+                        // leading byte of the digest, which is otherwise
+                        // almost entirely absorbed into the reduction of x.
+                        let odd = Choice::from(digest.as_bytes()[63] & 1);
+                        let y = $base::conditional_select(&y, &-y, y.is_odd() ^ odd);
+                        return $name_affine { x, y }.to_curve();
+                    }
+                    unreachable!("a square is found well before the counter overflows")
+                })
+            }
+
+            fn is_on_curve(&self) -> Choice {
+                // Y^2 = X^3 + bZ^6
+                let z2 = self.z.square();
+                let z6 = z2.square() * z2;
+                (self.y.square() - self.x.square() * self.x).ct_eq(&(z6 * $name::curve_constant_b()))
+                    | self.z.is_zero()
+            }
+
+            fn a() -> Self::Base {
+                $base::ZERO
+            }
+
+            fn b() -> Self::Base {
+                $name::curve_constant_b()
+            }
+
+            fn new_jacobian(x: Self::Base, y: Self::Base, z: Self::Base) -> CtOption<Self> {
+                let p = $name { x, y, z };
+                CtOption::new(p, p.is_on_curve())
+            }
+        }
+
+        impl group::Curve for $name {
+            type AffineRepr = $name_affine;
+
+            fn batch_normalize(p: &[Self], q: &mut [Self::AffineRepr]) {
+                assert_eq!(p.len(), q.len());
+
+                let mut acc = $base::ONE;
+                for (p, q) in p.iter().zip(q.iter_mut()) {
+                    // We use the `x` field of the affine point to store the
+                    // product of previous z-coordinates seen.
+                    q.x = acc;
+
+                    // We will end up skipping all identities in p
+                    acc = $base::conditional_select(&(acc * p.z), &acc, p.is_identity());
+                }
+
+                // This is the inverse, as all z-coordinates are nonzero and
+                // the ones that are not are skipped.
+                acc = acc.invert().unwrap();
+
+                for (p, q) in p.iter().rev().zip(q.iter_mut().rev()) {
+                    let skip = p.is_identity();
+
+                    // Compute tmp = 1/z
+                    let tmp = q.x * acc;
+
+                    // Cancel out z-coordinate in denominator of `acc`
+                    acc = $base::conditional_select(&(acc * p.z), &acc, skip);
+
+                    // Set the coordinates to the correct value
+                    let tmp2 = tmp.square();
+                    let tmp3 = tmp2 * tmp;
+
+                    q.x = p.x * tmp2;
+                    q.y = p.y * tmp3;
+
+                    *q = $name_affine::conditional_select(q, &$name_affine::identity(), skip);
+                }
+            }
+
+            fn to_affine(&self) -> Self::AffineRepr {
+                let zinv = self.z.invert().unwrap_or($base::ZERO);
+                let zinv2 = zinv.square();
+                let x = self.x * zinv2;
+                let zinv3 = zinv2 * zinv;
+                let y = self.y * zinv3;
+
+                let tmp = $name_affine { x, y };
+
+                $name_affine::conditional_select(&tmp, &$name_affine::identity(), zinv.is_zero())
+            }
+        }
+
+        impl PrimeGroup for $name {}
+
+        impl PrimeCurve for $name {
+            type Affine = $name_affine;
+        }
+
+        impl GroupEncoding for $name {
+            type Repr = CompressedPoint;
+
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                $name_affine::from_bytes(bytes).map(Self::from)
+            }
+
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+                // We can't avoid curve checks when parsing a compressed encoding.
+                $name_affine::from_bytes(bytes).map(Self::from)
+            }
+
+            fn to_bytes(&self) -> Self::Repr {
+                $name_affine::from(self).to_bytes()
+            }
+        }
+
+        impl<'a> From<&'a $name_affine> for $name {
+            fn from(p: &'a $name_affine) -> $name {
+                p.to_curve()
+            }
+        }
+
+        impl From<$name_affine> for $name {
+            fn from(p: $name_affine) -> $name {
+                p.to_curve()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name::identity()
+            }
+        }
+
+        impl ConstantTimeEq for $name {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                // Is (xz^2, yz^3, z) equal to (x'z'^2, yz'^3, z') when
+                // converted to affine?
+
+                let z = other.z.square();
+                let x1 = self.x * z;
+                let z = z * other.z;
+                let y1 = self.y * z;
+                let z = self.z.square();
+                let x2 = other.x * z;
+                let z = z * self.z;
+                let y2 = other.y * z;
+
+                let self_is_zero = self.is_identity();
+                let other_is_zero = other.is_identity();
+
+                // Both points at infinity, or neither point at infinity and
+                // the coordinates are the same.
+                (self_is_zero & other_is_zero)
+                    | ((!self_is_zero) & (!other_is_zero) & x1.ct_eq(&x2) & y1.ct_eq(&y2))
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.ct_eq(other).into()
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl ConditionallySelectable for $name {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                $name {
+                    x: $base::conditional_select(&a.x, &b.x, choice),
+                    y: $base::conditional_select(&a.y, &b.y, choice),
+                    z: $base::conditional_select(&a.z, &b.z, choice),
+                }
+            }
+        }
+
+        impl Neg for &$name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name {
+                    x: self.x,
+                    y: -self.y,
+                    z: self.z,
+                }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                -&self
+            }
+        }
+
+        impl<T> Sum<T> for $name
+        where
+            T: core::borrow::Borrow<$name>,
+        {
+            fn sum<I>(iter: I) -> Self
+            where
+                I: Iterator<Item = T>,
+            {
+                iter.fold(Self::identity(), |acc, item| acc + item.borrow())
+            }
+        }
+
+        impl<'a> Add<&'a $name> for &$name {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name) -> $name {
+                if bool::from(self.is_identity()) {
+                    *rhs
+                } else if bool::from(rhs.is_identity()) {
+                    *self
+                } else {
+                    let z1z1 = self.z.square();
+                    let z2z2 = rhs.z.square();
+                    let u1 = self.x * z2z2;
+                    let u2 = rhs.x * z1z1;
+                    let s1 = self.y * z2z2 * rhs.z;
+                    let s2 = rhs.y * z1z1 * self.z;
+
+                    if u1 == u2 {
+                        if s1 == s2 {
+                            self.double()
+                        } else {
+                            $name::identity()
+                        }
+                    } else {
+                        let h = u2 - u1;
+                        let i = (h + h).square();
+                        let j = h * i;
+                        let r = s2 - s1;
+                        let r = r + r;
+                        let v = u1 * i;
+                        let x3 = r.square() - j - v - v;
+                        let s1 = s1 * j;
+                        let s1 = s1 + s1;
+                        let y3 = r * (v - x3) - s1;
+                        let z3 = (self.z + rhs.z).square() - z1z1 - z2z2;
+                        let z3 = z3 * h;
+
+                        $name { x: x3, y: y3, z: z3 }
+                    }
+                }
+            }
+        }
+
+        impl<'a> Add<&'a $name_affine> for &$name {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name_affine) -> $name {
+                if bool::from(self.is_identity()) {
+                    rhs.to_curve()
+                } else if bool::from(rhs.is_identity()) {
+                    *self
+                } else {
+                    let z1z1 = self.z.square();
+                    let u2 = rhs.x * z1z1;
+                    let s2 = rhs.y * z1z1 * self.z;
+
+                    if self.x == u2 {
+                        if self.y == s2 {
+                            self.double()
+                        } else {
+                            $name::identity()
+                        }
+                    } else {
+                        let h = u2 - self.x;
+                        let hh = h.square();
+                        let i = hh + hh;
+                        let i = i + i;
+                        let j = h * i;
+                        let r = s2 - self.y;
+                        let r = r + r;
+                        let v = self.x * i;
+                        let x3 = r.square() - j - v - v;
+                        let j = self.y * j;
+                        let j = j + j;
+                        let y3 = r * (v - x3) - j;
+                        let z3 = (self.z + h).square() - z1z1 - hh;
+
+                        $name { x: x3, y: y3, z: z3 }
+                    }
+                }
+            }
+        }
+
+        impl<'a> Sub<&'a $name> for &$name {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name) -> $name {
+                self + (-other)
+            }
+        }
+
+        impl<'a> Sub<&'a $name_affine> for &$name {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name_affine) -> $name {
+                self + (-other)
+            }
+        }
+
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        impl<'b> Mul<&'b $scalar> for &$name {
+            type Output = $name;
+
+            fn mul(self, other: &'b $scalar) -> Self::Output {
+                // This is a simple double-and-add implementation of point
+                // multiplication, moving from most significant to least
+                // significant bit of the scalar.
+                let mut acc = $name::identity();
+                for bit in other
+                    .to_repr()
+                    .iter()
+                    .rev()
+                    .flat_map(|byte| (0..8).rev().map(move |i| Choice::from((byte >> i) & 1u8)))
+                {
+                    acc = acc.double();
+                    acc = $name::conditional_select(&acc, &(acc + self), bit);
+                }
+
+                acc
+            }
+        }
+
+        impl Neg for &$name_affine {
+            type Output = $name_affine;
+
+            fn neg(self) -> $name_affine {
+                $name_affine {
+                    x: self.x,
+                    y: -self.y,
+                }
+            }
+        }
+
+        impl Neg for $name_affine {
+            type Output = $name_affine;
+
+            fn neg(self) -> $name_affine {
+                -&self
+            }
+        }
+
+        impl<'a> Add<&'a $name> for &$name_affine {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name) -> $name {
+                rhs + self
+            }
+        }
+
+        impl<'a> Add<&'a $name_affine> for &$name_affine {
+            type Output = $name;
+
+            fn add(self, rhs: &'a $name_affine) -> $name {
+                self.to_curve() + rhs
+            }
+        }
+
+        impl<'a> Sub<&'a $name_affine> for &$name_affine {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name_affine) -> $name {
+                self + (-other)
+            }
+        }
+
+        impl<'a> Sub<&'a $name> for &$name_affine {
+            type Output = $name;
+
+            fn sub(self, other: &'a $name) -> $name {
+                self + (-other)
+            }
+        }
+
+        impl<'b> Mul<&'b $scalar> for &$name_affine {
+            type Output = $name;
+
+            fn mul(self, other: &'b $scalar) -> Self::Output {
+                self.to_curve() * other
+            }
+        }
+
+        impl PrimeCurveAffine for $name_affine {
+            type Curve = $name;
+            type Scalar = $scalar;
+
+            fn identity() -> Self {
+                Self {
+                    x: $base::ZERO,
+                    y: $base::ZERO,
+                }
+            }
+
+            fn generator() -> Self {
+                Self {
+                    x: $base::from_raw($generator_x),
+                    y: $base::from_raw($generator_y),
+                }
+            }
+
+            fn is_identity(&self) -> Choice {
+                self.x.is_zero() & self.y.is_zero()
+            }
+
+            fn to_curve(&self) -> Self::Curve {
+                $name {
+                    x: self.x,
+                    y: self.y,
+                    z: $base::conditional_select(&$base::ONE, &$base::ZERO, self.is_identity()),
+                }
+            }
+        }
+
+        impl GroupEncoding for $name_affine {
+            type Repr = CompressedPoint;
+
+            fn from_bytes(bytes: &Self::Repr) -> CtOption<Self> {
+                let tag = bytes.0[0];
+                let mut repr = [0u8; 32];
+                for (dst, src) in repr.iter_mut().zip(bytes.0[1..].iter().rev()) {
+                    *dst = *src;
+                }
+
+                let is_identity = tag.ct_eq(&0) & repr.ct_eq(&[0; 32]);
+                let is_point = tag.ct_eq(&2) | tag.ct_eq(&3);
+                let odd = Choice::from(tag & 1);
+
+                $base::from_repr(repr).and_then(|x| {
+                    CtOption::new(Self::identity(), is_identity).or_else(|| {
+                        (x.square() * x + $name::curve_constant_b())
+                            .sqrt()
+                            .and_then(|y| {
+                                let y = $base::conditional_select(&y, &-y, y.is_odd() ^ odd);
+                                CtOption::new($name_affine { x, y }, is_point)
+                            })
+                    })
+                })
+            }
+
+            fn from_bytes_unchecked(bytes: &Self::Repr) -> CtOption<Self> {
+                // We can't avoid curve checks when parsing a compressed encoding.
+                Self::from_bytes(bytes)
+            }
+
+            fn to_bytes(&self) -> Self::Repr {
+                let mut bytes = [0u8; 33];
+                if !bool::from(self.is_identity()) {
+                    bytes[0] = 2 | self.y.is_odd().unwrap_u8();
+                    for (dst, src) in bytes[1..].iter_mut().zip(self.x.to_repr().iter().rev()) {
+                        *dst = *src;
+                    }
+                }
+                CompressedPoint(bytes)
+            }
+        }
+
+        impl CurveAffine for $name_affine {
+            type ScalarExt = $scalar;
+            type Base = $base;
+            type CurveExt = $name;
+
+            fn is_on_curve(&self) -> Choice {
+                // y^2 - x^3 ?= b
+                (self.y.square() - self.x.square() * self.x).ct_eq(&$name::curve_constant_b())
+                    | self.is_identity()
+            }
+
+            fn coordinates(&self) -> CtOption<Coordinates<Self>> {
+                Coordinates::from_xy(self.x, self.y)
+                    .and_then(|coordinates| CtOption::new(coordinates, !self.is_identity()))
+            }
+
+            fn from_xy(x: Self::Base, y: Self::Base) -> CtOption<Self> {
+                let p = $name_affine { x, y };
+                CtOption::new(p, p.is_on_curve())
+            }
+
+            fn a() -> Self::Base {
+                $base::ZERO
+            }
+
+            fn b() -> Self::Base {
+                $name::curve_constant_b()
+            }
+        }
+
+        impl Default for $name_affine {
+            fn default() -> $name_affine {
+                $name_affine::identity()
+            }
+        }
+
+        impl<'a> From<&'a $name> for $name_affine {
+            fn from(p: &'a $name) -> $name_affine {
+                p.to_affine()
+            }
+        }
+
+        impl From<$name> for $name_affine {
+            fn from(p: $name) -> $name_affine {
+                p.to_affine()
+            }
+        }
+
+        impl ConstantTimeEq for $name_affine {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.x.ct_eq(&other.x) & self.y.ct_eq(&other.y)
+            }
+        }
+
+        impl PartialEq for $name_affine {
+            fn eq(&self, other: &Self) -> bool {
+                self.ct_eq(other).into()
+            }
+        }
+
+        impl Eq for $name_affine {}
+
+        impl ConditionallySelectable for $name_affine {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                $name_affine {
+                    x: $base::conditional_select(&a.x, &b.x, choice),
+                    y: $base::conditional_select(&a.y, &b.y, choice),
+                }
+            }
+        }
+
+        impl_binops_additive!($name, $name);
+        impl_binops_additive!($name, $name_affine);
+        impl_binops_additive_specify_output!($name_affine, $name_affine, $name);
+        impl_binops_additive_specify_output!($name_affine, $name, $name);
+        impl_binops_multiplicative!($name, $scalar);
+        impl_binops_multiplicative_mixed!($name_affine, $scalar, $name);
+    };
+}
+
+new_curve_impl!(
+    /// A point of the secp256k1 curve over [`Fp`], whose group has prime
+    /// order $q$.
+    Secp256k1,
+    Secp256k1Affine,
+    Fp,
+    Fq,
+    "secp256k1",
+    [
+        0x59f2815b16f81798,
+        0x029bfcdb2dce28d9,
+        0x55a06295ce870b07,
+        0x79be667ef9dcbbac,
+    ],
+    [
+        0x9c47d08ffb10d4b8,
+        0xfd17b448a6855419,
+        0x5da4fbfc0e1108a8,
+        0x483ada7726a3c465,
+    ],
+);
+
+new_curve_impl!(
+    /// A point of the secq256k1 curve over [`Fq`], whose group has prime
+    /// order $p$.
+    ///
+    /// The generator is the point with $x = 1$ and the smaller of its two
+    /// $y$-coordinates.
+    Secq256k1,
+    Secq256k1Affine,
+    Fq,
+    Fp,
+    "secq256k1",
+    [1, 0, 0, 0],
+    [
+        0xc1b800d1ffc278c0,
+        0x48916189041f2af0,
+        0x4909abdf82c9bd02,
+        0x0c7c97045a207463,
+    ],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn check_curve<C: CurveExt>()
+    where
+        C::AffineExt: GroupEncoding,
+    {
+        let mut rng = StdRng::seed_from_u64(1234);
+        let g = C::generator();
+        assert!(bool::from(g.is_on_curve()));
+        assert!(bool::from(C::AffineExt::generator().is_on_curve()));
+
+        // The generator has the order of the scalar field.
+        assert!(bool::from((g * -C::ScalarExt::ONE + g).is_identity()));
+        assert!(!bool::from(g.is_identity()));
+
+        for _ in 0..20 {
+            let a = C::ScalarExt::random(&mut rng);
+            let b = C::ScalarExt::random(&mut rng);
+            let p = g * a;
+            let q = g * b;
+            assert!(bool::from(p.is_on_curve()));
+            assert_eq!(p + q, g * (a + b));
+            assert_eq!(p - q, g * (a - b));
+            assert_eq!(p.double(), p + p);
+            assert_eq!(p + p.to_affine(), p.double());
+            assert_eq!(p.to_affine() + q.to_affine(), p + q);
+            assert_eq!(p.to_affine() * b, g * (a * b));
+            assert!(bool::from((p + -p).is_identity()));
+
+            // The endomorphism multiplies by the scalar cube root of unity.
+            assert_eq!(p.endo(), p * C::ScalarExt::ZETA);
+
+            let encoded = p.to_affine().to_bytes();
+            assert_eq!(C::AffineExt::from_bytes(&encoded).unwrap(), p.to_affine());
+        }
+
+        let identity = C::AffineExt::identity();
+        assert_eq!(
+            C::AffineExt::from_bytes(&identity.to_bytes()).unwrap(),
+            identity
+        );
+        // As in `pasta_curves`, (0, 0) encodes the identity.
+        assert_eq!(
+            <C::AffineExt as CurveAffine>::from_xy(Field::ZERO, Field::ZERO).unwrap(),
+            identity
+        );
+        assert!(bool::from(
+            <C::AffineExt as CurveAffine>::from_xy(Field::ONE, Field::ONE).is_none()
+        ));
+
+        let points: Vec<C> = (0..8)
+            .map(|i| {
+                if i == 3 {
+                    C::identity()
+                } else {
+                    C::random(&mut rng)
+                }
+            })
+            .collect();
+        let mut affine = alloc::vec![C::AffineExt::identity(); points.len()];
+        C::batch_normalize(&points, &mut affine);
+        for (p, a) in points.iter().zip(affine.iter()) {
+            assert_eq!(p.to_affine(), *a);
+        }
+
+        let hasher = C::hash_to_curve("test");
+        let h = hasher(b"message");
+        assert!(bool::from(h.is_on_curve()));
+        assert_eq!(h, hasher(b"message"));
+        assert_ne!(h, hasher(b"other message"));
+        assert_ne!(h, C::hash_to_curve("other")(b"message"));
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        check_curve::<Secp256k1>();
+
+        // 2G, from the SEC 2 test vectors.
+        let two_g = Secp256k1::generator().double().to_affine();
+        let coordinates = two_g.coordinates().unwrap();
+        assert_eq!(
+            *coordinates.x(),
+            Fp::from_raw([
+                0xabac09b95c709ee5,
+                0x5c778e4b8cef3ca7,
+                0x3045406e95c07cd8,
+                0xc6047f9441ed7d6d,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_secq256k1() {
+        check_curve::<Secq256k1>();
+    }
+}
@@ -0,0 +1,668 @@
+//! The base field $\mathbb{F}_p$ and scalar field $\mathbb{F}_q$ of the
+//! secp256k1 curve, which are the scalar and base fields of secq256k1.
+//!
+//! Both moduli are close to $2^{256}$, so elements are stored as four 64-bit
+//! limbs in Montgomery form with $R = 2^{256}$, and every reduction accounts
+//! for the carry out of the top limb.
+
+use core::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use ff::{Field, FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use rand::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+/// Computes `a + b + carry`, returning the result and the new carry.
+#[inline(always)]
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + (b as u128) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+/// Computes `a - (b + borrow)`, returning the result and the new borrow (zero
+/// or one).
+#[inline(always)]
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let ret = (a as u128).wrapping_sub((b as u128) + (borrow as u128));
+    (ret as u64, (ret >> 127) as u64)
+}
+
+/// Computes `a + (b * c) + carry`, returning the result and the new carry.
+#[inline(always)]
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let ret = (a as u128) + ((b as u128) * (c as u128)) + (carry as u128);
+    (ret as u64, (ret >> 64) as u64)
+}
+
+macro_rules! field_impl {
+    (
+        $(#[$attr:meta])*
+        $field:ident,
+        modulus: $modulus:expr,
+        modulus_str: $modulus_str:literal,
+        inv: $inv:expr,
+        r: $r:expr,
+        r2: $r2:expr,
+        r3: $r3:expr,
+        generator: $generator:expr,
+        s: $s:expr,
+        root_of_unity: $root_of_unity:expr,
+        root_of_unity_inv: $root_of_unity_inv:expr,
+        two_inv: $two_inv:expr,
+        delta: $delta:expr,
+        zeta: $zeta:expr,
+        t_minus1_over2: $t_minus1_over2:expr,
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Eq)]
+        pub struct $field([u64; 4]);
+
+        impl $field {
+            /// The modulus, as little-endian limbs.
+            const MODULUS: [u64; 4] = $modulus;
+
+            /// $-p^{-1} \bmod 2^{64}$ for the modulus $p$.
+            const INV: u64 = $inv;
+
+            /// $2^{256} \bmod p$, the Montgomery form of one.
+            const R: [u64; 4] = $r;
+
+            /// $2^{512} \bmod p$.
+            const R2: [u64; 4] = $r2;
+
+            /// $2^{768} \bmod p$.
+            const R3: [u64; 4] = $r3;
+
+            /// $(t - 1) / 2$ for the odd part $t$ of $p - 1$.
+            const T_MINUS1_OVER2: [u64; 4] = $t_minus1_over2;
+
+            /// Converts from an integer, given as little-endian limbs, which
+            /// must be smaller than the modulus.
+            pub const fn from_raw(val: [u64; 4]) -> Self {
+                $field(Self::montgomery_mul(&val, &Self::R2))
+            }
+
+            /// Returns the canonical little-endian limbs of this element.
+            const fn to_raw(self) -> [u64; 4] {
+                Self::montgomery_mul(&self.0, &[1, 0, 0, 0])
+            }
+
+            /// Subtracts the modulus from `value + 2^256 * hi` if the result
+            /// is not negative, where the input is less than twice the
+            /// modulus.
+            #[inline(always)]
+            const fn reduce_once(value: [u64; 4], hi: u64) -> [u64; 4] {
+                let (d0, borrow) = sbb(value[0], Self::MODULUS[0], 0);
+                let (d1, borrow) = sbb(value[1], Self::MODULUS[1], borrow);
+                let (d2, borrow) = sbb(value[2], Self::MODULUS[2], borrow);
+                let (d3, borrow) = sbb(value[3], Self::MODULUS[3], borrow);
+
+                // Keep the difference when it did not underflow, or when the
+                // value exceeded 2^256.
+                let keep = (hi | (borrow ^ 1)).wrapping_neg();
+                [
+                    (d0 & keep) | (value[0] & !keep),
+                    (d1 & keep) | (value[1] & !keep),
+                    (d2 & keep) | (value[2] & !keep),
+                    (d3 & keep) | (value[3] & !keep),
+                ]
+            }
+
+            /// Computes $a b R^{-1} \bmod p$ for any $a < 2^{256}$ and $b < p$.
+            const fn montgomery_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+                let mut t = [0u64; 4];
+                let mut hi = 0u64;
+
+                let mut i = 0;
+                while i < 4 {
+                    // t += a * b[i]
+                    let mut carry = 0;
+                    let mut j = 0;
+                    while j < 4 {
+                        let (limb, c) = mac(t[j], a[j], b[i], carry);
+                        t[j] = limb;
+                        carry = c;
+                        j += 1;
+                    }
+                    let (limb, overflow) = adc(hi, carry, 0);
+                    hi = limb;
+
+                    // t = (t + m * p) / 2^64
+                    let m = t[0].wrapping_mul(Self::INV);
+                    let (_, mut carry) = mac(t[0], m, Self::MODULUS[0], 0);
+                    let mut j = 1;
+                    while j < 4 {
+                        let (limb, c) = mac(t[j], m, Self::MODULUS[j], carry);
+                        t[j - 1] = limb;
+                        carry = c;
+                        j += 1;
+                    }
+                    let (limb, c) = adc(hi, carry, 0);
+                    t[3] = limb;
+                    hi = overflow + c;
+
+                    i += 1;
+                }
+
+                Self::reduce_once(t, hi)
+            }
+
+            /// Converts from a 512-bit integer given as little-endian limbs,
+            /// reducing it modulo $p$.
+            const fn from_u512(limbs: [u64; 8]) -> Self {
+                let lo = Self::montgomery_mul(&[limbs[0], limbs[1], limbs[2], limbs[3]], &Self::R2);
+                let hi = Self::montgomery_mul(&[limbs[4], limbs[5], limbs[6], limbs[7]], &Self::R3);
+                $field::add(&$field(lo), &$field(hi))
+            }
+
+            /// Adds two elements.
+            #[inline]
+            pub const fn add(&self, rhs: &Self) -> Self {
+                let (d0, carry) = adc(self.0[0], rhs.0[0], 0);
+                let (d1, carry) = adc(self.0[1], rhs.0[1], carry);
+                let (d2, carry) = adc(self.0[2], rhs.0[2], carry);
+                let (d3, carry) = adc(self.0[3], rhs.0[3], carry);
+                $field(Self::reduce_once([d0, d1, d2, d3], carry))
+            }
+
+            /// Subtracts `rhs` from this element.
+            #[inline]
+            pub const fn sub(&self, rhs: &Self) -> Self {
+                let (d0, borrow) = sbb(self.0[0], rhs.0[0], 0);
+                let (d1, borrow) = sbb(self.0[1], rhs.0[1], borrow);
+                let (d2, borrow) = sbb(self.0[2], rhs.0[2], borrow);
+                let (d3, borrow) = sbb(self.0[3], rhs.0[3], borrow);
+
+                // Add the modulus back if the subtraction underflowed.
+                let mask = borrow.wrapping_neg();
+                let (d0, carry) = adc(d0, Self::MODULUS[0] & mask, 0);
+                let (d1, carry) = adc(d1, Self::MODULUS[1] & mask, carry);
+                let (d2, carry) = adc(d2, Self::MODULUS[2] & mask, carry);
+                let (d3, _) = adc(d3, Self::MODULUS[3] & mask, carry);
+                $field([d0, d1, d2, d3])
+            }
+
+            /// Negates this element.
+            #[inline]
+            pub const fn neg(&self) -> Self {
+                let (d0, borrow) = sbb(Self::MODULUS[0], self.0[0], 0);
+                let (d1, borrow) = sbb(Self::MODULUS[1], self.0[1], borrow);
+                let (d2, borrow) = sbb(Self::MODULUS[2], self.0[2], borrow);
+                let (d3, _) = sbb(Self::MODULUS[3], self.0[3], borrow);
+
+                // The negation of zero is zero, not the modulus.
+                let mask = (((self.0[0] | self.0[1] | self.0[2] | self.0[3]) == 0) as u64)
+                    .wrapping_sub(1);
+                $field([d0 & mask, d1 & mask, d2 & mask, d3 & mask])
+            }
+
+            /// Multiplies two elements.
+            #[inline]
+            pub const fn mul(&self, rhs: &Self) -> Self {
+                $field(Self::montgomery_mul(&self.0, &rhs.0))
+            }
+
+            /// Squares this element.
+            #[inline]
+            pub const fn square(&self) -> Self {
+                self.mul(self)
+            }
+        }
+
+        impl fmt::Debug for $field {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let tmp = self.to_repr();
+                write!(f, "0x")?;
+                for &b in tmp.iter().rev() {
+                    write!(f, "{:02x}", b)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl Default for $field {
+            fn default() -> Self {
+                Self::ZERO
+            }
+        }
+
+        impl From<u64> for $field {
+            fn from(val: u64) -> $field {
+                $field::from_raw([val, 0, 0, 0])
+            }
+        }
+
+        impl ConstantTimeEq for $field {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0[0].ct_eq(&other.0[0])
+                    & self.0[1].ct_eq(&other.0[1])
+                    & self.0[2].ct_eq(&other.0[2])
+                    & self.0[3].ct_eq(&other.0[3])
+            }
+        }
+
+        impl PartialEq for $field {
+            fn eq(&self, other: &Self) -> bool {
+                self.ct_eq(other).into()
+            }
+        }
+
+        impl Ord for $field {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                let left = self.to_raw();
+                let right = other.to_raw();
+                left.iter().rev().cmp(right.iter().rev())
+            }
+        }
+
+        impl PartialOrd for $field {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl ConditionallySelectable for $field {
+            fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                $field([
+                    u64::conditional_select(&a.0[0], &b.0[0], choice),
+                    u64::conditional_select(&a.0[1], &b.0[1], choice),
+                    u64::conditional_select(&a.0[2], &b.0[2], choice),
+                    u64::conditional_select(&a.0[3], &b.0[3], choice),
+                ])
+            }
+        }
+
+        impl Neg for &$field {
+            type Output = $field;
+
+            #[inline]
+            fn neg(self) -> $field {
+                $field::neg(self)
+            }
+        }
+
+        impl Neg for $field {
+            type Output = $field;
+
+            #[inline]
+            fn neg(self) -> $field {
+                -&self
+            }
+        }
+
+        impl<'b> Sub<&'b $field> for &$field {
+            type Output = $field;
+
+            #[inline]
+            fn sub(self, rhs: &'b $field) -> $field {
+                $field::sub(self, rhs)
+            }
+        }
+
+        impl<'b> Add<&'b $field> for &$field {
+            type Output = $field;
+
+            #[inline]
+            fn add(self, rhs: &'b $field) -> $field {
+                $field::add(self, rhs)
+            }
+        }
+
+        impl<'b> Mul<&'b $field> for &$field {
+            type Output = $field;
+
+            #[inline]
+            fn mul(self, rhs: &'b $field) -> $field {
+                $field::mul(self, rhs)
+            }
+        }
+
+        impl_binops_additive!($field, $field);
+        impl_binops_multiplicative!($field, $field);
+
+        impl<T: core::borrow::Borrow<$field>> core::iter::Sum<T> for $field {
+            fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, item| acc + item.borrow())
+            }
+        }
+
+        impl<T: core::borrow::Borrow<$field>> core::iter::Product<T> for $field {
+            fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+                iter.fold(Self::ONE, |acc, item| acc * item.borrow())
+            }
+        }
+
+        impl Field for $field {
+            const ZERO: Self = $field([0, 0, 0, 0]);
+            const ONE: Self = $field(Self::R);
+
+            fn random(mut rng: impl RngCore) -> Self {
+                let mut bytes = [0u8; 64];
+                rng.fill_bytes(&mut bytes);
+                Self::from_uniform_bytes(&bytes)
+            }
+
+            fn double(&self) -> Self {
+                self.add(self)
+            }
+
+            fn square(&self) -> Self {
+                $field::square(self)
+            }
+
+            fn invert(&self) -> CtOption<Self> {
+                // By Fermat's little theorem, a^{p - 2} = a^{-1}. The exponent
+                // is public, so a variable-time exponentiation leaks nothing
+                // about `self`.
+                let p_minus_2 = $field::sub(&$field(Self::MODULUS), &$field([2, 0, 0, 0])).0;
+                CtOption::new(self.pow_vartime(p_minus_2), !self.is_zero())
+            }
+
+            fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+                ff::helpers::sqrt_ratio_generic(num, div)
+            }
+
+            fn sqrt(&self) -> CtOption<Self> {
+                ff::helpers::sqrt_tonelli_shanks(self, Self::T_MINUS1_OVER2)
+            }
+        }
+
+        impl PrimeField for $field {
+            type Repr = [u8; 32];
+
+            const MODULUS: &'static str = $modulus_str;
+            const NUM_BITS: u32 = 256;
+            const CAPACITY: u32 = 255;
+            const TWO_INV: Self = $field::from_raw($two_inv);
+            const MULTIPLICATIVE_GENERATOR: Self = $field::from_raw([$generator, 0, 0, 0]);
+            const S: u32 = $s;
+            const ROOT_OF_UNITY: Self = $field::from_raw($root_of_unity);
+            const ROOT_OF_UNITY_INV: Self = $field::from_raw($root_of_unity_inv);
+            const DELTA: Self = $field::from_raw($delta);
+
+            fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+                let mut limbs = [0u64; 4];
+                for (limb, bytes) in limbs.iter_mut().zip(repr.chunks_exact(8)) {
+                    *limb = u64::from_le_bytes(bytes.try_into().unwrap());
+                }
+
+                // The representation is canonical if subtracting the modulus
+                // underflows.
+                let (_, borrow) = sbb(limbs[0], Self::MODULUS[0], 0);
+                let (_, borrow) = sbb(limbs[1], Self::MODULUS[1], borrow);
+                let (_, borrow) = sbb(limbs[2], Self::MODULUS[2], borrow);
+                let (_, borrow) = sbb(limbs[3], Self::MODULUS[3], borrow);
+
+                CtOption::new($field::from_raw(limbs), Choice::from(borrow as u8))
+            }
+
+            fn to_repr(&self) -> Self::Repr {
+                let mut repr = [0u8; 32];
+                for (bytes, limb) in repr.chunks_exact_mut(8).zip(self.to_raw()) {
+                    bytes.copy_from_slice(&limb.to_le_bytes());
+                }
+                repr
+            }
+
+            fn is_odd(&self) -> Choice {
+                Choice::from((self.to_raw()[0] & 1) as u8)
+            }
+        }
+
+        impl WithSmallOrderMulGroup<3> for $field {
+            const ZETA: Self = $field::from_raw($zeta);
+        }
+
+        impl FromUniformBytes<64> for $field {
+            /// Converts a 512-bit little endian integer into an element by
+            /// reducing it modulo the field modulus.
+            fn from_uniform_bytes(bytes: &[u8; 64]) -> Self {
+                let mut limbs = [0u64; 8];
+                for (limb, bytes) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+                    *limb = u64::from_le_bytes(bytes.try_into().unwrap());
+                }
+                Self::from_u512(limbs)
+            }
+        }
+    };
+}
+
+field_impl!(
+    /// An element of the base field of secp256k1, which is the scalar field
+    /// of secq256k1.
+    ///
+    /// The modulus is $p = 2^{256} - 2^{32} - 977$, which has 2-adicity 1.
+    Fp,
+    modulus: [
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ],
+    modulus_str: "0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    inv: 0xd838091dd2253531,
+    r: [0x00000001000003d1, 0, 0, 0],
+    r2: [0x000007a2000e90a1, 0x0000000000000001, 0, 0],
+    r3: [0x002bb1e33795f671, 0x0000000100000b73, 0, 0],
+    generator: 3,
+    s: 1,
+    root_of_unity: [
+        0xfffffffefffffc2e,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ],
+    root_of_unity_inv: [
+        0xfffffffefffffc2e,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ],
+    two_inv: [
+        0xffffffff7ffffe18,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ],
+    delta: [9, 0, 0, 0],
+    zeta: [
+        0xc1396c28719501ee,
+        0x9cf0497512f58995,
+        0x6e64479eac3434e9,
+        0x7ae96a2b657c0710,
+    ],
+    t_minus1_over2: [
+        0xffffffffbfffff0b,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x3fffffffffffffff,
+    ],
+);
+
+field_impl!(
+    /// An element of the scalar field of secp256k1, which is the base field
+    /// of secq256k1.
+    ///
+    /// The modulus $q$ is the order of the secp256k1 group, which has
+    /// 2-adicity 6.
+    Fq,
+    modulus: [
+        0xbfd25e8cd0364141,
+        0xbaaedce6af48a03b,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+    ],
+    modulus_str: "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    inv: 0x4b0dff665588b13f,
+    r: [0x402da1732fc9bebf, 0x4551231950b75fc4, 0x0000000000000001, 0],
+    r2: [
+        0x896cf21467d7d140,
+        0x741496c20e7cf878,
+        0xe697f5e45bcd07c6,
+        0x9d671cd581c69bc5,
+    ],
+    r3: [
+        0x7bc0cfe0e9ff41ed,
+        0x0017648444d4322c,
+        0xb1b31347f1d0b2da,
+        0x555d800c18ef116d,
+    ],
+    generator: 7,
+    s: 6,
+    root_of_unity: [
+        0x992f4b5402b052f2,
+        0x98bdeab680756045,
+        0xdf9879a3fbc483a8,
+        0x0c1dc060e7a91986,
+    ],
+    root_of_unity_inv: [
+        0xb6fb30a0884f0d1c,
+        0x77a275910aa413c3,
+        0xefc7b0c75b8cbb72,
+        0xfd3ae181f12d7096,
+    ],
+    two_inv: [
+        0xdfe92f46681b20a1,
+        0x5d576e7357a4501d,
+        0xffffffffffffffff,
+        0x7fffffffffffffff,
+    ],
+    delta: [
+        0x199417c8c0bb7601,
+        0xd63b78e780e1341e,
+        0x000cbc21fe4561c8,
+        0,
+    ],
+    zeta: [
+        0xdf02967c1b23bd72,
+        0x122e22ea20816678,
+        0xa5261c028812645a,
+        0x5363ad4cc05c30e0,
+    ],
+    t_minus1_over2: [
+        0x777fa4bd19a06c82,
+        0xfd755db9cd5e9140,
+        0xffffffffffffffff,
+        0x01ffffffffffffff,
+    ],
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Returns the modulus of `F` as little-endian limbs.
+    fn modulus<F: PrimeField<Repr = [u8; 32]>>() -> [u64; 4] {
+        let repr = (-F::ONE).to_repr();
+        let mut limbs = [0u64; 4];
+        for (limb, bytes) in limbs.iter_mut().zip(repr.chunks_exact(8)) {
+            *limb = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        limbs[0] += 1;
+        limbs
+    }
+
+    fn check_field<
+        F: PrimeField<Repr = [u8; 32]> + WithSmallOrderMulGroup<3> + FromUniformBytes<64> + Ord,
+    >(
+        modulus_str: &str,
+    ) {
+        let mut rng = StdRng::seed_from_u64(1234);
+
+        // The modulus string matches the modulus.
+        let limbs = modulus::<F>();
+        let mut expected = alloc::string::String::from("0x");
+        for limb in limbs.iter().rev() {
+            expected.push_str(&alloc::format!("{:016x}", limb));
+        }
+        assert_eq!(expected, modulus_str);
+        assert_eq!(F::MODULUS, modulus_str);
+
+        // Ring axioms against a second computation path.
+        for _ in 0..100 {
+            let a = F::random(&mut rng);
+            let b = F::random(&mut rng);
+            let c = F::random(&mut rng);
+            assert_eq!([a, b].iter().sum::<F>(), b + a);
+            assert_eq!((a + b) - b, a);
+            assert_eq!(a * (b + c), a * b + a * c);
+            assert_eq!(a + (-a), F::ZERO);
+            assert_eq!(a.double(), a + a);
+            assert_eq!(a.square(), a * a);
+            if !bool::from(a.is_zero()) {
+                assert_eq!(a * a.invert().unwrap(), F::ONE);
+            }
+            let square = a.square();
+            let root = square.sqrt().unwrap();
+            assert!(root == a || root == -a);
+            assert_eq!(F::from_repr(a.to_repr()).unwrap(), a);
+        }
+        assert!(bool::from(F::ZERO.invert().is_none()));
+        assert_eq!(-F::ZERO, F::ZERO);
+
+        // Values near the modulus.
+        let minus_one = -F::ONE;
+        assert_eq!(minus_one + F::ONE, F::ZERO);
+        assert_eq!(minus_one * minus_one, F::ONE);
+        assert_eq!(
+            minus_one.square().sqrt().map(|r| r.square()).unwrap(),
+            F::ONE
+        );
+        let mut repr = minus_one.to_repr();
+        repr[0] += 1;
+        assert!(bool::from(F::from_repr(repr).is_none()));
+        assert!(bool::from(F::from_repr([0xff; 32]).is_none()));
+
+        // 2^512 - 1 reduces to (2^256 - 1) * (2^256 + 1).
+        let max = F::from_uniform_bytes(&[0xff; 64]);
+        let two_256_minus_1 = F::from(2).pow_vartime([256]) - F::ONE;
+        assert_eq!(max, two_256_minus_1 * (two_256_minus_1 + F::from(2)));
+
+        // Constants.
+        assert_eq!(F::TWO_INV * F::from(2), F::ONE);
+        assert_eq!(F::ROOT_OF_UNITY.pow_vartime([1 << F::S]), F::ONE);
+        assert_ne!(F::ROOT_OF_UNITY.pow_vartime([1 << (F::S - 1)]), F::ONE);
+        assert_eq!(F::ROOT_OF_UNITY * F::ROOT_OF_UNITY_INV, F::ONE);
+        assert_eq!(
+            F::DELTA,
+            F::MULTIPLICATIVE_GENERATOR.pow_vartime([1 << F::S])
+        );
+        assert_ne!(F::ZETA, F::ONE);
+        assert_eq!(F::ZETA.cube(), F::ONE);
+
+        // The multiplicative generator is a quadratic non-residue, and its odd
+        // part generates the 2-Sylow subgroup.
+        assert!(bool::from(F::MULTIPLICATIVE_GENERATOR.sqrt().is_none()));
+        let mut t = limbs;
+        t[0] -= 1;
+        let t = {
+            // (p - 1) >> S
+            let mut shifted = [0u64; 4];
+            for i in 0..4 {
+                shifted[i] = t[i] >> F::S;
+                if i < 3 {
+                    shifted[i] |= t[i + 1] << (64 - F::S);
+                }
+            }
+            shifted
+        };
+        assert_eq!(F::MULTIPLICATIVE_GENERATOR.pow_vartime(t), F::ROOT_OF_UNITY);
+
+        // Ordering is by canonical value.
+        assert!(F::ZERO < F::ONE);
+        assert!(F::ONE < minus_one);
+    }
+
+    #[test]
+    fn test_fp() {
+        check_field::<Fp>("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+    }
+
+    #[test]
+    fn test_fq() {
+        check_field::<Fq>("0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+    }
+}
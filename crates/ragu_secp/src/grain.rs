@@ -0,0 +1,153 @@
+//! Regenerates the Poseidon parameters with the Grain LFSR of the Poseidon
+//! reference implementation (`generate_parameters_grain.sage`).
+//!
+//! The generator is checked against the Pasta tables in `ragu_pasta`, which
+//! were produced by the reference script, and then used to check the tables in
+//! this crate.
+
+use alloc::{vec, vec::Vec};
+
+use arithmetic::PoseidonPermutation;
+use ff::{FromUniformBytes, PrimeField};
+
+const T: usize = 5;
+const FULL_ROUNDS: usize = 8;
+const PARTIAL_ROUNDS: usize = 56;
+
+/// The self-shrinking Grain LFSR, seeded for a prime field (field type 1) and
+/// the $x^\alpha$ S-box (S-box type 0).
+struct Grain {
+    state: Vec<bool>,
+}
+
+impl Grain {
+    fn new(num_bits: usize) -> Self {
+        let mut state = Vec::with_capacity(80);
+        let mut push = |value: usize, width: usize| {
+            state.extend((0..width).rev().map(|i| (value >> i) & 1 == 1));
+        };
+        push(1, 2);
+        push(0, 4);
+        push(num_bits, 12);
+        push(T, 12);
+        push(FULL_ROUNDS, 10);
+        push(PARTIAL_ROUNDS, 10);
+        state.extend([true; 30]);
+
+        let mut grain = Grain { state };
+        for _ in 0..160 {
+            grain.step();
+        }
+        grain
+    }
+
+    fn step(&mut self) -> bool {
+        let s = &self.state;
+        let bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+        self.state.remove(0);
+        self.state.push(bit);
+        bit
+    }
+
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.step();
+            let bit = self.step();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// Draws `num_bits` bits as a big-endian integer, returned as 64
+    /// little-endian bytes.
+    fn next_integer(&mut self, num_bits: usize) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        for i in (0..num_bits).rev() {
+            if self.next_bit() {
+                bytes[i / 8] |= 1 << (i % 8);
+            }
+        }
+        bytes
+    }
+}
+
+/// Returns the round constants and MDS matrix generated for `F`.
+fn generate<F: PrimeField + FromUniformBytes<64>>() -> (Vec<Vec<F>>, Vec<Vec<F>>) {
+    let num_bits = F::NUM_BITS as usize;
+    let mut grain = Grain::new(num_bits);
+
+    // Round constants are sampled by rejection.
+    let mut constants = Vec::with_capacity(T * (FULL_ROUNDS + PARTIAL_ROUNDS));
+    while constants.len() < constants.capacity() {
+        let bytes = grain.next_integer(num_bits);
+        let mut repr = F::Repr::default();
+        let len = repr.as_ref().len();
+        repr.as_mut().copy_from_slice(&bytes[..len]);
+        if let Some(value) = Option::<F>::from(F::from_repr(repr)) {
+            constants.push(value);
+        }
+    }
+    let round_constants = constants.chunks(T).map(|round| round.to_vec()).collect();
+
+    // The MDS matrix is a Cauchy matrix, with its points reduced modulo p.
+    loop {
+        let points: Vec<F> = loop {
+            let points: Vec<F> = (0..2 * T)
+                .map(|_| F::from_uniform_bytes(&grain.next_integer(num_bits)))
+                .collect();
+            if points
+                .iter()
+                .enumerate()
+                .all(|(i, a)| points[..i].iter().all(|b| a != b))
+            {
+                break points;
+            }
+        };
+        let (xs, ys) = points.split_at(T);
+        if xs
+            .iter()
+            .any(|x| ys.iter().any(|y| bool::from((*x + y).is_zero())))
+        {
+            continue;
+        }
+
+        let mut mds = vec![vec![F::ZERO; T]; T];
+        for (row, x) in mds.iter_mut().zip(xs) {
+            for (entry, y) in row.iter_mut().zip(ys) {
+                *entry = (*x + y).invert().unwrap();
+            }
+        }
+        return (round_constants, mds);
+    }
+}
+
+fn check_tables<F, P>(permutation: &P)
+where
+    F: PrimeField + FromUniformBytes<64>,
+    P: PoseidonPermutation<F>,
+{
+    assert_eq!(P::T, T);
+    assert_eq!(P::FULL_ROUNDS, FULL_ROUNDS);
+    assert_eq!(P::PARTIAL_ROUNDS, PARTIAL_ROUNDS);
+
+    let (round_constants, mds) = generate::<F>();
+    assert!(
+        permutation
+            .round_constants()
+            .eq(round_constants.iter().map(Vec::as_slice))
+    );
+    assert!(permutation.mds_matrix().eq(mds.iter().map(Vec::as_slice)));
+}
+
+#[test]
+fn test_reproduces_pasta() {
+    check_tables::<ragu_pasta::Fp, _>(&ragu_pasta::PoseidonFp);
+    check_tables::<ragu_pasta::Fq, _>(&ragu_pasta::PoseidonFq);
+}
+
+#[test]
+fn test_secp_tables() {
+    check_tables::<crate::Fp, _>(&crate::PoseidonFp);
+    check_tables::<crate::Fq, _>(&crate::PoseidonFq);
+}
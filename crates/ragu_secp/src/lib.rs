@@ -0,0 +1,210 @@
+//! # `ragu_secp`
+//!
+//! This crate provides [`Secp`], an implementation of the [`Cycle`] trait,
+//! which stores the public parameters and constants used in Ragu for the fields
+//! and curves associated with the
+//! [sec**p**256k1](https://en.bitcoin.it/wiki/Secp256k1)/sec**q**256k1 cycle.
+//!
+//! Both curves have the equation $y^2 = x^3 + 7$. The base field
+//! $\mathbb{F}_p$ of secp256k1 is the scalar field of secq256k1 and vice versa,
+//! so circuits are expressed over [`Fp`], whose commitments live on
+//! [`Secq256k1`], while the nested curve [`Secp256k1`] is defined over it.
+//!
+//! ## 2-adicity
+//!
+//! $p - 1$ and $q - 1$ are divisible only by $2$ and $2^6$ respectively, so
+//! the radix-2 evaluation domains used by the Pasta curves are tiny here.
+//! Registries over these fields place circuits on cosets of the largest
+//! radix-2 subgroup (see [`CosetDomain`](arithmetic::CosetDomain)), and
+//! polynomial arithmetic that needs larger FFTs is not available.
+//!
+//! ## Parameter Initialization
+//!
+//! Parameters are derived with [`Secp::generate`], or with a chosen number of
+//! generators by [`SecpParams::generate_with_k`]. The generators are obtained
+//! by a try-and-increment hash to the curve, which is not constant time but
+//! only ever hashes public inputs.
+//!
+//! ## Precomputed Tables
+//!
+//! As in `ragu_pasta`, the crate feature `precompute` builds a
+//! [`FixedBaseTable`] for each curve in [`Secp::generate`], and
+//! [`SecpParams::precompute`] builds them on demand.
+
+#![no_std]
+#![allow(rustdoc::broken_intra_doc_links)]
+#![deny(missing_docs)]
+#![deny(unsafe_code)]
+#![doc(html_favicon_url = "https://tachyon.z.cash/assets/ragu/v1/favicon-32x32.png")]
+#![doc(html_logo_url = "https://tachyon.z.cash/assets/ragu/v1/rustdoc-128x128.png")]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+
+mod curves;
+mod fields;
+#[cfg(test)]
+mod grain;
+mod params;
+mod poseidon_fp;
+mod poseidon_fq;
+
+use arithmetic::{Cycle, FixedBaseTable, FixedGenerators};
+
+pub use curves::{CompressedPoint, Secp256k1, Secp256k1Affine, Secq256k1, Secq256k1Affine};
+pub use fields::{Fp, Fq};
+pub use params::{Secp256k1Generators, SecpParams, Secq256k1Generators};
+pub use poseidon_fp::PoseidonFp;
+pub use poseidon_fq::PoseidonFq;
+
+/// Zero-sized marker type for the secp256k1/secq256k1 curve cycle.
+///
+/// Runtime parameters are stored in [`SecpParams`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Secp;
+
+impl Cycle for Secp {
+    type CircuitField = Fp;
+    type ScalarField = Fq;
+    type NestedCurve = Secp256k1Affine;
+    type HostCurve = Secq256k1Affine;
+
+    type HostGenerators = Secq256k1Generators;
+    type NestedGenerators = Secp256k1Generators;
+
+    type CircuitPoseidon = PoseidonFp;
+    type ScalarPoseidon = PoseidonFq;
+
+    type Params = SecpParams;
+
+    fn host_generators(params: &Self::Params) -> &Self::HostGenerators {
+        &params.secq
+    }
+
+    fn nested_generators(params: &Self::Params) -> &Self::NestedGenerators {
+        &params.secp
+    }
+
+    fn circuit_poseidon(_params: &Self::Params) -> &Self::CircuitPoseidon {
+        &PoseidonFp
+    }
+
+    fn scalar_poseidon(_params: &Self::Params) -> &Self::ScalarPoseidon {
+        &PoseidonFq
+    }
+
+    fn generate() -> Self::Params {
+        #[allow(unused_mut)]
+        let mut params = SecpParams::generate();
+        #[cfg(feature = "precompute")]
+        params.precompute();
+        params
+    }
+}
+
+impl SecpParams {
+    /// Precomputes [`FixedBaseTable`]s for the generators of both curves,
+    /// which are then used to compute commitments.
+    pub fn precompute(&mut self) {
+        self.secp.precompute();
+        self.secq.precompute();
+    }
+}
+
+impl FixedGenerators<Secp256k1Affine> for Secp256k1Generators {
+    fn g(&self) -> &[Secp256k1Affine] {
+        &self.g
+    }
+
+    fn h(&self) -> &Secp256k1Affine {
+        &self.h
+    }
+
+    fn table(&self) -> Option<&FixedBaseTable<Secp256k1Affine>> {
+        self.table.as_ref()
+    }
+}
+
+impl Secp256k1Generators {
+    fn precompute(&mut self) {
+        if self.table.is_none() {
+            self.table = Some(FixedBaseTable::new(self.g.iter().chain(Some(&self.h))));
+        }
+    }
+}
+
+impl FixedGenerators<Secq256k1Affine> for Secq256k1Generators {
+    fn g(&self) -> &[Secq256k1Affine] {
+        &self.g
+    }
+
+    fn h(&self) -> &Secq256k1Affine {
+        &self.h
+    }
+
+    fn table(&self) -> Option<&FixedBaseTable<Secq256k1Affine>> {
+        self.table.as_ref()
+    }
+}
+
+impl Secq256k1Generators {
+    fn precompute(&mut self) {
+        if self.table.is_none() {
+            self.table = Some(FixedBaseTable::new(self.g.iter().chain(Some(&self.h))));
+        }
+    }
+}
+
+#[test]
+fn test_generate_with_k() {
+    let small = SecpParams::generate_with_k(3);
+    let large = SecpParams::generate_with_k(4);
+
+    assert_eq!(small.secp.g.len(), 8);
+    assert_eq!(small.secq.g.len(), 8);
+    assert_eq!(large.secp.g.len(), 16);
+    assert_eq!(small.secp.g[..], large.secp.g[..8]);
+    assert_eq!(small.secq.g[..], large.secq.g[..8]);
+    assert_eq!(small.secp.h, large.secp.h);
+    assert_eq!(small.secq.h, large.secq.h);
+}
+
+#[test]
+fn test_precompute() {
+    let mut params = SecpParams::generate_with_k(6);
+    let coeffs: alloc::vec::Vec<Fq> = (0..40u64).map(|i| Fq::from(i * i + 7)).collect();
+    let terms = || (3..).zip(coeffs.iter());
+    let blind = Fq::from(1234);
+
+    let expected = params.secp.commit(terms(), &blind);
+    assert!(params.secp.table().is_none());
+
+    params.precompute();
+    assert_eq!(params.secp.table().unwrap().len(), 65);
+    assert_eq!(params.secp.commit(terms(), &blind), expected);
+}
+
+#[test]
+fn test_commit() {
+    use group::prime::PrimeCurveAffine;
+
+    // The multiscalar multiplication decomposes scalars with the curve
+    // endomorphism, so compare it against plain scalar multiplication.
+    let params = SecpParams::generate_with_k(4);
+    let coeffs: alloc::vec::Vec<Fp> = (0..16u64).map(|i| -Fp::from(i * 31 + 5)).collect();
+    let blind = Fp::from(99);
+
+    let expected = params
+        .secq
+        .g
+        .iter()
+        .zip(coeffs.iter())
+        .fold(params.secq.h * blind, |acc, (g, c)| acc + g.to_curve() * c);
+    assert_eq!(
+        params.secq.commit(coeffs.iter().enumerate(), &blind),
+        expected
+    );
+}
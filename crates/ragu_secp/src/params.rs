@@ -0,0 +1,92 @@
+use alloc::{vec, vec::Vec};
+
+use arithmetic::{CurveExt, FixedBaseTable};
+use group::{Curve, prime::PrimeCurveAffine};
+
+use crate::{Secp256k1, Secp256k1Affine, Secq256k1, Secq256k1Affine};
+
+const DOMAIN_PREFIX: &str = "Ragu-Parameters";
+
+const DEFAULT_K: usize = 13;
+
+/// Runtime parameters for the secp256k1/secq256k1 curve cycle, holding
+/// generators.
+pub struct SecpParams {
+    pub(crate) secp: Secp256k1Generators,
+    pub(crate) secq: Secq256k1Generators,
+}
+
+/// Fixed generators for the secp256k1 curve.
+pub struct Secp256k1Generators {
+    pub(crate) g: Vec<Secp256k1Affine>,
+    pub(crate) h: Secp256k1Affine,
+    pub(crate) table: Option<FixedBaseTable<Secp256k1Affine>>,
+}
+
+/// Fixed generators for the secq256k1 curve.
+pub struct Secq256k1Generators {
+    pub(crate) g: Vec<Secq256k1Affine>,
+    pub(crate) h: Secq256k1Affine,
+    pub(crate) table: Option<FixedBaseTable<Secq256k1Affine>>,
+}
+
+/// The hash-to-curve message for the `i`th `g` generator.
+fn g_message(i: u32) -> [u8; 5] {
+    let mut message = [0u8; 5];
+    message[1..5].copy_from_slice(&i.to_le_bytes());
+    message
+}
+
+/// The hash-to-curve message for the `h` generator.
+const H_MESSAGE: &[u8] = &[1];
+
+fn params_for_curve<C: CurveExt>(n: usize) -> (Vec<C::AffineExt>, C::AffineExt) {
+    let g_projective = {
+        let hasher = C::hash_to_curve(DOMAIN_PREFIX);
+        let mut g = Vec::with_capacity(n);
+        for i in 0..(n as u32) {
+            g.push(hasher(&g_message(i)));
+        }
+        g
+    };
+    let mut g = vec![C::AffineExt::identity(); n];
+    Curve::batch_normalize(&g_projective[..], &mut g);
+
+    let h: C::AffineExt = C::hash_to_curve(DOMAIN_PREFIX)(H_MESSAGE).into();
+
+    (g, h)
+}
+
+impl SecpParams {
+    /// Generate parameters at runtime via hash-to-curve.
+    pub(crate) fn generate() -> Self {
+        Self::generate_with_k(DEFAULT_K)
+    }
+
+    /// Generate parameters at runtime via hash-to-curve, with $2^k$
+    /// generators for each curve.
+    ///
+    /// This supports committing to polynomials with up to $2^k$ coefficients,
+    /// and thus polynomial ranks up to `R<k>`. The generators are always a
+    /// prefix of those generated for any larger `k`.
+    ///
+    /// The result has no precomputed tables, even with the `precompute`
+    /// feature; see [`SecpParams::precompute`].
+    pub fn generate_with_k(k: usize) -> Self {
+        let (secp_g, secp_h) = params_for_curve::<Secp256k1>(1usize << k);
+        let (secq_g, secq_h) = params_for_curve::<Secq256k1>(1usize << k);
+
+        SecpParams {
+            secp: Secp256k1Generators {
+                g: secp_g,
+                h: secp_h,
+                table: None,
+            },
+            secq: Secq256k1Generators {
+                g: secq_g,
+                h: secq_h,
+                table: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,512 @@
+/// Poseidon permutation parameters for the base field of secp256k1
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PoseidonFp;
+
+// Generated with the Grain LFSR of the Poseidon reference implementation
+// sage generate_parameters_grain.sage 1 0 256 5 8 56 0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f
+// which the tests in `grain` regenerate. The round numbers (8 and 56) are
+// those given by calc_round_numbers.py for the state size 5 and the use of
+// x^5 for the sbox.
+impl arithmetic::PoseidonPermutation<crate::Fp> for PoseidonFp {
+    const T: usize = 5;
+    const RATE: usize = 4;
+    const FULL_ROUNDS: usize = 8;
+    const PARTIAL_ROUNDS: usize = 56;
+    const ALPHA: isize = 5;
+
+    fn round_constants(&self) -> impl Iterator<Item = &[crate::Fp]> {
+        ROUND_CONSTANTS.iter().copied()
+    }
+    fn mds_matrix(&self) -> impl ExactSizeIterator<Item = &[crate::Fp]> {
+        MDS_MATRIX.iter().copied()
+    }
+}
+
+pub(crate) const ROUND_CONSTANTS: &[&[crate::Fp]] = &[
+    &[
+        fp!(0x8312aef880c5538892886fdc30a94568ed9cb41c8f6af6da634f0e589ce0a03c),
+        fp!(0x4e1de0912894c3282a9ae7ddbfe08d7b6c1822b6c233d9a297812971abb12409),
+        fp!(0xfd21c5a15494b1e2855e5281c9df2d18b5ef3b351cc4b048cb82ecdc2c762538),
+        fp!(0xcfb0dcfdfb17ee1e841867904ead60b8adc097df572489ed723d99692b882936),
+        fp!(0xcf9174f6c275832f430ab224c6b2ad038ed5a2eeeafef5be4fe10d52c3ae954e),
+    ],
+    &[
+        fp!(0x74f3d18a8e066ca570ca40d08240d41b51638e6bbe21277c24cb6e74dceeb5d1),
+        fp!(0xe0fa8acb039d93e14b9bfcb75d9ce0376ce3b3b8ac0e458fc79818cae6a7facb),
+        fp!(0xf035636a3e92033d7f615778108fd603a2625fc939494af96ff5c2d8cf1794a9),
+        fp!(0x63fc49ccf54224231b135fe8bd33b68fcc71876345ada27268c0a5386d744675),
+        fp!(0xc37bcefe43baf0bd185fcc7007d732b4ce1427a78305067848638535c90d3804),
+    ],
+    &[
+        fp!(0x7b2afbbee0eafb1c3b690cc75ebcbcd65c050a5d5ae634e59ea1636bae19ad68),
+        fp!(0x3b15659ca64faedc55b86f71f69c4903459f8bb23669bc0447a31eba5fee21c1),
+        fp!(0xc1ed643a94a8fc77d95692246da3267fc8d334833cfa6f30ee4bf3856190eccf),
+        fp!(0xfb54ae4151dcd838b6270b1e1079d0059e3268fe583a951b9b92c505cb2cd147),
+        fp!(0xd3a5fb9ca8c824d622224c05f1298ad95ed2012b451627d28067fdb7fc63fd69),
+    ],
+    &[
+        fp!(0xa5f683adf989ef295d7df38b972d6a6025f161176a7583863be7fd77e3c86701),
+        fp!(0xeaf4490777f70057c4ffd62f109fa6b0d7dcb2173563bce594082a2e045382e0),
+        fp!(0xa3f054db277f898664fb0156d21ec31b6ff4d671954301a875c62f19ce985a6e),
+        fp!(0x3ed3721c75235d90ac0dd1ac89d90a2af9d5987eccb7d1bfd977c969348664f2),
+        fp!(0xd5d7a7d7c3e4449a5e8a0c3b6b617c66c1932eeea5a2eefd7219adce92076a1d),
+    ],
+    &[
+        fp!(0x38548de1463b515f3f29eac66098e9d79f494b1e67bbd552ad61c099642e7ea6),
+        fp!(0x7d4f821087829755a3ebea712cdc1c9417715a99a891d50755ea0475ca6c5117),
+        fp!(0xc9b432a0b144bfd112ad4651ccfa8c00abdd31d119d02116c6920ea3ff76f187),
+        fp!(0x18c06eb5e24eb18e7bf4aee3a7fc3c6bbe7857a36f0799ae942c53baa80cca3e),
+        fp!(0x2d8bd25bc95c60daeec0d0b52eeea65240cfddbcac90ca2c3e4594dbad6d3406),
+    ],
+    &[
+        fp!(0x5d9d94862028dd7a133e4fc70ad4286042d699490076ba767758453e9eb9e31b),
+        fp!(0x48ccdf0d206badd9b5ee9c547c373465a0917b20533670a12ee53d17033bb7cb),
+        fp!(0x150cde00cc7ab6252ee2dd945755eddca70193a3cf0b734f0665ab6cab0fb539),
+        fp!(0x88b9fd02766efa3435e2fcfc18d1c1bb52622591de4c413f9e2a81ffe2d269e5),
+        fp!(0x09707c667f618bf1aa7fc6eac637e23ae714b1c997abbb62664a7efdc12a7e99),
+    ],
+    &[
+        fp!(0xd830ac0be52beefd743f7345ae7b9166e5bf409bbe442fad252573d0808a2335),
+        fp!(0xd2d79e06432013f7dd9064a66938cf73f93f2a0a443add5e19e6c8392756bec1),
+        fp!(0xa3529427ebd8dbbdfa988785be8a84fb45bdff3e5ccbfb2fa2d17fd4fa427c6f),
+        fp!(0xae0b07fd5d53e0bf6a2b5f9e5ea515a0660e1cc884c6824c56c7096088073e24),
+        fp!(0x8857b133e593bd69d3b01b5a9281e8a43d36e77dc72777143f1a07f2a0599906),
+    ],
+    &[
+        fp!(0x65ced7907078db3bda32d42277bebabff7ae21ba00bb3b3376e59bf0f9450c2b),
+        fp!(0x9d9155f0cd55a9e3304a776f68a70c66f1093ccad533248bf118012fb7396fbb),
+        fp!(0x9c94f9754e4301c6cd9aa4b1b1c3f6834cee3c66856d15b476b559a1aa927388),
+        fp!(0x8d909b7703cb5b07de42d27e623c80e5109c03518404edc32134d993da9e2a4d),
+        fp!(0x5e577fdc953e86278077ea6e8eaf5be1587554fe623ac79d3338a3853d8918f2),
+    ],
+    &[
+        fp!(0x2fa547f92589012f5d6c7d043f50f85ee2e5f9039a968b9c0b89f27789d9afec),
+        fp!(0x6094edf934ef7290408e803a5cc739fb9171f5fe5682a786048301a26b216844),
+        fp!(0xbcb7aa7f94cb7bfd3e7b93e87accbb5358ddc204f58557078f25228f5812077c),
+        fp!(0xe6b1e43108b0478552653a43bee07f29b31fa4eae4b9c46e98caa51f438a9ecf),
+        fp!(0x085ff72c8f05b1e5c27bce1e7238d2112dbf3d2b83206abf43228675fd0f3f1b),
+    ],
+    &[
+        fp!(0x971d2797298f13947bc8e16cf9d84e95e690ca1670e3912d2255c71896a197b3),
+        fp!(0xa422dba1bca32827bd48578823bf38e9d24a8224abbe181429a6bc53192da8a5),
+        fp!(0x8f793405a31795c50d02af651a691d67d61e5869e5e54217458f2be350a6ca20),
+        fp!(0x22f23da949279bddd9dbf231a55d5d5ad4e98531fa92c5feb5a6e84e98b30daf),
+        fp!(0xcd6e5fd9a56ce11220e3eaeb5a453d8d4acf7fd77b2b386fb63bea5c2c4c6040),
+    ],
+    &[
+        fp!(0xc021be1a374167e57d278f8425bd06240f1ff6639118e70001c28451417b0468),
+        fp!(0xc8978e3de1067c2852d7e0c2d9d2328e885855228723c7d43b8e41a2251d21b3),
+        fp!(0x9a007abab4a8d800eefaca67efe745b586fcd92d037e1ac925dee6efdff5279e),
+        fp!(0x5e7a8585939dd02a7c269a5f38b4e671c9979ae1426de2c37a2914c23fd2274e),
+        fp!(0xed6102dba23a98241baa26a4772b3111c7f559865b24248b984e0731695cd2cd),
+    ],
+    &[
+        fp!(0x50337364b9267a35598b737db038224c9c640ce152898067b2dda5ed5e740e74),
+        fp!(0xcaf388a02989306aecf9e0ed3b736f357afa8e288ad1a85717177b26a7be8d21),
+        fp!(0xe9a22bc0edae56034543d26d9f1e9dc911e9843617ee24ceaad96b127d403e50),
+        fp!(0x02daa06fd30633fb38871fedefbd8350c8a7bd37a7dcf9379336dc3133884a67),
+        fp!(0x47556e0e1c248c0c2d2d116377877cb90c4e2158e6a614665fab8f961d57f482),
+    ],
+    &[
+        fp!(0x87736d7c6565707f174849822bb3b03ea13fc6aca23dd8a88b3927229d3fdeb3),
+        fp!(0xf2f457d7de80c3a5eebb2244915ff6cc9f5f9954cf704059cbc619d3e898462f),
+        fp!(0x046807fee1c56155e18969073e9e7f1c6666b1008770b213098e7bbf89073f78),
+        fp!(0x7429da0cf08c1e009cd317c68bc6d7f22a2513b3e75d3321602be94dffa9cfd1),
+        fp!(0x12a30b24b0963501418b4315a3cb437e980c2d77c23611f0488a8b9b62601391),
+    ],
+    &[
+        fp!(0x346e210b1c7d01b2c890d8dedbb54a1232b97242ec4bae77fb61b32e9689b69e),
+        fp!(0x744ff7b4981aa34ad2df3dc3bb84a244f67fe0e93713b55c51a1c1368a88384d),
+        fp!(0x6ca14a96a0d935b60b0f2a49f15a229389fa561519b5999704b5e03115ed6b74),
+        fp!(0xc0bf9bc0db4941b1e06491835a93e724bedb011e3d31394ae36bceb18e392a0c),
+        fp!(0x08cac775b0b8657da1dd5bf4c9022e976e9fe4ad48beefb4656cfe57c90863a7),
+    ],
+    &[
+        fp!(0x960755f48720c84a608a4ebdfc81a9d29e8b86cf93cd766ed93c48247f0486c4),
+        fp!(0x81f0109c20adc8489b70299694ecc39a1d65f015f3f04294dbdf3942940cd0c1),
+        fp!(0x5b88a6e5f46fe9803088dd7c2d479b0a8e34d32c81c62eb20d56c4ec59ad095c),
+        fp!(0x653942a6711d4f9963a5d219ca9de505eeb40e4b915b197f6cd71444411ecc52),
+        fp!(0xc54d2cf2e42d424898e8d7a0169c4d64be27e03893dec4c4f3d799de29345a76),
+    ],
+    &[
+        fp!(0x81e3ffc978efbc113b3d3229d3fb8eb3913814e0b06e2a2cc8fb7afd1b72f1ab),
+        fp!(0x3619fb96bd01a9e4b2ac61cdd07528d501ca646d6434da127b029f1bc7ea4e20),
+        fp!(0xb15bab3dda8d4858a07b3a5333559ff542de9b65963b1bac7d063c1f25dee858),
+        fp!(0x0f371637ab4026428d67d1812d633020c573c1c80a10d3b3fddcb4eb414f4b66),
+        fp!(0x3c348a6212dfb943b9483c4bc36d84dcfd767721e3c14c75b2fe6566d57959ee),
+    ],
+    &[
+        fp!(0x4df017473ed894a4b1e52554969a1c44a468461123287399442820f8768367ee),
+        fp!(0x653180b14dfd17a804c42052d77a66527f96ad5e76da3900568b74a830f645ac),
+        fp!(0xccae0ba78eddc3a670fc17964afc5700a092bb9a7abf51a5d5c4802b55ede48c),
+        fp!(0x3cf0be7312e06ec4ab59b09d2f04f2c086fc8e3a1aa232d36d04cd54383bb50b),
+        fp!(0x8433816529b1719b05fb9fef6ccbe7fb85a5a549af2b7c4aa11c00d3799cdcc0),
+    ],
+    &[
+        fp!(0x486cb9b21056a062fa3b0a85c00230cb12c74e5dae77227279b024114792c652),
+        fp!(0x3ac5c840f5a49a2b21e36c582941c360ac8c92291e9ed9db61ce24739ee1cd4d),
+        fp!(0x83bb50386ce91be15cb53cb4bd8f2ecf82340fa23ea6c5dc72a29b0a00a669ed),
+        fp!(0xcc9d1d4a4f5d63430923c686e7670d24ef92a1a8d06dba41dec55d8091c21550),
+        fp!(0xd3067ebb275b10bb6cd77a9ea3d2e2cc29b7fce8f6e41a8074c295e29d641445),
+    ],
+    &[
+        fp!(0x20f83f011a0778e151d5604395dc5d52c81771e124079f57764f17983bc4064d),
+        fp!(0x2bebf18dcfad6d08fd92ec80b22c94589cfa83243b12190f9307c3c8f34c3a1a),
+        fp!(0x705c14b0a82918ba18ffe1b2d1422b4ba47c8e1fdaf73f188524833a69513a5b),
+        fp!(0xdb314677d9f91d936587460664f521d3648af0ec4d9da15554909ed79f1c1e67),
+        fp!(0x9da52bd7bd97fc2416b32c30f0710e45db071a95bac1ff0d7c9dda9651b5a7cc),
+    ],
+    &[
+        fp!(0x8449160ba897c4c580fb18eff6a1ee9f82524f74d0731fd5fff9b8fb96934332),
+        fp!(0x2e5fc1ab0988fbfe57fad11f32df0e8704337533088f72537895adfff75a44fe),
+        fp!(0x173d5e1849ca04888e5c0b4d470cb0f20f6ac51d5b4edf9bfff3be15f5284130),
+        fp!(0xa51609a709821028fa74898bae8d7de68ce8abe26cc99d07194b16993b0577f0),
+        fp!(0xfea5a60aaf21d9c6b3427a667937aef3fe0adb879fe41f5784c5fa526ace9a23),
+    ],
+    &[
+        fp!(0x25947b6ed1c83e6db38ac0e80d78df06475f3ed7dce55e97dbb048976cbc6c90),
+        fp!(0x75677a1236d0ca98efc785877169b42b8695c01de63e790da22f9809eded898f),
+        fp!(0x47fb3aa3ecc78a1820d58c733cde40e7967f87f41a5732b0ffd6243eb35d1926),
+        fp!(0x5713bb5d92c9cbee53c03b6e3e6acd2ef15bdfcdc16a096f68c86b7229752ec1),
+        fp!(0x1b1272dc09e2e556473b1719c21fa2e2c415c5c0ed22875fadd451649472b364),
+    ],
+    &[
+        fp!(0xb2f408ea036b5c230a60def126f16d855c44434ad373c28ef64b3acc2f6a2fd1),
+        fp!(0x649975168d1fc8af960c6515528f9c642849820118d9d7b4ccb0156725943ed5),
+        fp!(0x157ce92363a9836ebc727c482d147621519000af78b27afc484237524960a92b),
+        fp!(0x3be2fc57c9a90c0567b95fc70e6f8062dc0d937dc6566abc5f9c815778922a22),
+        fp!(0x03b42cbad744e7b6f83cfe3b1fa78984886722ae141f198fa13f9bd0900a8281),
+    ],
+    &[
+        fp!(0x105ddd5e0e790b32bcfc528efbc1227804440ebd37b4e91d0efa4b3f67588ebb),
+        fp!(0x0308f611f36376ad6b84a3b8d535217d921aa176b2ce232dde35400654b58c92),
+        fp!(0xb05afb6289239ba40907919cad0f96182509e74e2d28abcac0f36d3d274a3e69),
+        fp!(0x29bb425bd053097d99508405a07857fc32ba0b0c6b3209aacf63b48dd1f90770),
+        fp!(0xcc15867f082ede73d0608e2f5275659cec042c701166d549bf9b8b607a15c429),
+    ],
+    &[
+        fp!(0x06019d306deccee7c0668f158b7716d4389af13df961aea4b0fa195b1cb1646c),
+        fp!(0x4bd43c201ddc6680495ce1417da58013618ca32e96f68c143e38fe59162a9e52),
+        fp!(0x912c85583942dfbb9876434fb061bbef600ab607ac5cc723eb3d797e8ae1a7ad),
+        fp!(0x4e3e7207255e09c863346656004eb8b60b942cd7ca1d2f38db594d0dcd6b681f),
+        fp!(0xb4553ca3a17ddd50e5a28fcc7202ac963d98ea9aa31637961e208dcfe99e2c9f),
+    ],
+    &[
+        fp!(0x47feaacfca8f4809212dc26fd4b6b23ae0796deacd52036f5f6f7d8d95afb316),
+        fp!(0xaeb4c8c052b016de1831e8fe56e952cb4d3ff38572d5f3e5fdb9ec9fa673b8cf),
+        fp!(0x3fe07fe96116e2f9183027aa59c81fad8770cbc25acaa975d0df2bafa11c11d0),
+        fp!(0x4c83a5e3d9f19ffb67c305fe027044dbdae99934bece2aa04951e43f8b13e54b),
+        fp!(0x5f911a8fbb92191837cc187f889ab3486285ed6443753c59a5316affe40a1192),
+    ],
+    &[
+        fp!(0x49d807f44d84d7131c7eb6f832e1841e0ff9476e444f2d887ce3e451ea004e3a),
+        fp!(0xa29c41ae038765e401c0e961cc91177a0b78ab9ff02c1bf31d2c8b54d0ff85db),
+        fp!(0x2a01f2065bdb20925171de060c8c15e8996bd9146938aa96dcf3ae702ccd1136),
+        fp!(0x8d7b208659c70944ef33467b1da9e7c237090b1a8fa64abc83e10c163bef27c2),
+        fp!(0xb4f42af2a02e4bf93bdadcb98db888b3ef5805c5bed3fda5cb4edad31c73411a),
+    ],
+    &[
+        fp!(0x58e072a9b1ca0d413f317b463c0639bed3c8e4576974780202588fffafbb0902),
+        fp!(0xacd25f23283be4e04bb3b5c954668a6acc4c2187f03481e435647aa7b691b96c),
+        fp!(0x05632146d174ccb0cea1c7815a6e9058980650b039eb45c4696701f072532f86),
+        fp!(0x036dab6bf8daf95229c5c460a360e40a86c68969d49d712fed8077d507abef84),
+        fp!(0x1cfc1941e12dd63eca46c3c6fb99bf731f71d34cb647384ae61ada6629a27f5d),
+    ],
+    &[
+        fp!(0x7d68fde06f419b615833305904fb27f28cfd506795082e3b847bc3ccd55a52b2),
+        fp!(0xf87737bce37bc098cb5d0cdee679ad13606a3521e692eda3eea99f516fe6d114),
+        fp!(0xf17985b4a8d22293b30b7229dc11e112ab77c4912972acd5aa486572b438626c),
+        fp!(0x558a960dc7211e7ae606ab811a169262f8704ab77991e51979324d0747a89b8c),
+        fp!(0x715980722b7a4b7483207e3e769c35da7c9b780737ead7b686a52173c2f8374c),
+    ],
+    &[
+        fp!(0xedd2f977b8ee4f4c7c3d633e14bd4163a898348aa0254999d538cccb2a7e13f2),
+        fp!(0x4801e72e58ab458eecec278b4376e040efa12ed2bfb2cedd43f4e47bbf095839),
+        fp!(0x62f5c678e101f54761b1559d82f4c39a129d1e4f490a5dbaf3ebd3c81834747e),
+        fp!(0xba17ed2fa0a27bd6045340dcebddf51e5acd32b6f39bf08daa0e6f7e801c26b8),
+        fp!(0x143e1b61a6965b4bded1b66c832cd1302c3c626480b35791cb895014d3d437a3),
+    ],
+    &[
+        fp!(0x2c8ece3620f44d37d3dad5ff45213ffd2aeb5001f4c5baac3b5884a25a65070b),
+        fp!(0x0649239707aab323835257071baecd413213059b33ef18796b77be4c7c76774f),
+        fp!(0x1a5789b3b8c2a4b031d11c160aa4c7af7436e7d13f70fd8bf63257fd2eee6af5),
+        fp!(0xadb8a206e4790e6ca71eb381aefed982929900f051d081ff866872179be252d8),
+        fp!(0xc231d3300ad06e2e6efb0f3d51dbd63e8ea965644b65089c746b8e52913b5db5),
+    ],
+    &[
+        fp!(0x389c58c4308acae3372986874fe76cd13fdba532c0d6f636131e2668667bb984),
+        fp!(0x3c89a878b978065632e37348b9d85b80316c5bc861eda76633496d483f6ded78),
+        fp!(0xede68c24b48f16a2659af7e3db5788e38ff9b8df615c6726f8f290b7229e0731),
+        fp!(0x3dbe8a5a002eb5fc439ae16174a2318443b6ef88bc1d6905a805ed11fa31a828),
+        fp!(0xb32cc704515ddfb806fe9e41e3d095e3f8b0e2fa177b22fb05ac29d71a33ea73),
+    ],
+    &[
+        fp!(0x2e62a94973257dc2d65966ac81c37a5558e23aed6361828679a97cd31b0b8a1c),
+        fp!(0x9d727bd3b055716da577f5b115a7a90d3b24bebda27d3ee1a4f8368ede26376d),
+        fp!(0xd999aeb82427e265c9e28d333702140c24c00d22e9f68da84d85af2ea182c802),
+        fp!(0x149ab679d32696f654ecc663484e8709cc9b14b69491bb43241ec828d97d27be),
+        fp!(0xc7a1980f83ee5e84522d0c97abf9edc1a8616c2ad05cda52fdce97d2528644ba),
+    ],
+    &[
+        fp!(0x094a312da2e4e4909543108c49282b18213fe566831801ea7f68d05240fcad96),
+        fp!(0x7c1bdafb717725993107e6f5a39a6f4d4028c82536186e62ff0e808ced7b1bd1),
+        fp!(0x08cc138792da9241a920aa4331e6807ca7b0e97bb2e0e58ee7dddb122803e8d9),
+        fp!(0xdd6f13f70d79db1aadedca1615bed0d1d5e15caaa53212257fb7af0ad890c355),
+        fp!(0xd6a53de3ada67479aa664214919320e10e1a0d6d675c3c20bef351c8285b16b2),
+    ],
+    &[
+        fp!(0xa36d92a354f9632f7121301016da7b388145afddf8274af41785dad43069b5a7),
+        fp!(0x6a75e47de00bcdb38d5c5acbcbf76497e36cfdf0ca7981d7d3f7f1ef0d26c4c5),
+        fp!(0x060beea9527ea4cc2b0aadcf295c83666c86b2ffc72218efd159a396d21e2457),
+        fp!(0xe2b67a144e971db9c4b1a0eaed605a4e431153a9d084f435fbb8c1e234612491),
+        fp!(0xdeb0799640d5c1e698e5ef3110b6a2842e9f8b72d716a8c2576a2f5877a8b9f8),
+    ],
+    &[
+        fp!(0xe3cf914061253bca9903de17c1024183e461e78a43b7b90b06e3c46bd15dc99d),
+        fp!(0x6533390b217ee5615b001c4bb10227e14196d898f4cace634441dd3c555837a3),
+        fp!(0x125a36becb46a468f49a64ffffd068fc10064c3c41657216aa1c22ee02ef8279),
+        fp!(0x7aacfea9991a8ac25854dce1b6eb160345dc64db2d543b0599b1e20ed08d29e9),
+        fp!(0x0ff633152d819661f29a644b88a2a2d162d3f6c263c76b627239198910f697a2),
+    ],
+    &[
+        fp!(0x3348080909984a049d6e6dfc9d8f7ee865207a0a770e8cfce31f1a512143a8f1),
+        fp!(0xb41be928a7aa50b299dec3bca2684299c23ada653fe87e18f95f70d1152b6827),
+        fp!(0xa4fb63211bee2a6fdc146088744ecba73fa6ba7e6d3c3e19e4b4d6f73269f3e9),
+        fp!(0x621797637c54dc484ae2c43c89337b6a9447129e88e9009d368850be40d02daa),
+        fp!(0xcc04029ead651d92c0d17874811a0923b72435d28f07fc306e2670460fa1c917),
+    ],
+    &[
+        fp!(0xeba07da6b5a95c6163007a25b3377be30fc07c8c7db7fb244165f22f804679f0),
+        fp!(0x0dbd588f7294d47b6aa7d3c2b23a2f521f04ac4fbf4ed29de9b99cbd6a17c965),
+        fp!(0xef157e730ab044328156ad87a7086f28bfb668f4b4af9fd79a3e066bac1bd941),
+        fp!(0x8cc21fdca4b44ca86d65258cb83cdd23dde50686c3ef6251aebfe04ef7a77956),
+        fp!(0xcf5a4f7edcadd1df21f814d458916d3cd516d11afcb6ebfb2c93866250a63a89),
+    ],
+    &[
+        fp!(0x0d8c2604deecacb0b11f9f4255e057b2ae87b2b04daadc099226a4577cc2ba0f),
+        fp!(0x960f500d80fc1eae2bb9beb0d09058dca7df54ec190b10bbf45d9c0cb1350ef2),
+        fp!(0x34117f040628fe864e7a70f3bc9ad4b6d2ad497a60b29ae19bdef685dd3914a3),
+        fp!(0xe2be9f6794c380bcfde999e784556d8fca24cb2f35609ecd5eb010e210f3cf6b),
+        fp!(0x8b79110381a8c1f8037f0df7b26919de769396c74e386f1044e46dfff7b6dac2),
+    ],
+    &[
+        fp!(0x866361a9d6ec29381a7ad698c94e2b1dedc8e69300c60c0a57402157f109efa1),
+        fp!(0x1d4f318b9a7540794697edc7dfa3601863b34b8aad18690dd1bff2755df6a8f2),
+        fp!(0x83716f08282b610f43b25571eff3111211cd17cc6108d434b96542157fe0de30),
+        fp!(0xf66b4271318cc26b200c4d29afc35e5bbf226bd1d645400a64cdd3cb76edd266),
+        fp!(0x444f4dbcdd5397acfd2f00d51e54b14dbdda817b383c65e68f2721483f003e1a),
+    ],
+    &[
+        fp!(0x58322aee067039b87d37b0753ce693cd06d4dd6e7980086e95e41b2b4b590519),
+        fp!(0x25844d1b983c03cdbd8d9ebaa2d698110554fe1eae88fa91cc9f994ef996fc9f),
+        fp!(0xd28abb15df7faa5a9321b624ce9c4e95532d7ad157fb457b5cb9b30a74bc479b),
+        fp!(0x0ea0905918dae71f62cdbf3ffc65ac9ba0001e5ae3523b5ce7ef09ea2ad0a061),
+        fp!(0x61cebe9d3b45bfad12ed78e46a11f97b8fd9af75cbcc443805286309c3926f4c),
+    ],
+    &[
+        fp!(0xfcf230a833fbd2e2a61f376c10c0eceec75eb9071c47c0b25e3c8bb164cab217),
+        fp!(0x8defb539567a4a542adc223b5d5497b584bb5d8d4514f896126a7ed6d5ee5d75),
+        fp!(0xc54eec2311cfe505a33184dd0389f917da04239f529b58e7b0cc067811c74a05),
+        fp!(0x96b1fd81f6874c0395bc8d105802f68c34703594c64f06180b03df72c2f9f767),
+        fp!(0x351eb656ddff0042cef6c2713c5bf0c6683295648db5158ce4eba58139f4bc7b),
+    ],
+    &[
+        fp!(0xfef97ba3bf57b8658b605820cca97ae8cdf7e7bd38a2260aa2ecdbcc911773d8),
+        fp!(0x9577e3b9292400313b27f2fe454ec09f7394f5c24ebe73aa8f95da5d904e9c99),
+        fp!(0x71eedb29bdb4937a8c16e206471fdbdd284afa0bdc53ca1a88351b69b21703a9),
+        fp!(0xe729f5d55b459b0b34c0a2616c6e1176965530111fac3550273c2ed8d2f2b42f),
+        fp!(0xa67848425b448a4b32f7439160f590acf87c2bed69e32ba4af6c22268d30c652),
+    ],
+    &[
+        fp!(0xb4a74ce8950ff35bba35c1a9228bfa0e0dbe23af13e7b4a6e2cdae54a579f3f2),
+        fp!(0x4270a01951771f74dc469469110516f4417fbc549b316816088dd13f54a9bd63),
+        fp!(0x93b42daee68217cc800c6237edc929e2b618efae30f2898482af9f8818e3fc17),
+        fp!(0xcbf57fd00eddf26ccf01c74e59f4ea4c819b1fee6d74e3e9878a1ae93b6b5cf3),
+        fp!(0x6333f7f672882f0b8b355bd3c45f4c419e921db982502aea0609f1a7874d4fa9),
+    ],
+    &[
+        fp!(0xdae10cdf100e41d07a923ce023595164d66417dc7b03c1432e7918b79ddd5c9b),
+        fp!(0xd126ee3c02fc5695386b8740678d111f84402b350444b13de9f2075105b69941),
+        fp!(0x1b9bc065751aa51ab59ff2176c52bc7eba953dba6a53268c9a4dcf6f023141c0),
+        fp!(0xb0679bf54a16ada81fec9d2f6a7ad940bb7dd0ba410125403492450fe6cd9aa7),
+        fp!(0x083b7205e0ad9a8cee0fc3407a7fb01976a7c992382e9d9cb8383b4797ebea6c),
+    ],
+    &[
+        fp!(0xe1cf1634403fce82a6f3bcaa78aec1893e1b7fd6279281e7ac2a09c8e7545aa5),
+        fp!(0x8c679e4b56739747e5a8bbb77499d18dbc2e0e7bb2da154a1340692ce3f20b27),
+        fp!(0x76a17ebfafca2ed5f3ad5e9fe631224909dfedb285c40f383f14f95d4e6a7f49),
+        fp!(0xb045ffa18945ba9035d4afeba47a7410214aa65dbdee20ec1dd6007fcef27367),
+        fp!(0xaa7a0da28b298d4f657cdbccfd90498c634ed4ced5537cf53648306fffc384fc),
+    ],
+    &[
+        fp!(0x214a702b95b56966d163b8c2ad65754a076adbc669efdd2daa5504d3efcc3922),
+        fp!(0x0c6df13c964e64fbcbf8fc46440ae7ef06b28b5637053f12bbb8a058c3edc595),
+        fp!(0x7149f228622edb61e15ef83e1415230d4dbabcd16327eb4edfc40042cc53be0c),
+        fp!(0x14f6a22f01bf98b4a14db169ecd77ba8cace746864ae4208eee639a21f12227b),
+        fp!(0x07e879a49a63235dcd4d9ec181f23cfa9d2585c24f3c43ec3e83e8d7b24390d9),
+    ],
+    &[
+        fp!(0xf765114d33b848f53695a205ffeb52ad6767e82fe24bba61719892e15315cc3a),
+        fp!(0x3219afae80e80c0a8c6ae5c339e8ad98e2271cc3f13a4509b41da7309cfb396f),
+        fp!(0x97601afbc2fdb361bd3b229609b31855f905c167191ff54eb0af5ed5b9cda630),
+        fp!(0x64da8cfb935e1120817b357df1aa1d108b712f336c068930726880b3236ffaff),
+        fp!(0x50c70e083f063949ff6cae480916e1c0e11731e4d0d47d1a631118ecf418855b),
+    ],
+    &[
+        fp!(0x1660330d34d7ed24cc42d40b760e62a0ad7b7226826117e657a357e25ba820bf),
+        fp!(0x31ca16f23c12dc7f6413a1cf183f18d6d8c8145291fbdf059ed40b836028d179),
+        fp!(0x2c56ba809dee7e43cb01361751a6c4b30cf875ab2059873878e212100a23952c),
+        fp!(0x31610eac1ed724816cdc56afd785c1a64867692f92ea4754ea9c9967624486d8),
+        fp!(0xcb7531f6a07f66a6fe11efebef66a178b813ee8e40223d166359807c9dbaf794),
+    ],
+    &[
+        fp!(0x36d740d66c24ed51ce324eb344407c3304fac431f57e251ab9edc7a7fe5c2ef3),
+        fp!(0xb0f77686c93116476b7496b0e8bec9c14447327e6b82d00ae4140a84c43dba24),
+        fp!(0x27924dec8d84e10dfd167e3f8d368ab1b0c1a7069c60272bb5cd603dc36661af),
+        fp!(0x5dc3c8aa365cb89ca8393ce3e8360d2e4ef4ae94e00027803ff9c8e17313d1b6),
+        fp!(0xa6bb4764839ec0e7353c8dc7698571f290908945aa3963499e0d1dc65f67978b),
+    ],
+    &[
+        fp!(0x7109e8a6da3177b270d87643a14c5c6eabf7ef8b44d53b144260af9486a1d8b3),
+        fp!(0x651fac19a60957ba33da2963aa01a50ef2ee081886a6a32079ab2beb4a7c43ec),
+        fp!(0xfed80064fc19d17e7ed91ee50780117f4a8d8be8414d31a19a9f7c8a78203101),
+        fp!(0xeaf42b43fdb3c7d0f273ded9eab20910696264e6c8244145c42d890408165069),
+        fp!(0xd1feb4943ed90638505bcda622fe3591640e468c138abf4f61abdbcbf118257f),
+    ],
+    &[
+        fp!(0xbdfc9805d88bf7759f19a05b1775d9cd5f087bd9db3112a3bb395e02837b4faa),
+        fp!(0x0282065f2f23ee0f847091b598a9e13a942b833651484e5265289f0218dbda31),
+        fp!(0xadcada5a302fe8584aa044c8fb2bfed3d6a5e7f71e221ada057167eb92397cb2),
+        fp!(0x985b1dbcc9362ebc3be470b046ec58d156bcc6e49b0b51d669289b512b76d906),
+        fp!(0xe61541384d4987c548575b01aecb3ecfb53fd61d77388c242b4b9621b686ecfb),
+    ],
+    &[
+        fp!(0x960fdf9aaad035e1a589a8e34217a20960c504b793bb0624bedca61791903d07),
+        fp!(0xa18257a434ad7e28f8d54f638a38b3951420ee80acc90a8f5897e55f38f6adae),
+        fp!(0x567a672f0d005aa7aea65e3ae77f16e48504841cc9e00d379173f5ef5b7ff06d),
+        fp!(0x91cd1a46ffea4b779716c84d818074f34a983e4842b1e83b50ac7c08b3d9b7d3),
+        fp!(0xab0ef74dd4d24567a01b118357249dd3ab3221ff260810f8b8f80f7269fa4618),
+    ],
+    &[
+        fp!(0x3261afe5eaabfb9d9864f1f943b641076766852c808ed4704aa096d7a40f49ab),
+        fp!(0xc6158be279a128f0f377ea21a5145739e6bdf5ab3b9300805aa34f86b3c703af),
+        fp!(0xe65aa7f0abea963b6463f402e2f317706776795acfa12e43609c2708b9def835),
+        fp!(0x3a47c3db76fdc43ea03efd4bfe40baa81fac22fb0169d0de8930bd45c7a8ad2f),
+        fp!(0xffdb6c3fe7d061cb373f5d312be00bac73f0ae0e1ac56636e573c8025f3c4158),
+    ],
+    &[
+        fp!(0x86d26c8d7a1f5c6a290c6a500d58631352713a9c529279e650c7b2bfa3fc9d2b),
+        fp!(0x356a2529d854707cd1fc20f601514d7649bd338b2312d5bae7aa852340e92aaf),
+        fp!(0xa8696477bf9d042cc7812f9a3e6ee344edf51919297cad14ad935cdf8b19629f),
+        fp!(0x7aa0ffeb52624d91052a7ed9f90a8c0fe259b00fe5e17270e9127d803c83db84),
+        fp!(0xc9b0c3da95bbe3e9ca62bb78ab3fd79c2e092f396782153c6f86f28c995fb749),
+    ],
+    &[
+        fp!(0x8ca2a75d75678697dd84c27693b6904c0671c0ae5b9fe355222b19b7fda7cc8a),
+        fp!(0x5c23fa58d7812e910e4311958f2ba615c71db2dbb4698fe6f287e9bdc59c36f3),
+        fp!(0x7f16c6b651532c97e197c5b0c082a2e1d566ed63ba41df7f4070915bff729c5b),
+        fp!(0x503ec3732d8a5d7191c57099b4b4210b88da89a4d22eee63ea952ecec829f88a),
+        fp!(0x9bc85a8ea44c4d36ceeeb88dafd4676a4aa09a3ece9f7e74941a64c42f7ed360),
+    ],
+    &[
+        fp!(0x2e4ad7809c6884ee56c2295731856bb8ca08e60614b8c1612cdb692827181eb4),
+        fp!(0x43c2f449886530a56a46e90a755402ff7740c891e2672abf4bc99d03680cd5f5),
+        fp!(0x83e16017f9358704a0506de464e405eb386494fd76285f7751ceba3625177126),
+        fp!(0x3e5518baece5d0ad814f4c694c5dfbf730582d414301ed8855baab044058364d),
+        fp!(0x7f1b1e896e39909281fa7cc17e1efb29d3384292bdcafcdc4725bddab0b487fa),
+    ],
+    &[
+        fp!(0xcc0b054a03d629c3f301f0c20888041245db35a17c6919358484fa5de037c8db),
+        fp!(0xa037b23d7403aebd5e1d1317a9c420d8143ab2a852365487708bae9f91232c98),
+        fp!(0x981b583a43493bbcec2982e24a79f4a3417fc17d53323515f3c448167fb7ee73),
+        fp!(0x5624f654e1c61b19518c5a2de0d0075d020422af273aced26a877874cfeaccc1),
+        fp!(0xb2c780eeb6ceea0e5aec88fa1901475001fa4160542673efc48a8228bb39875c),
+    ],
+    &[
+        fp!(0xc9a87340ea614b450612919caa81945a113a769e1df1e513021af4c9730d7fa9),
+        fp!(0x8f0980ccb86c8fe97170ea9e1bf1c5f65c786f77d254c2e43e9e96b198946d2f),
+        fp!(0x91bd85c69899ca4f5e04fa551de4e73d791c1144b166201ad4203b57808c02a6),
+        fp!(0xe060211f994d7adf14d73d36deb52e2f9f595adf593cbd9fb71849d59d13a562),
+        fp!(0x664e874602ca7ef76b146a8e4d961107e38cecfb1cdbaa9b6c86a211c751fd36),
+    ],
+    &[
+        fp!(0xfc3810c7bda9f6bf39a3c26d147bcad270809be1d84651bc5172f92f0fb93b59),
+        fp!(0x9d6bf4f2f874b3c84e6de3cbc88fc507bc5cad18bb15898bcc1ce37135aff81f),
+        fp!(0xde545cc108a008a4272bdc189c6bf3153d8919479b79e08ebfc7ee9d80e1b121),
+        fp!(0x8dbcead52ea22f161131047bab4c1d4b9f9c476922b6a32c3ba8499423479649),
+        fp!(0xae73c583cabe8f943118676d99cf88ccc8beaf3f4b4e3aaf5636c13d26061603),
+    ],
+    &[
+        fp!(0x7364a6f64a90c43a191c3dc26f2a9c4883fe7baffe142224413a900d6e7416f2),
+        fp!(0x158c99c95bf8594fef81f6e3815876514f2ecb4ffb8b0bf42d0c95e8cbd2e404),
+        fp!(0xc2177aacf52801ffe8ed8485014cf936b068866224af9ca7feb9c3f97e993e81),
+        fp!(0x2eb2344f8116e227530da0b3098f07a1846ff7e0741b08e1d323d50c18baf7ac),
+        fp!(0xa6a2fd026534a9675c5aa88edc32470dec90ccfab830905901527e59d903e3d6),
+    ],
+    &[
+        fp!(0xd0a5d73d006f1ef34f39fed7991481188ab7e10bff763161331e6300a6c898b7),
+        fp!(0x00c6b44dba7ec49f5361870e8050b061e7b71da21b3428b341799c5d12cbcd7e),
+        fp!(0x848d3306cfcde355992b13d14741b47121c246c99962748309ab2c0e6c71c40d),
+        fp!(0xa79ae3e9cb408d7b46dbd20ce5b5cf1e3f5173faa94fbb18e4e00abf2346b896),
+        fp!(0x1ec7d52c956d5b97834fb2a7eb84574b8d35003c17e1ea2afb678a95dc0b3ff1),
+    ],
+    &[
+        fp!(0x39d5b877a9640545ff04289598fa7a1c2fde155998583c223ee2017302ed882c),
+        fp!(0x510c60e0f7e289b0a3896b3c498a9b3eef12f726f4373c706c6b4e0dcff8c2d1),
+        fp!(0xf32e7033d0cc7832aea8a55d9ab3e5d3b0cb13cbbad06b09cb8db48b79d5e07e),
+        fp!(0x0a7f3503bf3e871bded3befe99247a2c19180c95c5618389c1f54ea5151cecab),
+        fp!(0xfd70a80a13690c7deb5c59f1df7fa5cedd72420a300f09ed7ffc655379131d25),
+    ],
+    &[
+        fp!(0xdf5ed55351c6315e0f20abdf5f0985bd9355170a616c83b77ddeef3b64b5bb02),
+        fp!(0x7bda490588d2c7713013e863c6b14e997873feb65949fa232a7132c2def20c5e),
+        fp!(0x7706143689c4314fe82c2e12c8e724062901d5724697987909031891302e7c8b),
+        fp!(0xeed412e90170d2e837690fcb2dc654774481bca904002fb55e8a835cea4afd7d),
+        fp!(0x624ccba3b5745d3b4635d53a16aed0376c53a97f8960f3c95e23ab5f0860fac6),
+    ],
+    &[
+        fp!(0xe92e3021fde9f41e8e259601ad0d4971a7a0c99f45f5d5851dab24915948dd8f),
+        fp!(0xb71e77d4e8a80cdcbcfc6ea24604a63940e6f8a68a0ed9c5d201519290cf282a),
+        fp!(0x24c4ea643d76633004ede26a16ec95e0b3f9897e876fc7512da65331ed6b04a9),
+        fp!(0xfa716c1844596cf3dbf229cd2867d515321d1104bc261bfbad7463e4db293216),
+        fp!(0x76705a309e54698f7b9b87ab8fc74ab597df5050c0deb309b4dbc01cf8f695d4),
+    ],
+];
+
+pub(crate) const MDS_MATRIX: &[&[crate::Fp]] = &[
+    &[
+        fp!(0x712ad61f54db34a05d7979a488b45f5aeabd7796f96287f416a1e69fc5aeff2c),
+        fp!(0x8512c84c46e0fdeb7dc385fcc8e44176f3dd8cec271177ddf869145e36e3042b),
+        fp!(0x7c854077cfa4754c6a5b04b295799de3724853ffeae7749a508749c570043922),
+        fp!(0x3e107aaa8cd05895b9a8a2189493619417b0d8bbe9c0ff44ffc03bcf6bb95b89),
+        fp!(0xc51c1f34bf4c3b9b0a068fc678d706ffb13107e7b79128ef0be31c865ce39b8d),
+    ],
+    &[
+        fp!(0xaea4623021b506e1b7b462a828fbc3168aa10ee971f4a93e9670af5630e84fe0),
+        fp!(0xaea4a8423ed3bbcc9f0510762a74cab82fb47cc1e186c4cf83c8da79cf72276c),
+        fp!(0x74b8446de2db3a08065667abbaa12c0e679f6649efc22acb930dd3735dc047b3),
+        fp!(0xc9237e843edd84e03ef0a63e4fed1b01cb53cf0dec38620699fb8a2d85ffc4c4),
+        fp!(0xe1597a39e0850a5cfd705dc73b655cad37bd451d721fff9824e039e7d6a8f07e),
+    ],
+    &[
+        fp!(0xffc247ddbddd2a408828f5112b46d038a2fd2ef3763674ee2dd1aba852ed3e4e),
+        fp!(0xec18f47a8f611aa027c11e8d60d5edb81ac57939d98378f59d2a63299c20017e),
+        fp!(0xeda8ea402a6c2eb1a139894d7935f94c5a4429f5b6f3a27faff85fc4126930bc),
+        fp!(0x34002328f7acaedc4dbb058d837dcd16d7239e9933feb007e24ef9804c1a2372),
+        fp!(0x1c8707b60f8fcb1130cbb039844ad3266b7a68f89c1dc49372ba8b1f2e345eb0),
+    ],
+    &[
+        fp!(0x3d841b03e571edf6f5ec842ebaca889cb85b69528ec26ce58790e44a457a6aa0),
+        fp!(0x3444478c1af9305d2bb7a862673c0ea9d7fed64848eab8297a5a581629e83113),
+        fp!(0x60800f837e9287a624fa6df5425fda3927d2f335bd40ad4458d802a875943f99),
+        fp!(0x06dec8996484eb4c0dbb714e1872b96c2077924f5c496e9f7896caa2988af197),
+        fp!(0x99c0de968f8ee93505084ed72d2913830e776f07b77b529cb1d57465496f9f5b),
+    ],
+    &[
+        fp!(0x564582290db51c8cafd4a72b93e365e678600b88b374d84438251d76a4f06a0c),
+        fp!(0x1a884cc8288d1bd9c1e6d36bf9007600446df4fa8455b76a86338b7393c91b12),
+        fp!(0xd22e5e4cc40900383181be52f605aefc294d38360fa1d4fe21c2bbf57ca3957e),
+        fp!(0xa008e12d860bbf1855850b59e070992441b72dadb6f841be996cfb2ad7d32117),
+        fp!(0x716f89146f7221fd432f4826f7ad2d321e898d811894a312459e3bef4ecaaf4d),
+    ],
+];